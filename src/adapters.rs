@@ -0,0 +1,285 @@
+//! A [`Stack`] and a [`Queue`], that only expose the ends of a list they are meant to use.
+
+use core::{
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use crate::{doublylist, doublylist::DoublyList, list, list::List};
+
+/// A last in, first out stack, built on a [`List`].
+///
+/// Items are pushed onto and popped off the top,
+/// and there is no way to reach the bottom of the stack by mistake.
+///
+/// # Examples
+/// ```
+/// # use lists::adapters::Stack;
+/// let mut stack = Stack::new();
+/// stack.push(1);
+/// stack.push(2);
+///
+/// assert_eq!(stack.peek(), Some(&2));
+/// assert_eq!(stack.pop(), Some(2));
+/// assert_eq!(stack.pop(), Some(1));
+/// assert_eq!(stack.pop(), None);
+/// ```
+pub struct Stack<T> {
+    list: List<T>,
+}
+
+impl<T> Default for Stack<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for Stack<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<T: Clone> Clone for Stack<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+        }
+    }
+}
+
+impl<T> Stack<T> {
+    /// Returns a new empty stack.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { list: List::new() }
+    }
+
+    /// Returns `true` if the stack is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Pushes `item` onto the top of the stack.
+    #[inline(always)]
+    pub fn push(&mut self, item: T) {
+        self.list.push(item);
+    }
+
+    /// Removes the top item of the stack, and returns it.
+    #[inline(always)]
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    /// Returns the top item of the stack.
+    #[inline(always)]
+    pub fn peek(&self) -> Option<&T> {
+        self.list.peek()
+    }
+
+    /// Returns a mutable reference to the top item of the stack.
+    #[inline(always)]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_mut()
+    }
+
+    /// Removes all items from the stack.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Returns an iterator over the items, from the top of the stack down.
+    #[inline(always)]
+    pub fn iter(&self) -> list::Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Returns the list holding the items, with the top of the stack at its head.
+    #[inline(always)]
+    pub fn into_inner(self) -> List<T> {
+        self.list
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    /// Pushes the items onto the stack in order, so the last one ends up on top.
+    #[inline(always)]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            list: List::from_iter(iter),
+        }
+    }
+}
+
+/// A first in, first out queue, built on a [`DoublyList`].
+///
+/// Items are enqueued at the back and dequeued from the front,
+/// and there is no way to take from the back by mistake.
+///
+/// # Examples
+/// ```
+/// # use lists::adapters::Queue;
+/// let mut queue = Queue::new();
+/// queue.enqueue(1);
+/// queue.enqueue(2);
+///
+/// assert_eq!(queue.len(), 2);
+/// assert_eq!(queue.peek(), Some(&1));
+/// assert_eq!(queue.dequeue(), Some(1));
+/// assert_eq!(queue.dequeue(), Some(2));
+/// assert_eq!(queue.dequeue(), None);
+/// ```
+pub struct Queue<T> {
+    list: DoublyList<T>,
+}
+
+impl<T> Default for Queue<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for Queue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<T: Clone> Clone for Queue<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+        }
+    }
+}
+
+impl<T> From<DoublyList<T>> for Queue<T> {
+    /// Returns a queue of the items of `list`, with its front item dequeued first.
+    #[inline(always)]
+    fn from(list: DoublyList<T>) -> Self {
+        Self { list }
+    }
+}
+
+impl<T> Queue<T> {
+    /// Returns a new empty queue.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            list: DoublyList::new(),
+        }
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Adds `item` to the back of the queue.
+    #[inline(always)]
+    pub fn enqueue(&mut self, item: T) {
+        self.list.push_back(item);
+    }
+
+    /// Removes the item at the front of the queue, which was enqueued first, and returns it.
+    #[inline(always)]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// Returns the item at the front of the queue.
+    #[inline(always)]
+    pub fn peek(&self) -> Option<&T> {
+        self.list.peek_front()
+    }
+
+    /// Returns a mutable reference to the item at the front of the queue.
+    #[inline(always)]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_front_mut()
+    }
+
+    /// Removes all items from the queue.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Returns an iterator over the items, in the order they will be dequeued.
+    #[inline(always)]
+    pub fn iter(&self) -> doublylist::Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Returns the list holding the items, with the front of the queue at its front.
+    #[inline(always)]
+    pub fn into_inner(self) -> DoublyList<T> {
+        self.list
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    /// Enqueues the items in order.
+    #[inline(always)]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            list: DoublyList::from_iter(iter),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ends() {
+        let mut stack = (0..3).collect::<Stack<u32>>();
+        let mut queue = (0..3).collect::<Queue<u32>>();
+
+        stack.push(3);
+        queue.enqueue(3);
+        assert!(stack.iter().eq(&[3, 2, 1, 0]));
+        assert!(queue.iter().eq(&[0, 1, 2, 3]));
+
+        *stack.peek_mut().unwrap() += 10;
+        *queue.peek_mut().unwrap() += 10;
+        assert_eq!(stack.pop(), Some(13));
+        assert_eq!(queue.dequeue(), Some(10));
+
+        stack.extend([4, 5]);
+        queue.extend([4, 5]);
+        assert_eq!(stack.into_inner(), [5, 4, 2, 1, 0]);
+        assert_eq!(queue.into_inner(), [1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Generic algorithms over any [`LinkedCollection`].
+//!
+//! The algorithms that rearrange a list move its nodes around,
+//! so no item is ever reallocated, cloned or moved out of its node.
+
+use core::mem;
+
+use crate::collection::LinkedCollection;
+
+/// Pops every node of `list`, front to back, and hands it to `f`.
+///
+/// The nodes are not deallocated, so `f` can push them into another list.
+#[inline]
+pub fn for_each_node<T, L, F>(list: &mut L, mut f: F)
+where
+    L: LinkedCollection<T>,
+    F: FnMut(Box<L::Node>),
+{
+    while let Some(node) = list.pop_node() {
+        f(node)
+    }
+}
+
+/// Reverses the order of the items in `list`.
+#[inline]
+pub fn reverse<T, L>(list: &mut L)
+where
+    L: LinkedCollection<T> + Default,
+{
+    let mut reversed = L::default();
+    for_each_node(list, |node| reversed.push_node(node));
+    *list = reversed;
+}
+
+/// Returns the first non-`None` result of `f` applied to the items of `list`, front to back.
+#[inline]
+pub fn find_map<T, L, B, F>(list: &L, f: F) -> Option<B>
+where
+    L: LinkedCollection<T>,
+    F: FnMut(&T) -> Option<B>,
+{
+    list.iter().find_map(f)
+}
+
+/// Merges two sorted lists into a single sorted list.
+///
+/// The merge is stable: of two equal items, the one of `left` comes first.
+pub fn merge_sorted<T, L>(mut left: L, mut right: L) -> L
+where
+    T: Ord,
+    L: LinkedCollection<T> + Default,
+{
+    let mut merged = L::default();
+
+    loop {
+        let source = match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) if l <= r => &mut left,
+            (Some(_), Some(_)) | (None, Some(_)) => &mut right,
+            (Some(_), None) => &mut left,
+            (None, None) => break,
+        };
+
+        if let Some(node) = source.pop_node() {
+            merged.push_node(node);
+        }
+    }
+
+    reverse(&mut merged);
+    merged
+}
+
+/// Splits `list` right before the first item for which `pred` returns `true`.
+///
+/// `list` keeps the items before that item,
+/// and the returned list holds that item and all items after it.
+/// If `pred` returns `false` for every item, the returned list is empty.
+pub fn split_when<T, L, F>(list: &mut L, mut pred: F) -> L
+where
+    L: LinkedCollection<T> + Default,
+    F: FnMut(&T) -> bool,
+{
+    let mut prefix = L::default();
+
+    while list.peek().is_some_and(|item| !pred(item)) {
+        if let Some(node) = list.pop_node() {
+            prefix.push_node(node);
+        }
+    }
+
+    reverse(&mut prefix);
+    mem::replace(list, prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{doublylist::DoublyList, list::List};
+
+    fn collect<L: LinkedCollection<u32> + Default>(items: &[u32]) -> L {
+        let mut list = L::default();
+        items.iter().rev().for_each(|&item| list.push(item));
+        list
+    }
+
+    fn exercise<L: LinkedCollection<u32> + Default>() {
+        let mut list = collect::<L>(&[1, 4, 6, 9]);
+        let merged = merge_sorted(list, collect(&[2, 4, 5, 10, 11]));
+        assert!(merged.iter().eq(&[1, 2, 4, 4, 5, 6, 9, 10, 11]));
+
+        list = merged;
+        let mut tail = split_when(&mut list, |item| *item > 5);
+        assert!(list.iter().eq(&[1, 2, 4, 4, 5]));
+        assert!(tail.iter().eq(&[6, 9, 10, 11]));
+
+        reverse(&mut tail);
+        assert!(tail.iter().eq(&[11, 10, 9, 6]));
+        assert_eq!(
+            find_map(&tail, |item| (item % 3 == 0).then_some(item * 2)),
+            Some(18)
+        );
+
+        assert!(split_when(&mut tail, |_| false).is_empty());
+        assert_eq!(tail.len(), 4);
+
+        let mut moved = L::default();
+        for_each_node(&mut tail, |node| moved.push_node(node));
+        assert!(tail.is_empty());
+        assert!(moved.iter().eq(&[6, 9, 10, 11]));
+    }
+
+    #[test]
+    fn test_both_backends() {
+        exercise::<List<_>>();
+        exercise::<DoublyList<_>>();
+    }
+}
@@ -0,0 +1,392 @@
+//! A bounded async queue, built on [`BoundedDoublyList`].
+
+use core::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{
+    bounded::BoundedDoublyList,
+    doublylist::{DoublyList, NodeRef},
+};
+
+/*
+ * Like in `AsyncQueue`, every pending future owns a node in a list of waiters,
+ * holding its most recent waker, and only the future itself unlinks that node.
+ * Pushes wait in `pushers`, and pops wait in `poppers`.
+ *
+ * Whenever the queue changes, or a waiter goes away, the front pusher is woken
+ * if there is room left, and the front popper is woken if there are items left.
+ * A woken waiter that finds the queue full or empty again keeps its place,
+ * so no wakeup is lost, and waiters are served in the order they started waiting.
+ */
+struct Inner<T> {
+    items: BoundedDoublyList<T>,
+    pushers: DoublyList<Waker>,
+    poppers: DoublyList<Waker>,
+}
+
+impl<T> Inner<T> {
+    /// Returns the wakers of the front pusher and the front popper,
+    /// if they can make progress.
+    #[inline]
+    fn next_wakers(&self) -> [Option<Waker>; 2] {
+        let pusher = if self.items.is_full() {
+            None
+        } else {
+            self.pushers.peek_front().cloned()
+        };
+
+        let popper = if self.items.is_empty() {
+            None
+        } else {
+            self.poppers.peek_front().cloned()
+        };
+
+        [pusher, popper]
+    }
+}
+
+#[inline(always)]
+fn wake_all([pusher, popper]: [Option<Waker>; 2]) {
+    pusher.into_iter().chain(popper).for_each(Waker::wake);
+}
+
+/// A queue holding at most a fixed number of items,
+/// where pushing waits while the queue is full, and popping waits while it is empty.
+///
+/// The waiting pushers hold back fast producers,
+/// which makes the queue usable as a simple async channel with backpressure,
+/// without depending on any runtime.
+///
+/// A queue with a limit of 0 never accepts an item, so pushing waits forever.
+///
+/// # Examples
+/// ```
+/// # use lists::async_bounded::AsyncBoundedQueue;
+/// let queue = AsyncBoundedQueue::new(1);
+///
+/// assert_eq!(queue.try_push(1), Ok(()));
+/// assert_eq!(queue.try_push(2), Err(2));
+/// assert_eq!(queue.try_pop(), Some(1));
+/// assert_eq!(queue.try_pop(), None);
+/// ```
+pub struct AsyncBoundedQueue<T> {
+    inner: Mutex<Inner<T>>,
+}
+
+impl<T> Debug for AsyncBoundedQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = self.lock();
+
+        f.debug_struct("AsyncBoundedQueue")
+            .field("len", &inner.items.len())
+            .field("limit", &inner.items.limit())
+            .finish()
+    }
+}
+
+impl<T> AsyncBoundedQueue<T> {
+    /// Returns a new empty queue, that holds at most `limit` items.
+    #[inline(always)]
+    pub const fn new(limit: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                items: BoundedDoublyList::new(limit),
+                pushers: DoublyList::new(),
+                poppers: DoublyList::new(),
+            }),
+        }
+    }
+
+    // As in `AsyncQueue`, a panic while the lock is held leaves every list intact.
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Inner<T>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    /// Returns `true` if the queue holds no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    /// Returns `true` if the queue holds as many items as it can.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.lock().items.is_full()
+    }
+
+    /// Returns the most items the queue holds.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.lock().items.limit()
+    }
+
+    /// Pushes `item` to the back of the queue, or gives it back if the queue is full.
+    pub fn try_push(&self, item: T) -> Result<(), T> {
+        let wakers = {
+            let mut inner = self.lock();
+            inner.items.push_back(item)?;
+            inner.next_wakers()
+        };
+
+        wake_all(wakers);
+        Ok(())
+    }
+
+    /// Pops the front item of the queue, if there is one.
+    pub fn try_pop(&self) -> Option<T> {
+        let (item, wakers) = {
+            let mut inner = self.lock();
+            let item = inner.items.pop_front()?;
+            (item, inner.next_wakers())
+        };
+
+        wake_all(wakers);
+        Some(item)
+    }
+
+    /// Returns a future that pushes `item` to the back of the queue,
+    /// waiting for room if the queue is full.
+    ///
+    /// Dropping the future before it completes drops `item`.
+    #[inline(always)]
+    pub fn push(&self, item: T) -> Push<'_, T> {
+        Push {
+            queue: self,
+            item: Some(item),
+            waiter: None,
+        }
+    }
+
+    /// Returns a future that pops the front item of the queue,
+    /// waiting for an item to be pushed if the queue is empty.
+    #[inline(always)]
+    pub fn pop(&self) -> Pop<'_, T> {
+        Pop {
+            queue: self,
+            waiter: None,
+        }
+    }
+}
+
+/// A future pushing an item to an [`AsyncBoundedQueue`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Push<'a, T> {
+    queue: &'a AsyncBoundedQueue<T>,
+    item: Option<T>,
+    waiter: Option<NodeRef<Waker>>,
+}
+
+// The item is moved into the queue, and never pinned.
+impl<T> Unpin for Push<'_, T> {}
+
+impl<T> Debug for Push<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Push")
+            .field("waiting", &self.waiter.is_some())
+            .finish()
+    }
+}
+
+impl<T> Future for Push<'_, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut inner = this.queue.lock();
+
+        if inner.items.is_full() {
+            match this.waiter {
+                // The node is ours, and still linked.
+                Some(waiter) => unsafe {
+                    let waker = inner.pushers.get_mut(waiter);
+
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                },
+                None => this.waiter = Some(inner.pushers.push_back_ref(cx.waker().clone())),
+            }
+
+            return Poll::Pending;
+        }
+
+        let item = this.item.take().expect("`Push` polled after completion");
+        // The queue was just checked to have room.
+        let _ = inner.items.push_back(item);
+
+        if let Some(waiter) = this.waiter.take() {
+            // The node is ours, and still linked.
+            unsafe { inner.pushers.remove(waiter) };
+        }
+
+        let wakers = inner.next_wakers();
+        drop(inner);
+
+        wake_all(wakers);
+        Poll::Ready(())
+    }
+}
+
+impl<T> Drop for Push<'_, T> {
+    fn drop(&mut self) {
+        if let Some(waiter) = self.waiter.take() {
+            let wakers = {
+                let mut inner = self.queue.lock();
+
+                // A pending push still owns its node.
+                unsafe { inner.pushers.remove(waiter) };
+                inner.next_wakers()
+            };
+
+            wake_all(wakers);
+        }
+    }
+}
+
+/// A future popping the front item of an [`AsyncBoundedQueue`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Pop<'a, T> {
+    queue: &'a AsyncBoundedQueue<T>,
+    waiter: Option<NodeRef<Waker>>,
+}
+
+impl<T> Debug for Pop<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pop")
+            .field("waiting", &self.waiter.is_some())
+            .finish()
+    }
+}
+
+impl<T> Future for Pop<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut inner = this.queue.lock();
+
+        match inner.items.pop_front() {
+            Some(item) => {
+                if let Some(waiter) = this.waiter.take() {
+                    // The node is ours, and still linked.
+                    unsafe { inner.poppers.remove(waiter) };
+                }
+
+                let wakers = inner.next_wakers();
+                drop(inner);
+
+                wake_all(wakers);
+                Poll::Ready(item)
+            }
+            None => {
+                match this.waiter {
+                    // The node is ours, and still linked.
+                    Some(waiter) => unsafe {
+                        let waker = inner.poppers.get_mut(waiter);
+
+                        if !waker.will_wake(cx.waker()) {
+                            *waker = cx.waker().clone();
+                        }
+                    },
+                    None => this.waiter = Some(inner.poppers.push_back_ref(cx.waker().clone())),
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Pop<'_, T> {
+    fn drop(&mut self) {
+        if let Some(waiter) = self.waiter.take() {
+            let wakers = {
+                let mut inner = self.queue.lock();
+
+                // A pending pop still owns its node.
+                unsafe { inner.poppers.remove(waiter) };
+                inner.next_wakers()
+            };
+
+            wake_all(wakers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{block_on, CountingWaker};
+
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    #[test]
+    fn test_backpressure() {
+        let queue = AsyncBoundedQueue::new(1);
+
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut pop = queue.pop();
+        assert!(Pin::new(&mut pop).poll(&mut cx).is_pending());
+
+        assert_eq!(Pin::new(&mut queue.push(1)).poll(&mut cx), Poll::Ready(()));
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+        let mut push = queue.push(2);
+        assert!(Pin::new(&mut push).poll(&mut cx).is_pending());
+
+        // Popping makes room, and wakes the waiting push.
+        assert_eq!(Pin::new(&mut pop).poll(&mut cx), Poll::Ready(1));
+        assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+
+        assert_eq!(Pin::new(&mut push).poll(&mut cx), Poll::Ready(()));
+        assert_eq!(queue.try_pop(), Some(2));
+
+        let inner = queue.lock();
+        assert!(inner.pushers.is_empty() && inner.poppers.is_empty());
+    }
+
+    #[test]
+    fn test_threads() {
+        let queue = Arc::new(AsyncBoundedQueue::new(4));
+
+        let producers = (0..4)
+            .map(|p| {
+                let queue = queue.clone();
+                thread::spawn(move || (0..250).for_each(|n| block_on(queue.push(p * 250 + n))))
+            })
+            .collect::<Vec<_>>();
+
+        let sum = (0..1000).map(|_| block_on(queue.pop())).sum::<u64>();
+
+        producers
+            .into_iter()
+            .for_each(|producer| producer.join().unwrap());
+
+        assert_eq!(sum, (0..1000).sum::<u64>());
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! An unbounded async queue, built on [`DoublyList`].
+
+use core::{
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::doublylist::{DoublyList, NodeRef};
+
+/*
+ * Every pending `pop_front` future owns a node in the list of waiters,
+ * holding its most recent waker.
+ * A push wakes the waiter at the front of that list, but does not unlink it:
+ * only the future itself unlinks its node, when it completes or is dropped,
+ * so its handle never dangles.
+ *
+ * A waiter that completes while items are left in the queue,
+ * or that is dropped while items are in the queue,
+ * passes the wakeup on to the next waiter, so no wakeup is lost.
+ */
+struct Inner<T> {
+    items: DoublyList<T>,
+    waiters: DoublyList<Waker>,
+}
+
+impl<T> Inner<T> {
+    /// Returns the waker of the front waiter, if there are items left to pop.
+    #[inline(always)]
+    fn next_waker(&self) -> Option<Waker> {
+        if self.items.is_empty() {
+            None
+        } else {
+            self.waiters.peek_front().cloned()
+        }
+    }
+}
+
+/// An unbounded queue, where popping from an empty queue
+/// waits for an item to be pushed.
+///
+/// This makes the queue usable as a simple unbounded async channel,
+/// that can be shared between any number of producers and consumers.
+pub struct AsyncQueue<T> {
+    inner: Mutex<Inner<T>>,
+}
+
+impl<T> Default for AsyncQueue<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for AsyncQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AsyncQueue").field(&self.len()).finish()
+    }
+}
+
+impl<T> AsyncQueue<T> {
+    /// Returns a new empty queue.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                items: DoublyList::new(),
+                waiters: DoublyList::new(),
+            }),
+        }
+    }
+
+    // A panic while the lock is held never leaves the lists half-linked,
+    // so a poisoned lock is safe to use.
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Inner<T>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock().items.len()
+    }
+
+    /// Returns `true` if the queue holds no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lock().items.is_empty()
+    }
+
+    /// Pushes `item` to the back of the queue,
+    /// waking up a task waiting to pop.
+    pub fn push_back(&self, item: T) {
+        let waker = {
+            let mut inner = self.lock();
+            inner.items.push_back(item);
+            inner.next_waker()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Pops the front item of the queue, if there is one.
+    #[inline]
+    pub fn try_pop_front(&self) -> Option<T> {
+        self.lock().items.pop_front()
+    }
+
+    /// Returns a future that pops the front item of the queue,
+    /// waiting for an item to be pushed if the queue is empty.
+    #[inline(always)]
+    pub fn pop_front(&self) -> PopFront<'_, T> {
+        PopFront {
+            queue: self,
+            waiter: None,
+        }
+    }
+}
+
+/// A future popping the front item of an [`AsyncQueue`].
+#[must_use = "futures do nothing unless polled"]
+pub struct PopFront<'a, T> {
+    queue: &'a AsyncQueue<T>,
+    waiter: Option<NodeRef<Waker>>,
+}
+
+impl<T> Debug for PopFront<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PopFront")
+            .field("waiting", &self.waiter.is_some())
+            .finish()
+    }
+}
+
+impl<T> Future for PopFront<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut inner = this.queue.lock();
+
+        match inner.items.pop_front() {
+            Some(item) => {
+                if let Some(waiter) = this.waiter.take() {
+                    // The node is ours, and still linked.
+                    unsafe { inner.waiters.remove(waiter) };
+                }
+
+                let waker = inner.next_waker();
+                drop(inner);
+
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+
+                Poll::Ready(item)
+            }
+            None => {
+                match this.waiter {
+                    // The node is ours, and still linked.
+                    Some(waiter) => unsafe {
+                        let waker = inner.waiters.get_mut(waiter);
+
+                        if !waker.will_wake(cx.waker()) {
+                            *waker = cx.waker().clone();
+                        }
+                    },
+                    None => this.waiter = Some(inner.waiters.push_back_ref(cx.waker().clone())),
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for PopFront<'_, T> {
+    fn drop(&mut self) {
+        if let Some(waiter) = self.waiter.take() {
+            let waker = {
+                let mut inner = self.queue.lock();
+
+                // The node is ours, and still linked.
+                unsafe { inner.waiters.remove(waiter) };
+                inner.next_waker()
+            };
+
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{block_on, CountingWaker};
+
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    #[test]
+    fn test_wakeups() {
+        let queue = AsyncQueue::new();
+
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(counter.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let mut first = queue.pop_front();
+        let mut second = queue.pop_front();
+        assert!(Pin::new(&mut first).poll(&mut cx).is_pending());
+        assert!(Pin::new(&mut second).poll(&mut cx).is_pending());
+
+        queue.push_back(1);
+        queue.push_back(2);
+        assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+
+        // Dropping the woken waiter passes the wakeup on.
+        drop(first);
+        assert_eq!(counter.0.load(Ordering::SeqCst), 3);
+
+        assert_eq!(Pin::new(&mut second).poll(&mut cx), Poll::Ready(1));
+        assert_eq!(queue.try_pop_front(), Some(2));
+        assert!(queue.lock().waiters.is_empty());
+    }
+
+    #[test]
+    fn test_threads() {
+        let queue = Arc::new(AsyncQueue::new());
+
+        let consumers = (0..4)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || (0..250).map(|_| block_on(queue.pop_front())).sum::<u64>())
+            })
+            .collect::<Vec<_>>();
+
+        for item in 0..1000 {
+            queue.push_back(item);
+        }
+
+        let sum = consumers
+            .into_iter()
+            .map(|consumer| consumer.join().unwrap())
+            .sum::<u64>();
+
+        assert_eq!(sum, (0..1000).sum::<u64>());
+        assert!(queue.is_empty());
+    }
+}
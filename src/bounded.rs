@@ -0,0 +1,297 @@
+//! A [`DoublyList`] that holds at most a fixed number of items.
+
+use core::{
+    fmt::{self, Debug},
+    mem,
+};
+
+use crate::doublylist::{DoublyList, Iter, IterMut};
+
+/// A [`DoublyList`] that refuses items once it holds `limit` of them.
+///
+/// Pushing checks the limit, so callers don't have to check
+/// [`len`](BoundedDoublyList::len) themselves before every push.
+///
+/// # Examples
+/// ```
+/// # use lists::bounded::BoundedDoublyList;
+/// let mut queue = BoundedDoublyList::new(2);
+///
+/// assert_eq!(queue.push_back(1), Ok(()));
+/// assert_eq!(queue.push_back(2), Ok(()));
+/// assert_eq!(queue.push_back(3), Err(3));
+///
+/// assert_eq!(queue.pop_front(), Some(1));
+/// assert_eq!(queue.push_back(3), Ok(()));
+/// ```
+pub struct BoundedDoublyList<T> {
+    list: DoublyList<T>,
+    limit: usize,
+}
+
+impl<T: Debug> Debug for BoundedDoublyList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<T: Clone> Clone for BoundedDoublyList<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list.clone(),
+            limit: self.limit,
+        }
+    }
+}
+
+impl<T> BoundedDoublyList<T> {
+    /// Returns a new empty list, that holds at most `limit` items.
+    #[inline(always)]
+    pub const fn new(limit: usize) -> Self {
+        Self {
+            list: DoublyList::new(),
+            limit,
+        }
+    }
+
+    /// Returns `list` with a limit of `limit` items,
+    /// or gives `list` back if it holds more items than that.
+    #[inline]
+    pub fn from_list(list: DoublyList<T>, limit: usize) -> Result<Self, DoublyList<T>> {
+        if list.len() > limit {
+            return Err(list);
+        }
+
+        Ok(Self { list, limit })
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.list.len() == 0
+    }
+
+    /// Returns the most items the list holds.
+    #[inline(always)]
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns `true` if the list refuses any more items.
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.list.len() >= self.limit
+    }
+
+    /// Returns how many more items the list accepts.
+    #[inline(always)]
+    pub const fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.list.len())
+    }
+
+    /// Returns a reference to the underlying list.
+    #[inline(always)]
+    pub const fn as_list(&self) -> &DoublyList<T> {
+        &self.list
+    }
+
+    /// Returns the underlying list.
+    #[inline(always)]
+    pub fn into_inner(self) -> DoublyList<T> {
+        self.list
+    }
+
+    /// Pushes `item` to the front of the list,
+    /// or gives it back if the list is full.
+    #[inline]
+    pub fn push_front(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+
+        self.list.push_front(item);
+        Ok(())
+    }
+
+    /// Pushes `item` to the back of the list,
+    /// or gives it back if the list is full.
+    #[inline]
+    pub fn push_back(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+
+        self.list.push_back(item);
+        Ok(())
+    }
+
+    /// Pushes `item` to the back of the list,
+    /// first removing the front item if the list is full, and returns the removed item.
+    ///
+    /// This keeps the last `limit` items pushed, like a ring buffer.
+    /// The node of the removed item is reused for `item`.
+    /// With a limit of 0, `item` itself is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::bounded::BoundedDoublyList;
+    /// let mut history = BoundedDoublyList::new(3);
+    ///
+    /// for event in ["open", "read", "write"] {
+    ///     assert_eq!(history.push_back_evicting(event), None);
+    /// }
+    ///
+    /// assert_eq!(history.push_back_evicting("close"), Some("open"));
+    /// assert_eq!(*history.as_list(), ["read", "write", "close"]);
+    /// ```
+    pub fn push_back_evicting(&mut self, item: T) -> Option<T> {
+        if !self.is_full() {
+            self.list.push_back(item);
+            return None;
+        }
+
+        match self.list.pop_front_node() {
+            None => Some(item),
+            Some(mut node) => {
+                let evicted = mem::replace(node.item_mut(), item);
+                self.list.push_back_node(node);
+                Some(evicted)
+            }
+        }
+    }
+
+    /// Removes the front item of the list, and returns it.
+    #[inline(always)]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    /// Removes the back item of the list, and returns it.
+    #[inline(always)]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    /// Returns a reference to the front item.
+    #[inline(always)]
+    pub fn peek_front(&self) -> Option<&T> {
+        self.list.peek_front()
+    }
+
+    /// Returns a mutable reference to the front item.
+    #[inline(always)]
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_front_mut()
+    }
+
+    /// Returns a reference to the back item.
+    #[inline(always)]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.list.peek_back()
+    }
+
+    /// Returns a mutable reference to the back item.
+    #[inline(always)]
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        self.list.peek_back_mut()
+    }
+
+    /// Removes all items from the list.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Returns an iterator over the items of the list.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Returns an iterator over mutable references to the items of the list.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.list.iter_mut()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BoundedDoublyList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut BoundedDoublyList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit() {
+        let mut list = BoundedDoublyList::new(3);
+
+        for n in 0..5 {
+            let pushed = if n % 2 == 0 {
+                list.push_back(n)
+            } else {
+                list.push_front(n)
+            };
+
+            assert_eq!(pushed.is_ok(), n < 3);
+        }
+
+        assert!(list.is_full());
+        assert_eq!(list.remaining(), 0);
+        assert_eq!(list.push_front(9), Err(9));
+        assert_eq!(*list.as_list(), [1, 0, 2]);
+
+        list.pop_back();
+        assert_eq!(list.remaining(), 1);
+        assert_eq!(list.push_front(9), Ok(()));
+
+        let list = list.into_inner();
+        assert!(BoundedDoublyList::from_list(list.clone(), 2).is_err());
+        assert!(BoundedDoublyList::from_list(list, 3).unwrap().is_full());
+
+        let mut closed = BoundedDoublyList::new(0);
+        assert_eq!(closed.push_back("item"), Err("item"));
+        assert!(closed.is_empty());
+    }
+
+    #[test]
+    fn test_evicting() {
+        let mut list = BoundedDoublyList::new(4);
+        let evicted = (0..10)
+            .filter_map(|n| list.push_back_evicting(n))
+            .collect::<Vec<_>>();
+
+        assert_eq!(evicted, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(*list.as_list(), [6, 7, 8, 9]);
+        assert_eq!(list.push_back(10), Err(10));
+
+        let mut closed = BoundedDoublyList::new(0);
+        assert_eq!(closed.push_back_evicting(1), Some(1));
+        assert!(closed.is_empty());
+    }
+}
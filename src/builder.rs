@@ -0,0 +1,148 @@
+//! A builder concatenating lists, iterators and items into a [`DoublyList`].
+
+use core::fmt::{self, Debug};
+
+use crate::doublylist::DoublyList;
+
+enum Segment<'a, T> {
+    Item(T),
+    List(DoublyList<T>),
+    Iter(Box<dyn Iterator<Item = T> + 'a>),
+}
+
+/// A builder for a [`DoublyList`], concatenating lists, iterators and single items.
+///
+/// Adding a segment takes amortized O(1), whatever its length:
+/// lists are kept as they are, and iterators are not run
+/// until [`finish`](ListBuilder::finish) builds the list in one pass.
+/// There, every list is linked in as a whole,
+/// so only the items of iterators and single items are allocated.
+///
+/// # Examples
+/// ```
+/// # use lists::{builder::ListBuilder, doublylist::DoublyList};
+/// let middle = (3..6).collect::<DoublyList<u32>>();
+///
+/// let list = ListBuilder::new()
+///     .item(0)
+///     .iter(1..3)
+///     .list(middle)
+///     .item(6)
+///     .finish();
+///
+/// assert_eq!(list, [0, 1, 2, 3, 4, 5, 6]);
+/// ```
+pub struct ListBuilder<'a, T> {
+    segments: Vec<Segment<'a, T>>,
+}
+
+impl<T> Default for ListBuilder<'_, T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for ListBuilder<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ListBuilder")
+            .field("segments", &self.segments.len())
+            .finish()
+    }
+}
+
+impl<'a, T> ListBuilder<'a, T> {
+    /// Returns a new empty builder.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Appends `item`.
+    #[inline]
+    pub fn item(mut self, item: T) -> Self {
+        self.segments.push(Segment::Item(item));
+        self
+    }
+
+    /// Appends the items of `list`, which are linked in without walking them.
+    #[inline]
+    pub fn list(mut self, list: DoublyList<T>) -> Self {
+        self.segments.push(Segment::List(list));
+        self
+    }
+
+    /// Appends the items of `iter`, which is not run until the list is built.
+    #[inline]
+    pub fn iter<I>(mut self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: 'a,
+    {
+        self.segments
+            .push(Segment::Iter(Box::new(iter.into_iter())));
+        self
+    }
+
+    /// Appends the segments of `other`.
+    ///
+    /// This takes O(m), where m is the number of segments of `other`,
+    /// but does not depend on the number of items in them.
+    #[inline]
+    pub fn builder(mut self, other: Self) -> Self {
+        self.segments.extend(other.segments);
+        self
+    }
+
+    /// Builds the list, running the iterators in the order they were appended.
+    pub fn finish(self) -> DoublyList<T> {
+        let mut list = DoublyList::new();
+
+        for segment in self.segments {
+            match segment {
+                Segment::Item(item) => list.push_back(item),
+                Segment::List(mut other) => list.append(&mut other),
+                Segment::Iter(iter) => list.extend(iter),
+            }
+        }
+
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn test_segments() {
+        let ran = Cell::new(false);
+        let words = ["b", "c"];
+
+        let tail = ListBuilder::new()
+            .iter(words.iter().map(|word| {
+                ran.set(true);
+                word.to_string()
+            }))
+            .item("d".to_string());
+
+        let builder = ListBuilder::new()
+            .list(DoublyList::new())
+            .item("a".to_string())
+            .builder(tail)
+            .list(["e", "f"].iter().map(|s| s.to_string()).collect());
+
+        assert!(!ran.get());
+
+        let list = builder.finish();
+        assert!(ran.get());
+        assert_eq!(list, ["a", "b", "c", "d", "e", "f"]);
+        list.assert_invariants();
+
+        assert!(ListBuilder::<u8>::new().finish().is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! A chain of byte buffers, built on a [`DoublyList`] of `[u8]` nodes.
+
+use core::{
+    cmp,
+    fmt::{self, Debug},
+};
+use std::io::{self, BufRead, Read, Write};
+
+use crate::doublylist::{DoublyList, Node};
+
+/// A chain of byte buffers, that is read from the front and written to the back.
+///
+/// Every buffer is stored in a single unsized `Node<[u8]>`,
+/// so appending a whole buffer takes just one allocation,
+/// and appending an already built node takes none.
+/// Consuming from the front only moves an offset into the front buffer,
+/// and frees buffers once they are fully consumed.
+///
+/// # Examples
+/// ```
+/// # use lists::bytelist::ByteList;
+/// use std::io::{BufRead, Read, Write};
+///
+/// let mut bytes = ByteList::new();
+/// bytes.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+/// bytes.write_all(b"Host: example.com\r\n\r\n").unwrap();
+///
+/// let mut line = String::new();
+/// bytes.read_line(&mut line).unwrap();
+/// assert_eq!(line, "GET / HTTP/1.1\r\n");
+///
+/// let mut rest = Vec::new();
+/// bytes.read_to_end(&mut rest).unwrap();
+/// assert_eq!(rest, b"Host: example.com\r\n\r\n");
+/// ```
+#[derive(Default)]
+pub struct ByteList {
+    chunks: DoublyList<[u8]>,
+
+    /// The number of bytes consumed from the front chunk.
+    offset: usize,
+
+    /// The number of bytes left to read.
+    len: usize,
+}
+
+impl Debug for ByteList {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ByteList")
+            .field("len", &self.len)
+            .field("chunks", &self.chunks.len())
+            .finish()
+    }
+}
+
+impl ByteList {
+    /// Returns a new empty list.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            chunks: DoublyList::new(),
+            offset: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes left to read.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bytes left to read.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of buffers in the chain.
+    #[inline(always)]
+    pub const fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Appends a copy of `bytes` to the back, as a single buffer.
+    #[inline]
+    pub fn push_back(&mut self, bytes: &[u8]) {
+        if !bytes.is_empty() {
+            self.push_back_node(Node::boxed_slice(bytes));
+        }
+    }
+
+    /// Appends `node` to the back as a whole, without copying it.
+    #[inline]
+    pub fn push_back_node(&mut self, node: Box<Node<[u8]>>) {
+        let len = node.item().len();
+
+        if len != 0 {
+            self.chunks.push_back_node(node);
+            self.len += len;
+        }
+    }
+
+    /// Returns the unread bytes of the front buffer.
+    #[inline]
+    pub fn front(&self) -> &[u8] {
+        self.chunks
+            .peek_front()
+            .map_or(&[], |chunk| &chunk[self.offset..])
+    }
+
+    /// Discards the first `amount` bytes.
+    ///
+    /// # Panics
+    /// Panics if `amount` is larger than `len()`.
+    pub fn advance(&mut self, mut amount: usize) {
+        assert!(
+            amount <= self.len,
+            "cannot advance past the end of the list"
+        );
+        self.len -= amount;
+
+        while amount != 0 {
+            let left = self.front().len();
+
+            if amount < left {
+                self.offset += amount;
+                return;
+            }
+
+            amount -= left;
+            self.chunks.pop_front_node();
+            self.offset = 0;
+        }
+    }
+
+    /// Returns an iterator over the unread bytes of every buffer, front to back.
+    #[inline]
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let offset = self.offset;
+
+        self.chunks
+            .iter()
+            .enumerate()
+            .map(move |(idx, chunk)| if idx == 0 { &chunk[offset..] } else { chunk })
+    }
+}
+
+impl Read for ByteList {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+
+        while read < buf.len() && !self.is_empty() {
+            let front = self.front();
+            let amount = cmp::min(front.len(), buf.len() - read);
+
+            buf[read..read + amount].copy_from_slice(&front[..amount]);
+            self.advance(amount);
+            read += amount;
+        }
+
+        Ok(read)
+    }
+}
+
+impl BufRead for ByteList {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.front())
+    }
+
+    #[inline]
+    fn consume(&mut self, amount: usize) {
+        self.advance(amount);
+    }
+}
+
+impl Write for ByteList {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push_back(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_across_chunks() {
+        let mut bytes = ByteList::new();
+        bytes.push_back(b"abc");
+        bytes.push_back(b"");
+        bytes.push_back(b"defg");
+        bytes.push_back_node(Node::boxed(*b"hi"));
+        assert_eq!(bytes.chunk_count(), 3);
+
+        let mut buf = [0; 5];
+        assert_eq!(bytes.read(&mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"abcde");
+        assert_eq!(bytes.chunk_count(), 2);
+        assert_eq!(bytes.chunks().collect::<Vec<_>>(), [&b"fg"[..], &b"hi"[..]]);
+
+        bytes.consume(3);
+        assert_eq!(bytes.fill_buf().unwrap(), b"i");
+        assert_eq!(bytes.read(&mut buf).unwrap(), 1);
+        assert_eq!(bytes.read(&mut buf).unwrap(), 0);
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut bytes = ByteList::new();
+        write!(bytes, "one\ntw").unwrap();
+        write!(bytes, "o\nthree").unwrap();
+
+        let lines = bytes.lines().collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(lines, ["one", "two", "three"]);
+    }
+}
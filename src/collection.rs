@@ -0,0 +1,193 @@
+//! A common interface over the lists of this crate.
+
+use crate::{doublylist, doublylist::DoublyList, list, list::List};
+
+/// The operations every list of this crate supports,
+/// so generic code can be written once against either of them.
+///
+/// `push`, `pop` and `peek` all work on the front of the list,
+/// which is also where `iter` starts,
+/// so every implementation behaves like a stack.
+///
+/// The node level operations allow moving items between lists
+/// without reallocating them, see the [`algo`](crate::algo) module.
+///
+/// # Examples
+/// ```
+/// # use lists::{collection::LinkedCollection, doublylist::DoublyList, list::List};
+/// fn fill<L: LinkedCollection<u32> + Default>() -> L {
+///     let mut list = L::default();
+///     list.push(2);
+///     list.push(1);
+///     list
+/// }
+///
+/// assert!(fill::<List<_>>().iter().eq(&[1, 2]));
+/// assert!(fill::<DoublyList<_>>().iter().eq(&[1, 2]));
+/// ```
+pub trait LinkedCollection<T> {
+    /// The type of the nodes of the list.
+    type Node;
+
+    /// The iterator returned by [`iter`](LinkedCollection::iter).
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Pushes `item` to the front of the list.
+    fn push(&mut self, item: T);
+
+    /// Pops the front item of the list.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Pushes `node` to the front of the list.
+    fn push_node(&mut self, node: Box<Self::Node>);
+
+    /// Pops the front node of the list.
+    fn pop_node(&mut self) -> Option<Box<Self::Node>>;
+
+    /// Returns a reference to the front item of the list.
+    fn peek(&self) -> Option<&T>;
+
+    /// Returns a mutable reference to the front item of the list.
+    fn peek_mut(&mut self) -> Option<&mut T>;
+
+    /// Returns an iterator over the items of the list, front to back.
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// Returns the number of items in the list.
+    ///
+    /// Lists that do not keep track of their length count their items,
+    /// which takes O(n).
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.peek().is_none()
+    }
+}
+
+impl<T> LinkedCollection<T> for List<T> {
+    type Node = list::Node<T>;
+    type Iter<'a>
+        = list::Iter<'a, T>
+    where
+        T: 'a;
+
+    #[inline(always)]
+    fn push(&mut self, item: T) {
+        List::push(self, item)
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Option<T> {
+        List::pop(self)
+    }
+
+    #[inline(always)]
+    fn push_node(&mut self, node: Box<Self::Node>) {
+        List::push_node(self, node)
+    }
+
+    #[inline(always)]
+    fn pop_node(&mut self) -> Option<Box<Self::Node>> {
+        List::pop_node(self)
+    }
+
+    #[inline(always)]
+    fn peek(&self) -> Option<&T> {
+        List::peek(self)
+    }
+
+    #[inline(always)]
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        List::peek_mut(self)
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> Self::Iter<'_> {
+        List::iter(self)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        List::iter(self).count()
+    }
+}
+
+impl<T> LinkedCollection<T> for DoublyList<T> {
+    type Node = doublylist::Node<T>;
+    type Iter<'a>
+        = doublylist::Iter<'a, T>
+    where
+        T: 'a;
+
+    #[inline(always)]
+    fn push(&mut self, item: T) {
+        self.push_front(item)
+    }
+
+    #[inline(always)]
+    fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    #[inline(always)]
+    fn push_node(&mut self, node: Box<Self::Node>) {
+        self.push_front_node(node)
+    }
+
+    #[inline(always)]
+    fn pop_node(&mut self) -> Option<Box<Self::Node>> {
+        self.pop_front_node()
+    }
+
+    #[inline(always)]
+    fn peek(&self) -> Option<&T> {
+        self.peek_front()
+    }
+
+    #[inline(always)]
+    fn peek_mut(&mut self) -> Option<&mut T> {
+        self.peek_front_mut()
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> Self::Iter<'_> {
+        DoublyList::iter(self)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        DoublyList::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercise<L: LinkedCollection<String> + Default>() {
+        let mut list = L::default();
+        assert!(list.is_empty());
+
+        for word in ["c", "b", "a"] {
+            list.push(word.to_string());
+        }
+
+        list.peek_mut().unwrap().push('!');
+        assert_eq!(list.len(), 3);
+        assert!(list.iter().eq(["a!", "b", "c"]));
+
+        assert_eq!(list.pop().as_deref(), Some("a!"));
+        assert_eq!(list.peek().map(String::as_str), Some("b"));
+    }
+
+    #[test]
+    fn test_both_backends() {
+        exercise::<List<_>>();
+        exercise::<DoublyList<_>>();
+    }
+}
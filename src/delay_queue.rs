@@ -0,0 +1,241 @@
+//! A queue of items that become ready after a delay.
+
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::doublylist::{DoublyList, NodeRef};
+
+/// Identifies an item in a [`DelayQueue`], to cancel it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Key(u64);
+
+struct Entry<T> {
+    key: Key,
+    deadline: Instant,
+    item: T,
+}
+
+/// A queue of items that become ready at a deadline.
+///
+/// The items are kept in a [`DoublyList`] sorted by deadline,
+/// next to a map from every [`Key`] to the node of its item.
+/// Polling the ready items takes O(1) each, and so does cancelling an item.
+/// Inserting searches from the back of the list,
+/// which takes O(1) when items mostly arrive in deadline order,
+/// like when they are all delayed equally long.
+///
+/// # Examples
+/// ```
+/// # use lists::delay_queue::DelayQueue;
+/// use std::time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// let mut queue = DelayQueue::new();
+///
+/// let retry = queue.insert_at(start + Duration::from_secs(3), "retry");
+/// queue.insert_at(start + Duration::from_secs(1), "ping");
+///
+/// assert_eq!(queue.cancel(retry), Some("retry"));
+/// assert_eq!(queue.poll_expired(start), None);
+/// assert_eq!(
+///     queue.poll_expired(start + Duration::from_secs(1)).map(|(_, item)| item),
+///     Some("ping")
+/// );
+/// ```
+pub struct DelayQueue<T> {
+    entries: DoublyList<Entry<T>>,
+    nodes: HashMap<Key, NodeRef<Entry<T>>>,
+    next_key: u64,
+}
+
+impl<T> Default for DelayQueue<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for DelayQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DelayQueue")
+            .field("len", &self.len())
+            .field("next_deadline", &self.next_deadline())
+            .finish()
+    }
+}
+
+impl<T> DelayQueue<T> {
+    /// Returns a new empty queue.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: DoublyList::new(),
+            nodes: HashMap::new(),
+            next_key: 0,
+        }
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.len() == 0
+    }
+
+    /// Returns the earliest deadline in the queue.
+    #[inline]
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.entries.peek_front().map(|entry| entry.deadline)
+    }
+
+    /// Inserts `item`, to become ready at `deadline`, and returns its key.
+    pub fn insert_at(&mut self, deadline: Instant, item: T) -> Key {
+        let key = Key(self.next_key);
+        self.next_key += 1;
+
+        let entry = Entry {
+            key,
+            deadline,
+            item,
+        };
+        let node = self
+            .entries
+            .insert_sorted_by(entry, |a, b| a.deadline.cmp(&b.deadline));
+
+        self.nodes.insert(key, node);
+        key
+    }
+
+    /// Inserts `item`, to become ready `delay` from now, and returns its key.
+    #[inline]
+    pub fn insert(&mut self, delay: Duration, item: T) -> Key {
+        self.insert_at(Instant::now() + delay, item)
+    }
+
+    /// Returns the deadline of the item `key`.
+    #[inline]
+    pub fn deadline(&self, key: Key) -> Option<Instant> {
+        let node = *self.nodes.get(&key)?;
+
+        // The map only holds nodes linked into the list.
+        Some(unsafe { self.entries.get(node) }.deadline)
+    }
+
+    /// Removes the item `key` before it becomes ready, and returns it.
+    pub fn cancel(&mut self, key: Key) -> Option<T> {
+        let node = self.nodes.remove(&key)?;
+
+        // The map only holds nodes linked into the list.
+        Some(unsafe { self.entries.remove(node) }.item)
+    }
+
+    /// Removes the item with the earliest deadline, if it is ready at `now`,
+    /// and returns it with its key.
+    pub fn poll_expired(&mut self, now: Instant) -> Option<(Key, T)> {
+        if self.next_deadline()? > now {
+            return None;
+        }
+
+        let entry = self.entries.pop_front()?;
+        self.nodes.remove(&entry.key);
+        Some((entry.key, entry.item))
+    }
+
+    /// Returns an iterator removing the items that are ready at `now`,
+    /// in the order of their deadlines.
+    ///
+    /// Ready items that the iterator does not get to stay in the queue.
+    #[inline(always)]
+    pub fn expired(&mut self, now: Instant) -> Expired<'_, T> {
+        Expired { queue: self, now }
+    }
+
+    /// Removes all items from the queue.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.entries.clear();
+    }
+}
+
+/// An iterator removing the ready items of a [`DelayQueue`],
+/// returned by [`DelayQueue::expired`].
+pub struct Expired<'a, T> {
+    queue: &'a mut DelayQueue<T>,
+    now: Instant,
+}
+
+impl<T> Debug for Expired<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Expired").field("now", &self.now).finish()
+    }
+}
+
+impl<T> Iterator for Expired<'_, T> {
+    type Item = (Key, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.poll_expired(self.now)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.queue.len()))
+    }
+}
+
+impl<T> FusedIterator for Expired<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_and_cancel() {
+        let start = Instant::now();
+        let at = |millis: u64| start + Duration::from_millis(millis);
+        let mut queue = DelayQueue::new();
+
+        let keys = (0..50u64)
+            .map(|n| queue.insert_at(at((n * 7) % 20), n))
+            .collect::<Vec<_>>();
+
+        for &key in keys.iter().step_by(3) {
+            assert!(queue.cancel(key).is_some());
+            assert_eq!(queue.cancel(key), None);
+            assert_eq!(queue.deadline(key), None);
+        }
+
+        assert_eq!(queue.deadline(keys[1]), Some(at(7)));
+        assert_eq!(queue.next_deadline(), Some(at(0)));
+
+        let mut ready = Vec::new();
+
+        for millis in (0..25).step_by(5) {
+            ready.extend(queue.expired(at(millis)).map(|(_, n)| n));
+            assert!(queue
+                .next_deadline()
+                .is_none_or(|deadline| deadline > at(millis)));
+        }
+
+        let mut expected = (0..50).filter(|n| n % 3 != 0).collect::<Vec<_>>();
+        expected.sort_by_key(|&n| ((n * 7) % 20, n));
+        assert_eq!(ready, expected);
+        assert!(queue.is_empty());
+        assert!(queue.nodes.is_empty());
+    }
+}
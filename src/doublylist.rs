@@ -1,11 +1,31 @@
 use core::{
-    fmt::{self, Debug},
+    alloc::Layout,
+    any::Any,
+    borrow::Borrow,
+    cell::UnsafeCell,
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
     iter::{ExactSizeIterator, FromIterator, FusedIterator},
     marker::PhantomData,
-    ptr::NonNull,
+    mem,
+    ops::{Add, AddAssign, Bound, ControlFlow, RangeBounds},
+    ptr::{self, NonNull},
+    sync::atomic::{self, AtomicUsize},
 };
+use std::alloc;
 
-type Link<T> = Option<NonNull<Node<T>>>;
+#[cfg(feature = "stats")]
+use crate::stats::Stats;
+use crate::{
+    join::Join,
+    list::List,
+    raw,
+    slice_node::{self, SliceNode},
+};
+
+/// The type used to link to another Node.
+pub type Link<T> = Option<NonNull<Node<T>>>;
 
 trait IntoNonNull {
     type Item: ?Sized;
@@ -22,10 +42,19 @@ impl<T: ?Sized> IntoNonNull for Box<T> {
     }
 }
 
+/// A node holds a value, and pointers to a next and a previous node.
+///
+/// See the [`raw`](crate::raw) module for linking nodes by hand.
+///
+/// With the `ffi` feature, nodes are `#[repr(C)]`:
+/// the `next` link, then the `prev` link, then the item.
+/// Links are nullable pointers, so a node of a sized item
+/// matches a C struct holding two pointers to it, followed by the item.
+#[cfg_attr(feature = "ffi", repr(C))]
 pub struct Node<T: ?Sized, U: ?Sized = T> {
-    next: Link<U>,
-    prev: Link<U>,
-    item: T,
+    pub(crate) next: Link<U>,
+    pub(crate) prev: Link<U>,
+    pub(crate) item: T,
 }
 
 impl<T, U: ?Sized> Node<T, U> {
@@ -42,33 +71,261 @@ impl<T, U: ?Sized> Node<T, U> {
     pub fn boxed(item: T) -> Box<Self> {
         Box::new(Self::new(item))
     }
+
+    /// Returns a new boxed node, whose item is constructed by `f`
+    /// only after the node has been allocated.
+    ///
+    /// This gives the optimizer the chance to construct the item
+    /// directly into the node, instead of moving it there through the stack.
+    #[inline(always)]
+    pub fn boxed_with<F>(f: F) -> Box<Self>
+    where
+        F: FnOnce() -> T,
+    {
+        let mut node = Box::<Self>::new_uninit();
+        let ptr = node.as_mut_ptr();
+
+        // If `f` panics, the uninitialized box is deallocated
+        // without running any destructor.
+        unsafe {
+            ptr::addr_of_mut!((*ptr).next).write(None);
+            ptr::addr_of_mut!((*ptr).prev).write(None);
+            ptr::addr_of_mut!((*ptr).item).write(f());
+            node.assume_init()
+        }
+    }
+}
+
+impl<T: ?Sized> Node<T> {
+    /// Consumes the boxed node, returning a pointer to it.
+    ///
+    /// The node is not unlinked: its `next` and `prev` links are kept as they are.
+    #[inline(always)]
+    pub fn into_raw(node: Box<Self>) -> NonNull<Self> {
+        IntoNonNull::into_non_null(node)
+    }
+
+    /// Constructs a boxed node from a pointer obtained from [`Node::into_raw`].
+    ///
+    /// # Safety
+    /// `node` must come from [`Node::into_raw`], and no other box may own it.
+    #[inline(always)]
+    pub unsafe fn from_raw(node: NonNull<Self>) -> Box<Self> {
+        Box::from_raw(node.as_ptr())
+    }
+
+    /// Returns the link to the next node.
+    #[inline(always)]
+    pub const fn next(&self) -> Link<T> {
+        self.next
+    }
+
+    /// Returns the link to the previous node.
+    #[inline(always)]
+    pub const fn prev(&self) -> Link<T> {
+        self.prev
+    }
+
+    /// Returns a reference to the item of the node.
+    #[inline(always)]
+    pub const fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Returns a mutable reference to the item of the node.
+    #[inline(always)]
+    pub fn item_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
 }
 
 impl<T> Node<T> {
+    #[allow(clippy::boxed_local)]
     #[inline(always)]
     pub fn into_item(self: Box<Self>) -> T {
         self.item
     }
 }
 
+// A `Node<[T; 0], [T]>` shares its links and the offset of its items with any `Node<[T]>`.
+unsafe impl<T> SliceNode<T> for Node<[T]> {
+    const HEADER: Layout = Layout::new::<Node<[T; 0], [T]>>();
+    const ITEMS: usize = mem::offset_of!(Node<[T; 0], [T]>, item);
+
+    #[inline(always)]
+    fn from_raw_parts(ptr: *mut u8, len: usize) -> *mut Self {
+        ptr::slice_from_raw_parts_mut(ptr.cast::<T>(), len) as *mut Self
+    }
+
+    #[inline(always)]
+    unsafe fn init_links(node: *mut Self) {
+        ptr::addr_of_mut!((*node).next).write(None);
+        ptr::addr_of_mut!((*node).prev).write(None);
+    }
+}
+
+impl<T> Node<[T]> {
+    /// Returns a new boxed node, holding the items of `items`.
+    ///
+    /// The items are moved into the node, without being cloned.
+    /// Since the links live in front of the items,
+    /// the buffer of the vector itself cannot be reused.
+    #[inline]
+    pub fn from_vec(items: Vec<T>) -> Box<Self> {
+        slice_node::from_vec(items)
+    }
+
+    /// Returns a new boxed node, holding the items of `items`.
+    ///
+    /// The items are moved into the node, without being cloned.
+    #[inline]
+    pub fn from_boxed_slice(items: Box<[T]>) -> Box<Self> {
+        Self::from_vec(items.into_vec())
+    }
+}
+
+impl<T: Clone> Node<[T]> {
+    /// Returns a new boxed node, holding clones of `items`.
+    #[inline]
+    pub fn boxed_slice(items: &[T]) -> Box<Self> {
+        slice_node::boxed_slice(items)
+    }
+}
+
+impl Node<str> {
+    /// Returns a new boxed node, holding a copy of `text`.
+    pub fn boxed_str(text: &str) -> Box<Self> {
+        let node = Node::<[u8]>::boxed_slice(text.as_bytes());
+
+        // A `Node<[u8]>` has the same layout as a `Node<str>`,
+        // and the bytes came from a `str`.
+        unsafe { Box::from_raw(Box::into_raw(node) as *mut Self) }
+    }
+}
+
+/// An opaque handle to a node linked into a [`DoublyList`].
+///
+/// Handles are returned by [`DoublyList::push_front_ref`], [`DoublyList::push_back_ref`]
+/// and their `_node_ref` counterparts, or found with [`DoublyList::find_node`],
+/// and allow addressing their node in O(1).
+/// A handle is only valid for as long as its node is linked into the list it was obtained from,
+/// which is why the methods taking a handle are `unsafe`.
+///
+/// Unlike the [`Index`](crate::veclist::Index) of a [`VecList`](crate::veclist::VecList),
+/// a handle carries no generation: a removed node is freed,
+/// so nothing is left behind to tell a stale handle apart.
+/// Use a `VecList` when handles may outlive their items,
+/// or check a handle with [`DoublyList::contains_ref`] first.
+pub struct NodeRef<T: ?Sized> {
+    node: NonNull<Node<T>>,
+}
+
+impl<T: ?Sized> NodeRef<T> {
+    /// Returns the pointer to the node this handle refers to.
+    #[inline(always)]
+    pub const fn as_ptr(self) -> NonNull<Node<T>> {
+        self.node
+    }
+}
+
+unsafe impl<T: ?Sized + Send> Send for NodeRef<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for NodeRef<T> {}
+
+impl<T: ?Sized> Copy for NodeRef<T> {}
+
+impl<T: ?Sized> Clone for NodeRef<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> PartialEq for NodeRef<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        ptr::addr_eq(self.node.as_ptr(), other.node.as_ptr())
+    }
+}
+
+impl<T: ?Sized> Eq for NodeRef<T> {}
+
+impl<T: ?Sized> Hash for NodeRef<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node.as_ptr().cast::<()>().hash(state)
+    }
+}
+
+impl<T: ?Sized> Debug for NodeRef<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NodeRef")
+            .field(&self.node.as_ptr().cast::<()>())
+            .finish()
+    }
+}
+
+/// The structural version of a [`DoublyList`], returned by [`DoublyList::version`].
+///
+/// The version changes whenever a node is linked into or unlinked from the list,
+/// and stays the same when items are only read or changed in place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Version(usize);
+
+/// The error returned by [`DoublyList::check_version`],
+/// when the list was structurally modified since a [`Version`] was taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Modified {
+    /// The version that was expected.
+    pub expected: Version,
+
+    /// The version the list is at.
+    pub found: Version,
+}
+
+impl Display for Modified {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "list was structurally modified: expected version {}, found {}",
+            self.expected.0, self.found.0
+        )
+    }
+}
+
+impl std::error::Error for Modified {}
+
 /// A doubly list.
 pub struct DoublyList<T: ?Sized> {
     head: Link<T>,
     tail: Link<T>,
     len: usize,
+
+    /// Counts every node linked and unlinked, see [`DoublyList::version`].
+    mutations: usize,
+    #[cfg(feature = "stats")]
+    stats: Stats,
     marker: PhantomData<Box<Node<T>>>,
 }
 
 unsafe impl<T: ?Sized + Send> Send for DoublyList<T> {}
 unsafe impl<T: ?Sized + Sync> Sync for DoublyList<T> {}
 
-impl<T: ?Sized + Clone> Clone for DoublyList<T> {
+impl<T: Clone> Clone for DoublyList<T> {
     #[inline]
     fn clone(&self) -> Self {
         self.iter().cloned().collect()
     }
 }
 
+impl<T: ?Sized> Default for DoublyList<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: ?Sized + Debug> Debug for DoublyList<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -76,6 +333,87 @@ impl<T: ?Sized + Debug> Debug for DoublyList<T> {
     }
 }
 
+impl<T: ?Sized + PartialEq> PartialEq for DoublyList<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for DoublyList<T> {}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for DoublyList<T> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.len == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<&[U]> for DoublyList<T> {
+    #[inline(always)]
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Vec<U>> for DoublyList<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for DoublyList<T> {
+    #[inline(always)]
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: ?Sized + PartialEq<U>, U: ?Sized> PartialEq<List<U>> for DoublyList<T> {
+    #[inline]
+    fn eq(&self, other: &List<U>) -> bool {
+        self.iter().eq(other)
+    }
+}
+
+impl<T> From<List<T>> for DoublyList<T> {
+    /// Moves the items of `list` into a new list, in the same order.
+    #[inline]
+    fn from(list: List<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T: ?Sized> Add for DoublyList<T> {
+    type Output = Self;
+
+    /// Returns the items of `self` followed by the items of `rhs`,
+    /// splicing the two lists together in O(1).
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let a = (0..3).collect::<DoublyList<u32>>();
+    /// let b = (3..5).collect::<DoublyList<u32>>();
+    ///
+    /// assert_eq!(a + b, [0, 1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: ?Sized> AddAssign for DoublyList<T> {
+    /// Appends the items of `rhs`, splicing the two lists together in O(1).
+    #[inline]
+    fn add_assign(&mut self, mut rhs: Self) {
+        self.append(&mut rhs);
+    }
+}
+
 impl<T: ?Sized> DoublyList<T> {
     /*
      * Pushing to the front:
@@ -135,38 +473,99 @@ impl<T: ?Sized> DoublyList<T> {
      *
      */
     #[inline(always)]
-    fn push_front_node_private(&mut self, mut node: Box<Node<T>>) {
-        unsafe {
-            node.next = self.head;
-            node.prev = None;
+    fn push_front_node_private(&mut self, node: Box<Node<T>>) -> NonNull<Node<T>> {
+        let node = Node::into_raw(node);
 
-            let node = Some(Box::into_non_null(node));
+        // The node was just taken out of its box, so it is linked into no list.
+        unsafe { self.link_front_private(node) };
+        node
+    }
 
-            match self.head {
-                None => self.tail = node,
-                Some(head) => (*head.as_ptr()).prev = node,
-            }
+    #[inline(always)]
+    fn push_back_node_private(&mut self, node: Box<Node<T>>) -> NonNull<Node<T>> {
+        let node = Node::into_raw(node);
+
+        // The node was just taken out of its box, so it is linked into no list.
+        unsafe { self.link_back_private(node) };
+        node
+    }
+
+    /*
+     * The nodes are relinked through the pointer they were linked in with,
+     * rather than taken back into a box:
+     * that would invalidate every handle to the node.
+     */
+
+    /// Links `node` in at the front.
+    ///
+    /// `node` must come from [`Node::into_raw`], and be linked into no list.
+    #[inline(always)]
+    unsafe fn link_front_private(&mut self, node: NonNull<Node<T>>) {
+        (*node.as_ptr()).next = self.head;
+        (*node.as_ptr()).prev = None;
 
-            self.head = node;
-            self.len += 1;
+        let link = Some(node);
+
+        match self.head {
+            None => self.tail = link,
+            Some(head) => (*head.as_ptr()).prev = link,
         }
+
+        self.head = link;
+        self.len += 1;
+        self.record_link(1);
     }
 
+    /// Links `node` in at the back.
+    ///
+    /// `node` must come from [`Node::into_raw`], and be linked into no list.
     #[inline(always)]
-    fn push_back_node_private(&mut self, mut node: Box<Node<T>>) {
-        unsafe {
-            node.next = None;
-            node.prev = self.tail;
+    unsafe fn link_back_private(&mut self, node: NonNull<Node<T>>) {
+        (*node.as_ptr()).next = None;
+        (*node.as_ptr()).prev = self.tail;
 
-            let node = Some(Box::into_non_null(node));
+        let link = Some(node);
 
-            match self.tail {
-                None => self.head = node,
-                Some(tail) => (*tail.as_ptr()).next = node,
-            }
+        match self.tail {
+            None => self.head = link,
+            Some(tail) => (*tail.as_ptr()).next = link,
+        }
+
+        self.tail = link;
+        self.len += 1;
+        self.record_link(1);
+    }
 
-            self.tail = node;
-            self.len += 1;
+    /// Merges the sorted nodes of `other` into this sorted list,
+    /// putting the nodes of this list first on ties.
+    ///
+    /// Every node is linked into one of both lists whenever `compare` runs,
+    /// so a panic leaves each node in a list.
+    fn merge_private<F>(&mut self, other: &mut Self, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut cursor = self.head;
+
+        while let Some(node) = other.head {
+            // The cursor walks the nodes of this list, and `node` is the front of `other`.
+            unsafe {
+                while let Some(at) = cursor {
+                    if compare(&(*node.as_ptr()).item, &(*at.as_ptr()).item) == Ordering::Less {
+                        break;
+                    }
+
+                    cursor = (*at.as_ptr()).next;
+                }
+
+                match cursor {
+                    Some(at) => {
+                        other.detach_private(node);
+                        self.link_before_private(at, node);
+                    }
+                    None => return self.append(other),
+                }
+            }
         }
     }
 
@@ -210,6 +609,7 @@ impl<T: ?Sized> DoublyList<T> {
             }
 
             self.len -= 1;
+            self.record_unlink(1);
             node
         })
     }
@@ -226,9 +626,213 @@ impl<T: ?Sized> DoublyList<T> {
             }
 
             self.len -= 1;
+            self.record_unlink(1);
             node
         })
     }
+
+    /*
+     * Unlinking a node from anywhere in the list:
+     *
+     * Point the previous node (or the head) past the node,
+     * and point the next node (or the tail) before the node.
+     *
+     * The node must be linked into this list.
+     */
+    #[inline(always)]
+    unsafe fn unlink_node_private(&mut self, node: NonNull<Node<T>>) -> Box<Node<T>> {
+        self.detach_private(node);
+        Node::from_raw(node)
+    }
+
+    /// Unlinks `node`, leaving it owned by no one.
+    ///
+    /// `node` must be linked into this list.
+    #[inline(always)]
+    unsafe fn detach_private(&mut self, node: NonNull<Node<T>>) {
+        let Node { prev, next, .. } = *node.as_ptr();
+
+        if prev.is_none() {
+            self.head = next;
+        }
+
+        if next.is_none() {
+            self.tail = prev;
+        }
+
+        raw::unlink(node);
+
+        self.len -= 1;
+        self.record_unlink(1);
+    }
+
+    /// Links `node` in right after `at`.
+    ///
+    /// `at` must be linked into this list.
+    #[inline(always)]
+    unsafe fn insert_node_after_private(
+        &mut self,
+        at: NonNull<Node<T>>,
+        node: Box<Node<T>>,
+    ) -> NonNull<Node<T>> {
+        let node = Node::into_raw(node);
+        self.link_after_private(at, node);
+        node
+    }
+
+    /// Links `node` in right after `at`.
+    ///
+    /// `at` must be linked into this list,
+    /// and `node` must come from [`Node::into_raw`], and be linked into no list.
+    #[inline(always)]
+    unsafe fn link_after_private(&mut self, at: NonNull<Node<T>>, node: NonNull<Node<T>>) {
+        raw::link_after(at, node);
+
+        if (*node.as_ptr()).next.is_none() {
+            self.tail = Some(node);
+        }
+
+        self.len += 1;
+        self.record_link(1);
+    }
+
+    /// Links `node` in right before `at`.
+    ///
+    /// `at` must be linked into this list.
+    #[inline(always)]
+    unsafe fn insert_node_before_private(
+        &mut self,
+        at: NonNull<Node<T>>,
+        node: Box<Node<T>>,
+    ) -> NonNull<Node<T>> {
+        let node = Node::into_raw(node);
+        self.link_before_private(at, node);
+        node
+    }
+
+    /// Links `node` in right before `at`.
+    ///
+    /// `at` must be linked into this list,
+    /// and `node` must come from [`Node::into_raw`], and be linked into no list.
+    #[inline(always)]
+    unsafe fn link_before_private(&mut self, at: NonNull<Node<T>>, node: NonNull<Node<T>>) {
+        raw::link_before(at, node);
+
+        if (*node.as_ptr()).prev.is_none() {
+            self.head = Some(node);
+        }
+
+        self.len += 1;
+        self.record_link(1);
+    }
+
+    /// Cuts the list right before `at`,
+    /// and returns the `len` nodes in front of it as a list of their own.
+    ///
+    /// `at` must be linked into this list, with exactly `len` nodes in front of it.
+    #[inline]
+    unsafe fn split_before_private(&mut self, at: NonNull<Node<T>>, len: usize) -> Self {
+        let mut front = Self::new();
+
+        if let Some(prev) = (*at.as_ptr()).prev.take() {
+            (*prev.as_ptr()).next = None;
+
+            front.head = self.head;
+            front.tail = Some(prev);
+            front.len = len;
+            front.record_link(len);
+
+            self.head = Some(at);
+            self.len -= len;
+            self.record_unlink(len);
+        }
+
+        front
+    }
+
+    /// Cuts the `len` nodes from `first` up to and including `last` out of the list,
+    /// and returns them as a list of their own.
+    ///
+    /// `first` and `last` must be linked into this list, with `len` nodes from one to the other.
+    #[inline]
+    unsafe fn cut_private(
+        &mut self,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        len: usize,
+    ) -> Self {
+        let prev = (*first.as_ptr()).prev.take();
+        let next = (*last.as_ptr()).next.take();
+
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = next,
+            None => self.head = next,
+        }
+
+        match next {
+            Some(next) => (*next.as_ptr()).prev = prev,
+            None => self.tail = prev,
+        }
+
+        self.len -= len;
+        self.record_unlink(len);
+
+        let mut chain = Self::new();
+        chain.head = Some(first);
+        chain.tail = Some(last);
+        chain.len = len;
+        chain.record_link(len);
+        chain
+    }
+
+    /// Links all nodes of `chain` in between `before` and `after`,
+    /// where `None` stands for the front or the back of the list.
+    ///
+    /// `before` and `after` must be linked into this list, right next to each other.
+    #[inline]
+    unsafe fn splice_private(&mut self, before: Link<T>, after: Link<T>, chain: &mut Self) {
+        let (first, last) = match (chain.head.take(), chain.tail.take()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return,
+        };
+
+        (*first.as_ptr()).prev = before;
+        (*last.as_ptr()).next = after;
+
+        match before {
+            Some(before) => (*before.as_ptr()).next = Some(first),
+            None => self.head = Some(first),
+        }
+
+        match after {
+            Some(after) => (*after.as_ptr()).prev = Some(last),
+            None => self.tail = Some(last),
+        }
+
+        let len = mem::replace(&mut chain.len, 0);
+        self.len += len;
+
+        chain.record_unlink(len);
+        self.record_link(len);
+    }
+
+    #[inline(always)]
+    const fn record_link(&mut self, count: usize) {
+        self.mutations = self.mutations.wrapping_add(1);
+        #[cfg(feature = "stats")]
+        self.stats.record_link(count);
+        #[cfg(not(feature = "stats"))]
+        let _ = count;
+    }
+
+    #[inline(always)]
+    fn record_unlink(&mut self, count: usize) {
+        self.mutations = self.mutations.wrapping_add(1);
+        #[cfg(feature = "stats")]
+        self.stats.record_unlink(count);
+        #[cfg(not(feature = "stats"))]
+        let _ = count;
+    }
 }
 
 impl<T: ?Sized> DoublyList<T> {
@@ -238,13 +842,141 @@ impl<T: ?Sized> DoublyList<T> {
             head: None,
             tail: None,
             len: 0,
+            mutations: 0,
+            #[cfg(feature = "stats")]
+            stats: Stats::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Decomposes the list into its head, tail and length,
+    /// handing ownership of its nodes to the caller.
+    ///
+    /// The list can be put back together with [`DoublyList::from_raw_parts`].
+    /// With the `ffi` feature, nodes are `#[repr(C)]`,
+    /// so the chain can also be walked from C.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = (1..=3).collect::<DoublyList<u32>>();
+    /// let (head, tail, len) = list.into_raw_parts();
+    ///
+    /// let list = unsafe { DoublyList::from_raw_parts(head, tail, len) };
+    /// assert_eq!(list, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn into_raw_parts(self) -> (Link<T>, Link<T>, usize) {
+        let list = mem::ManuallyDrop::new(self);
+        (list.head, list.tail, list.len)
+    }
+
+    /// Leaks the nodes of the list, and returns an iterator over them
+    /// that may live for the rest of the program, like [`Vec::leak`].
+    ///
+    /// The iterator is [`Copy`], so a list built once at startup
+    /// can be shared and walked from anywhere, without ever being freed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::{DoublyList, Iter};
+    /// let primes: Iter<'static, u32> = [2, 3, 5, 7].iter().copied().collect::<DoublyList<_>>().leak();
+    ///
+    /// assert_eq!(primes.sum::<u32>(), 17);
+    /// assert_eq!(primes.rev().next(), Some(&7));
+    /// ```
+    #[inline]
+    pub fn leak<'a>(self) -> Iter<'a, T>
+    where
+        T: 'a,
+    {
+        let (head, tail, len) = self.into_raw_parts();
+
+        // The nodes are never freed, nor handed to anyone that could change them.
+        unsafe { Iter::from_raw_parts(head, tail, len) }
+    }
+
+    /// Reconstitutes a list from its head, tail and length.
+    ///
+    /// # Safety
+    /// `head` to `tail` must be a properly linked chain of `len` nodes,
+    /// that all come from [`Node::into_raw`], and are owned by no one else,
+    /// such as the parts returned by [`DoublyList::into_raw_parts`].
+    /// The `prev` link of `head` and the `next` link of `tail` must be `None`.
+    #[inline(always)]
+    pub const unsafe fn from_raw_parts(head: Link<T>, tail: Link<T>, len: usize) -> Self {
+        let mut list = Self {
+            head,
+            tail,
+            len,
+            mutations: 0,
+            #[cfg(feature = "stats")]
+            stats: Stats::new(),
             marker: PhantomData,
+        };
+
+        list.record_link(len);
+        list
+    }
+
+    /// Returns the structural version of the list.
+    ///
+    /// Code that holds on to [`NodeRef`]s between calls
+    /// can compare versions to notice that nodes were linked or unlinked in the meantime,
+    /// and that its handles may no longer be linked into the list.
+    #[inline(always)]
+    pub const fn version(&self) -> Version {
+        Version(self.mutations)
+    }
+
+    /// Returns an error if the list was structurally modified since `version` was taken.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = DoublyList::new();
+    /// list.push_back(1);
+    ///
+    /// let version = list.version();
+    /// *list.peek_front_mut().unwrap() += 1;
+    /// assert!(list.check_version(version).is_ok());
+    ///
+    /// list.pop_front();
+    /// assert!(list.check_version(version).is_err());
+    /// ```
+    #[inline]
+    pub fn check_version(&self, version: Version) -> Result<(), Modified> {
+        match self.version() {
+            found if found == version => Ok(()),
+            found => Err(Modified {
+                expected: version,
+                found,
+            }),
+        }
+    }
+
+    /// Panics with a message naming `what`,
+    /// if the list was structurally modified since `version` was taken.
+    ///
+    /// This is a cheap check, meant for code built on [`NodeRef`]s.
+    #[inline]
+    #[track_caller]
+    pub fn assert_version(&self, version: Version, what: &str) {
+        if let Err(err) = self.check_version(version) {
+            panic!("{} used after the list changed: {}", what, err);
         }
     }
 
     #[inline(always)]
     pub fn clear(&mut self) {
-        *self = Self::new();
+        while self.pop_front_node().is_some() {}
+    }
+
+    /// Returns the link counters of the list.
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    pub const fn stats(&self) -> Stats {
+        self.stats
     }
 
     #[inline(always)]
@@ -303,16 +1035,34 @@ impl<T: ?Sized> DoublyList<T> {
 
     #[inline(always)]
     pub fn push_front_node(&mut self, node: Box<Node<T>>) {
-        self.push_front_node_private(node)
+        self.push_front_node_private(node);
     }
 
     #[inline(always)]
     pub fn push_back_node(&mut self, node: Box<Node<T>>) {
-        self.push_back_node_private(node)
+        self.push_back_node_private(node);
     }
 
+    /// Pushes `node` to the front of the list,
+    /// and returns a handle to it.
     #[inline(always)]
-    pub fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
+    pub fn push_front_node_ref(&mut self, node: Box<Node<T>>) -> NodeRef<T> {
+        NodeRef {
+            node: self.push_front_node_private(node),
+        }
+    }
+
+    /// Pushes `node` to the back of the list,
+    /// and returns a handle to it.
+    #[inline(always)]
+    pub fn push_back_node_ref(&mut self, node: Box<Node<T>>) -> NodeRef<T> {
+        NodeRef {
+            node: self.push_back_node_private(node),
+        }
+    }
+
+    #[inline(always)]
+    pub fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
         self.pop_front_node_private()
     }
 
@@ -321,299 +1071,5489 @@ impl<T: ?Sized> DoublyList<T> {
         self.pop_back_node_private()
     }
 
-    #[inline(always)]
-    pub const fn iter(&self) -> Iter<T> {
-        Iter {
-            head: self.head,
-            tail: self.tail,
-            len: self.len,
-            marker: PhantomData,
+    /// Returns whether `node` refers to a node that is linked into this list.
+    ///
+    /// This walks the list, and only compares `node` with the nodes it finds,
+    /// so it is safe to call with any handle, and takes O(n).
+    /// A stale handle whose address was reused by a node pushed later
+    /// refers to that node.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..3).collect::<DoublyList<u32>>();
+    /// let one = list.find_node(|&n| n == 1).unwrap();
+    /// assert!(list.contains_ref(one));
+    ///
+    /// // The handle came from this list, and its node is still linked in.
+    /// unsafe { list.remove(one) };
+    /// assert!(!list.contains_ref(one));
+    /// ```
+    #[inline]
+    pub fn contains_ref(&self, node: NodeRef<T>) -> bool {
+        let mut cursor = self.head;
+
+        while let Some(linked) = cursor {
+            if ptr::addr_eq(linked.as_ptr(), node.node.as_ptr()) {
+                return true;
+            }
+
+            // The cursor only walks the nodes linked into the list.
+            cursor = unsafe { (*linked.as_ptr()).next };
         }
+
+        false
     }
 
+    /// Returns a reference to the item `node` refers to.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut {
-            head: self.head,
-            tail: self.tail,
-            len: self.len,
-            marker: PhantomData,
-        }
+    pub unsafe fn get(&self, node: NodeRef<T>) -> &T {
+        &(*node.node.as_ptr()).item
     }
-}
 
-impl<T> DoublyList<T> {
+    /// Returns a mutable reference to the item `node` refers to.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    pub fn push_front(&mut self, item: T) {
-        self.push_front_node(Node::boxed(item));
+    pub unsafe fn get_mut(&mut self, node: NodeRef<T>) -> &mut T {
+        &mut (*node.node.as_ptr()).item
     }
 
-    #[inline(always)]
-    pub fn push_back(&mut self, item: T) {
-        self.push_back_node(Node::boxed(item));
+    /// Returns mutable references to the items the handles in `nodes` refer to,
+    /// or `None` if two of the handles refer to the same node.
+    ///
+    /// # Safety
+    /// Every handle in `nodes` must refer to a node that is currently linked into this list.
+    #[inline]
+    pub unsafe fn get_disjoint_nodes_mut<const N: usize>(
+        &mut self,
+        nodes: [NodeRef<T>; N],
+    ) -> Option<[&mut T; N]> {
+        for (i, node) in nodes.iter().enumerate() {
+            if nodes[..i].contains(node) {
+                return None;
+            }
+        }
+
+        Some(nodes.map(|node| &mut (*node.node.as_ptr()).item))
+    }
+
+    /// Returns mutable references to the items at `indices`,
+    /// or `None` if an index is out of bounds or appears twice.
+    ///
+    /// The list is walked once, up to the largest index.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..5).collect::<DoublyList<u32>>();
+    ///
+    /// if let Some([a, b]) = list.get_disjoint_mut([3, 1]) {
+    ///     core::mem::swap(a, b);
+    /// }
+    ///
+    /// assert_eq!(list, [0, 3, 2, 1, 4]);
+    /// assert!(list.get_disjoint_mut([2, 2]).is_none());
+    /// assert!(list.get_disjoint_mut([0, 5]).is_none());
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_unstable_by_key(|&i| indices[i]);
+
+        if order.windows(2).any(|w| indices[w[0]] == indices[w[1]]) {
+            return None;
+        }
+
+        if order.last().is_some_and(|&i| indices[i] >= self.len) {
+            return None;
+        }
+
+        let mut nodes: [Link<T>; N] = [None; N];
+        let (mut cursor, mut position) = (self.head, 0);
+
+        for i in order {
+            // Every index is less than `len`, so the cursor stays on a node of the list.
+            unsafe {
+                while position < indices[i] {
+                    cursor = (*cursor?.as_ptr()).next;
+                    position += 1;
+                }
+            }
+
+            nodes[i] = cursor;
+        }
+
+        // The nodes are distinct, and the list is borrowed mutably.
+        Some(nodes.map(|node| unsafe { &mut (*node.unwrap().as_ptr()).item }))
     }
 
+    /// Unlinks the node `node` refers to, and returns it.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    pub fn pop_front(&mut self) -> Option<T> {
-        self.pop_front_node().map(Node::into_item)
+    pub unsafe fn remove_node(&mut self, node: NodeRef<T>) -> Box<Node<T>> {
+        self.unlink_node_private(node.node)
     }
 
+    /// Moves the node `node` refers to to the front of the list.
+    /// The handle stays valid.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    pub fn pop_back(&mut self) -> Option<T> {
-        self.pop_back_node().map(Node::into_item)
+    pub unsafe fn move_to_front(&mut self, node: NodeRef<T>) {
+        if self.head != Some(node.node) {
+            self.detach_private(node.node);
+            self.link_front_private(node.node);
+        }
     }
-}
 
-impl<T: ?Sized> Drop for DoublyList<T> {
-    fn drop(&mut self) {
-        while let Some(_) = self.pop_front_node() {}
+    /// Moves the node `node` refers to to the back of the list.
+    /// The handle stays valid.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
+    #[inline(always)]
+    pub unsafe fn move_to_back(&mut self, node: NodeRef<T>) {
+        if self.tail != Some(node.node) {
+            self.detach_private(node.node);
+            self.link_back_private(node.node);
+        }
     }
-}
 
-impl<'a, T: ?Sized> IntoIterator for &'a DoublyList<T> {
-    type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
+    /// Swaps the positions of the nodes `a` and `b` refer to, in O(1).
+    ///
+    /// The nodes are relinked rather than their items swapped,
+    /// so this works for unsized items too, and both handles stay valid,
+    /// each following its own item to the position of the other.
+    ///
+    /// # Safety
+    /// `a` and `b` must both refer to nodes that are currently linked into this list.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::{DoublyList, Node};
+    /// let mut list = DoublyList::<str>::new();
+    /// let a = list.push_back_node_ref(Node::boxed_str("a"));
+    /// list.push_back_node(Node::boxed_str("b"));
+    /// let c = list.push_back_node_ref(Node::boxed_str("c"));
+    ///
+    /// // Both handles were just pushed.
+    /// unsafe { list.swap_nodes(a, c) };
+    /// assert!(list.iter().eq(["c", "b", "a"]));
+    /// assert_eq!(list.front_ref(), Some(c));
+    /// ```
+    pub unsafe fn swap_nodes(&mut self, a: NodeRef<T>, b: NodeRef<T>) {
+        let (a, b) = (a.node, b.node);
+        let links_to = |link: Link<T>, node: NonNull<Node<T>>| {
+            link.is_some_and(|link| ptr::addr_eq(link.as_ptr(), node.as_ptr()))
+        };
 
+        if ptr::addr_eq(a.as_ptr(), b.as_ptr()) {
+            return;
+        }
+
+        // Adjacent nodes swap by moving the back one in front of the other.
+        if links_to((*a.as_ptr()).next, b) {
+            self.detach_private(b);
+            self.link_before_private(a, b);
+            return;
+        }
+
+        if links_to((*b.as_ptr()).next, a) {
+            self.detach_private(a);
+            self.link_before_private(b, a);
+            return;
+        }
+
+        // The neighbours of `a` are neither `a` nor `b`, so they stay put while both move.
+        let (a_prev, a_next) = ((*a.as_ptr()).prev, (*a.as_ptr()).next);
+
+        self.detach_private(a);
+        self.link_before_private(b, a);
+
+        self.detach_private(b);
+        match (a_prev, a_next) {
+            (_, Some(next)) => self.link_before_private(next, b),
+            (Some(prev), None) => self.link_after_private(prev, b),
+            // `b` is linked apart from `a`, so `a` had a neighbour.
+            (None, None) => unreachable!(),
+        }
+    }
+
+    /// Unlinks the node `node` refers to, and links it in at the back of `dest`, in O(1).
+    ///
+    /// The item is not moved and nothing is allocated,
+    /// so from now on the handle refers to a node of `dest`.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut idle = DoublyList::new();
+    /// let mut active = DoublyList::new();
+    ///
+    /// let conn = idle.push_back_ref("conn-1");
+    /// idle.push_back("conn-2");
+    ///
+    /// // `conn` was pushed into `idle`, and is moved into `active`.
+    /// unsafe { idle.transfer(conn, &mut active) };
+    /// assert_eq!(idle, ["conn-2"]);
+    ///
+    /// // It is now a handle into `active`.
+    /// assert_eq!(unsafe { active.get(conn) }, &"conn-1");
+    /// ```
     #[inline(always)]
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    pub unsafe fn transfer(&mut self, node: NodeRef<T>, dest: &mut Self) {
+        self.detach_private(node.node);
+        dest.link_back_private(node.node);
     }
-}
 
-impl<'a, T: ?Sized> IntoIterator for &'a mut DoublyList<T> {
-    type Item = &'a mut T;
-    type IntoIter = IterMut<'a, T>;
+    /// Returns a handle to the front node of the list.
+    #[inline(always)]
+    pub fn front_ref(&self) -> Option<NodeRef<T>> {
+        self.head.map(|node| NodeRef { node })
+    }
 
+    /// Returns a handle to the back node of the list.
     #[inline(always)]
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    pub fn back_ref(&self) -> Option<NodeRef<T>> {
+        self.tail.map(|node| NodeRef { node })
     }
-}
 
-impl<T> IntoIterator for DoublyList<T> {
-    type Item = T;
-    type IntoIter = IntoIter<T>;
+    /// Returns a handle to the node after the node `node` refers to.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
+    #[inline(always)]
+    pub unsafe fn next_ref(&self, node: NodeRef<T>) -> Option<NodeRef<T>> {
+        (*node.node.as_ptr()).next.map(|node| NodeRef { node })
+    }
 
+    /// Returns a handle to the node before the node `node` refers to.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter { inner: self }
+    pub unsafe fn prev_ref(&self, node: NodeRef<T>) -> Option<NodeRef<T>> {
+        (*node.node.as_ptr()).prev.map(|node| NodeRef { node })
     }
-}
 
-impl<T> FromIterator<T> for DoublyList<T> {
+    /// Returns a handle to the first node, from the front, whose item matches `pred`.
+    ///
+    /// The handle can then be used to get, move or remove the item in O(1),
+    /// without searching for it again.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..6).collect::<DoublyList<u32>>();
+    /// let three = list.find_node(|&n| n == 3).unwrap();
+    ///
+    /// // The handle came from this list, and its node is still linked in.
+    /// unsafe {
+    ///     list.move_to_front(three);
+    ///     assert_eq!(list.remove(three), 3);
+    /// }
+    /// assert_eq!(list.find_node(|&n| n == 3), None);
+    /// ```
     #[inline]
-    fn from_iter<I>(iter: I) -> Self
+    pub fn find_node<F>(&self, mut pred: F) -> Option<NodeRef<T>>
     where
-        I: IntoIterator<Item = T>,
+        F: FnMut(&T) -> bool,
     {
-        let mut list = DoublyList::new();
+        let mut cursor = self.head;
 
-        for item in iter {
-            list.push_back(item)
+        while let Some(node) = cursor {
+            // The cursor only walks the nodes linked into the list.
+            let node_ref = unsafe { &*node.as_ptr() };
+
+            if pred(&node_ref.item) {
+                return Some(NodeRef { node });
+            }
+
+            cursor = node_ref.next;
         }
 
-        list
+        None
     }
-}
 
-pub struct Iter<'a, T: ?Sized> {
-    head: Link<T>,
-    tail: Link<T>,
-    len: usize,
-    marker: PhantomData<&'a Node<T>>,
-}
+    /// Returns a handle to the last node whose item matches `pred`,
+    /// searching from the back.
+    #[inline]
+    pub fn rfind_node<F>(&self, mut pred: F) -> Option<NodeRef<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut cursor = self.tail;
 
-unsafe impl<T: ?Sized + Send> Send for Iter<'_, T> {}
-unsafe impl<T: ?Sized + Sync> Sync for Iter<'_, T> {}
+        while let Some(node) = cursor {
+            // Like in `find_node`, walking the `prev` links.
+            let node_ref = unsafe { &*node.as_ptr() };
 
-impl<T: ?Sized> Copy for Iter<'_, T> {}
+            if pred(&node_ref.item) {
+                return Some(NodeRef { node });
+            }
 
-impl<T: ?Sized> Clone for Iter<'_, T> {
-    #[inline(always)]
-    fn clone(&self) -> Self {
-        Iter { ..*self }
-    }
-}
+            cursor = node_ref.prev;
+        }
 
-impl<T: ?Sized + Debug> Debug for Iter<'_, T> {
-    #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("Iter").field(&self.len).finish()
+        None
     }
-}
-
-impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
-    type Item = &'a T;
 
+    /// Links `node` in right after the node `at` refers to,
+    /// and returns a handle to it.
+    ///
+    /// # Safety
+    /// `at` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.len == 0 {
-            None
-        } else {
-            self.head.map(|node| unsafe {
-                // Need an unbound lifetime to get 'a
-                let node = &*node.as_ptr();
-                self.len -= 1;
-                self.head = node.next;
-                &node.item
-            })
+    pub unsafe fn insert_node_after(&mut self, at: NodeRef<T>, node: Box<Node<T>>) -> NodeRef<T> {
+        NodeRef {
+            node: self.insert_node_after_private(at.node, node),
         }
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
-    }
-}
-
-impl<'a, T: ?Sized> DoubleEndedIterator for Iter<'a, T> {
+    /// Links `node` in right before the node `at` refers to,
+    /// and returns a handle to it.
+    ///
+    /// # Safety
+    /// `at` must refer to a node that is currently linked into this list.
     #[inline(always)]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len == 0 {
-            None
-        } else {
-            self.tail.map(|node| unsafe {
-                // Need an unbound lifetime to get 'a
-                let node = &*node.as_ptr();
-                self.len -= 1;
-                self.tail = node.prev;
-                &node.item
-            })
+    pub unsafe fn insert_node_before(&mut self, at: NodeRef<T>, node: Box<Node<T>>) -> NodeRef<T> {
+        NodeRef {
+            node: self.insert_node_before_private(at.node, node),
         }
     }
-}
 
-impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
-impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+    /// Moves all nodes of `other` to the back of this list, in O(1).
+    /// `other` is left empty.
+    pub fn append(&mut self, other: &mut Self) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
 
-pub struct IterMut<'a, T: ?Sized> {
-    head: Link<T>,
-    tail: Link<T>,
-    len: usize,
-    marker: PhantomData<&'a mut Node<T>>,
-}
+        match self.tail {
+            None => self.head = Some(head),
+            Some(old_tail) => unsafe {
+                (*old_tail.as_ptr()).next = Some(head);
+                (*head.as_ptr()).prev = Some(old_tail);
+            },
+        }
 
-unsafe impl<T: ?Sized + Send> Send for IterMut<'_, T> {}
-unsafe impl<T: ?Sized + Sync> Sync for IterMut<'_, T> {}
+        self.tail = Some(tail);
 
-impl<T: ?Sized + Debug> Debug for IterMut<'_, T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("IterMut").field(&self.len).finish()
+        let len = mem::replace(&mut other.len, 0);
+        self.len += len;
+
+        other.record_unlink(len);
+        self.record_link(len);
     }
-}
 
-impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
+    /// Alternates the nodes of the list with those of `other`, starting with the list,
+    /// and appends what remains of the longer one.
+    ///
+    /// Nodes are relinked, not reallocated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..3).collect::<DoublyList<u32>>();
+    /// list.interleave((10..15).collect());
+    ///
+    /// assert_eq!(list, [0, 10, 1, 11, 2, 12, 13, 14]);
+    /// ```
+    pub fn interleave(&mut self, mut other: Self) {
+        let mut cursor = self.head;
 
-    #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.len == 0 {
-            None
-        } else {
-            self.head.map(|node| unsafe {
-                // Need an unbound lifetime to get 'a
-                let node = &mut *node.as_ptr();
-                self.len -= 1;
-                self.head = node.next;
-                &mut node.item
-            })
+        while let Some(node) = cursor {
+            let theirs = match other.pop_front_node() {
+                Some(theirs) => theirs,
+                None => return,
+            };
+
+            // The cursor only walks the nodes linked into the list,
+            // and skips over the ones linked in behind it.
+            unsafe {
+                cursor = (*node.as_ptr()).next;
+                self.insert_node_after_private(node, theirs);
+            }
         }
+
+        self.append(&mut other);
     }
 
+    /// Shortens the list to its first `len` nodes, dropping the rest.
+    ///
+    /// Does nothing if the list holds no more than `len` nodes.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop_back_node();
+        }
     }
-}
 
-impl<'a, T: ?Sized> DoubleEndedIterator for IterMut<'a, T> {
-    #[inline(always)]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len == 0 {
-            None
-        } else {
-            self.tail.map(|node| unsafe {
-                // Need an unbound lifetime to get 'a
-                let node = &mut *node.as_ptr();
-                self.len -= 1;
-                self.tail = node.prev;
-                &mut node.item
-            })
+    /// Returns the number of bytes allocated for the nodes of the list,
+    /// including the links of every node.
+    ///
+    /// Memory owned by the items themselves is not included,
+    /// see [`HeapSize`](crate::heapsize::HeapSize) for that.
+    /// This walks the list, so it takes O(n).
+    pub fn memory_usage(&self) -> usize {
+        let mut cursor = self.head;
+        let mut bytes = 0;
+
+        // Every linked node is live.
+        while let Some(node) = cursor.map(|node| unsafe { &*node.as_ptr() }) {
+            bytes += mem::size_of_val(node);
+            cursor = node.next;
         }
+
+        bytes
     }
-}
 
-impl<T: ?Sized> FusedIterator for IterMut<'_, T> {}
-impl<T: ?Sized> ExactSizeIterator for IterMut<'_, T> {}
+    /// Walks the list, and panics if its links are corrupted.
+    ///
+    /// This checks that every `next` link is mirrored by a `prev` link,
+    /// that the chain starts at the head and ends at the tail,
+    /// and that the number of nodes in the chain equals `len()`.
+    /// It is meant for validating code built on top of the node level and [`raw`](crate::raw) APIs,
+    /// and takes O(n), so it fits well within `if cfg!(debug_assertions)`.
+    pub fn assert_invariants(&self) {
+        fn addr<T: ?Sized>(link: Link<T>) -> Option<*const ()> {
+            link.map(|node| node.as_ptr().cast_const().cast::<()>())
+        }
 
-pub struct IntoIter<T> {
-    inner: DoublyList<T>,
-}
+        match (self.head, self.tail) {
+            (None, None) => {
+                assert_eq!(
+                    self.len, 0,
+                    "list without nodes has a length of {}",
+                    self.len
+                );
+                return;
+            }
+            (Some(_), None) => panic!("list has a head, but no tail"),
+            (None, Some(_)) => panic!("list has a tail, but no head"),
+            (Some(_), Some(_)) => {}
+        }
 
-impl<T: Debug> Debug for IntoIter<T> {
-    #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("IntoIter").field(&self.inner).finish()
-    }
-}
+        let mut prev: Link<T> = None;
+        let mut cursor = self.head;
+        let mut count = 0;
 
-impl<T> Iterator for IntoIter<T> {
-    type Item = T;
+        while let Some(node) = cursor {
+            let node = unsafe { node.as_ref() };
 
-    #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.pop_front()
+            assert_eq!(
+                addr(node.prev),
+                addr(prev),
+                "node {} does not link back to its previous node",
+                count
+            );
+
+            count += 1;
+            assert!(
+                count <= self.len,
+                "list has more nodes than its length of {}",
+                self.len
+            );
+
+            prev = cursor;
+            cursor = node.next;
+        }
+
+        assert_eq!(
+            addr(prev),
+            addr(self.tail),
+            "last node of the chain is not the tail"
+        );
+        assert_eq!(
+            count, self.len,
+            "list has {} nodes, but a length of {}",
+            count, self.len
+        );
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.inner.len, Some(self.inner.len))
+    /// Returns an adaptor that displays the items in the list,
+    /// separated by `separator`.
+    #[inline(always)]
+    pub const fn join<'a>(&'a self, separator: &'a str) -> Join<'a, Iter<'a, T>>
+    where
+        T: Display,
+    {
+        Join::new(self.iter(), separator)
     }
-}
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
     #[inline(always)]
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.inner.pop_back()
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
     }
-}
 
-impl<T> FusedIterator for IntoIter<T> {}
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over every pair of adjacent items, front to back.
+    ///
+    /// This is what [`slice::windows`] with a size of 2 yields for a slice.
+    /// A list with fewer than two items yields no pairs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let readings = [3, 7, 6, 10].iter().copied().collect::<DoublyList<i32>>();
+    /// let deltas = readings.iter_pairs().map(|(a, b)| b - a);
+    ///
+    /// assert!(deltas.eq([4, -1, 4]));
+    /// ```
+    #[inline(always)]
+    pub fn iter_pairs(&self) -> Pairs<'_, T> {
+        Pairs { iter: self.iter() }
+    }
+
+    /// Returns a cursor over every pair of adjacent items, front to back,
+    /// handing out mutable references to both.
+    ///
+    /// Adjacent pairs overlap, so the cursor is not an [`Iterator`]:
+    /// a pair has to be dropped before [`PairsMut::next`] returns the next one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut totals = (1..=4).collect::<DoublyList<u32>>();
+    /// let mut pairs = totals.iter_pairs_mut();
+    ///
+    /// while let Some((a, b)) = pairs.next() {
+    ///     *b += *a;
+    /// }
+    ///
+    /// assert_eq!(totals, [1, 3, 6, 10]);
+    /// ```
+    #[inline(always)]
+    pub fn iter_pairs_mut(&mut self) -> PairsMut<'_, T> {
+        PairsMut {
+            head: self.head,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sorts the list, keeping equal items in their order.
+    ///
+    /// This is a merge sort that relinks the nodes,
+    /// so no item is moved or cloned, and unsized items are sorted too.
+    /// It takes O(n log n) comparisons.
+    ///
+    /// # Panics
+    /// If the `Ord` implementation of `T` panics, the panic is propagated,
+    /// and the list keeps all its items, in an unspecified order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = [5, 1, 4, 2, 3].iter().copied().collect::<DoublyList<u32>>();
+    /// list.sort();
+    ///
+    /// assert_eq!(list, [1, 2, 3, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list with `compare`, like [`sort`](DoublyList::sort).
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = ["bb", "a", "cc", "d"].iter().copied().collect::<DoublyList<_>>();
+    /// list.sort_by(|a, b| a.len().cmp(&b.len()));
+    ///
+    /// // Equal items keep their order.
+    /// assert_eq!(list, ["a", "d", "bb", "cc"]);
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut guard = MergeGuard {
+            list: self,
+            carry: DoublyList::new(),
+            runs: Vec::new(),
+        };
+        let MergeGuard { list, carry, runs } = &mut guard;
+
+        /*
+         * Every node is taken from the front of the list in turn,
+         * and carried up through the runs like a binary counter:
+         * `runs[i]` is either empty, or holds 2^i sorted nodes,
+         * which came before the nodes of the runs below it.
+         */
+        while let Some(head) = list.head {
+            // The head is linked into the list.
+            unsafe {
+                list.detach_private(head);
+                carry.link_back_private(head);
+            }
+
+            let mut level = 0;
+
+            while level < runs.len() && !runs[level].is_empty() {
+                runs[level].merge_private(carry, &mut compare);
+                mem::swap(carry, &mut runs[level]);
+                level += 1;
+            }
+
+            if level == runs.len() {
+                runs.push(DoublyList::new());
+            }
+
+            mem::swap(carry, &mut runs[level]);
+        }
+
+        for level in 1..runs.len() {
+            let (below, above) = runs.split_at_mut(level);
+            above[0].merge_private(&mut below[level - 1], &mut compare);
+        }
+
+        // The guard links the one run left back into the list.
+    }
+
+    /// Sorts the list by the keys `f` returns, like [`sort`](DoublyList::sort).
+    ///
+    /// `f` is called twice per comparison,
+    /// see [`sort_by_cached_key`](DoublyList::sort_by_cached_key) for expensive keys.
+    #[inline]
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Sorts the list, without keeping equal items in their order.
+    ///
+    /// The nodes are gathered into a buffer of pointers, sorted there with
+    /// [`slice::sort_unstable_by`], and relinked in their new order,
+    /// so no item is moved or cloned, however large it is.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = [5, 1, 4, 2, 3].iter().copied().collect::<DoublyList<u32>>();
+    /// list.sort_unstable();
+    ///
+    /// assert_eq!(list, [1, 2, 3, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_unstable_by(T::cmp);
+    }
+
+    /// Sorts the list with `compare`, like [`sort_unstable`](DoublyList::sort_unstable).
+    ///
+    /// If `compare` panics, the list keeps all its items, in an unspecified order.
+    pub fn sort_unstable_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut guard = RelinkGuard {
+            nodes: Vec::with_capacity(self.len),
+            list: self,
+        };
+
+        while let Some(node) = guard.list.pop_front_node() {
+            guard.nodes.push(((), node));
+        }
+
+        guard
+            .nodes
+            .sort_unstable_by(|(_, a), (_, b)| compare(&a.item, &b.item));
+    }
+
+    /// Sorts the list by the keys `f` returns,
+    /// like [`sort_unstable`](DoublyList::sort_unstable).
+    #[inline]
+    pub fn sort_unstable_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_unstable_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Sorts the list by the keys `f` returns, keeping equal items in their order.
+    ///
+    /// `f` is called once per item, and the keys are kept next to their nodes
+    /// while those are sorted, which pays off when computing a key is expensive.
+    /// If `f` panics, the list is left as it was,
+    /// and if comparing two keys panics, the list keeps all its items, in an unspecified order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = ["ccc", "a", "bb", "d"].iter().copied().collect::<DoublyList<_>>();
+    /// list.sort_by_cached_key(|word| word.len());
+    ///
+    /// assert_eq!(list, ["a", "d", "bb", "ccc"]);
+    /// ```
+    pub fn sort_by_cached_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let mut guard = RelinkGuard {
+            nodes: Vec::with_capacity(self.len),
+            list: self,
+        };
+
+        while let Some(front) = guard.list.peek_front() {
+            let key = f(front);
+            let node = guard.list.pop_front_node().unwrap();
+            guard.nodes.push((key, node));
+        }
+
+        guard.nodes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    /// Returns `true` if the items are sorted in ascending order, front to back.
+    ///
+    /// This walks the list once, so it suits debug assertions
+    /// on lists kept sorted with [`insert_sorted`](DoublyList::insert_sorted).
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = DoublyList::new();
+    /// [3, 1, 2].iter().for_each(|&n| drop(list.insert_sorted(n)));
+    ///
+    /// debug_assert!(list.is_sorted());
+    /// assert!(!list.iter().rev().collect::<DoublyList<_>>().is_sorted());
+    /// ```
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Returns `true` if `compare` returns `true` for every pair of adjacent items,
+    /// like [`slice::is_sorted_by`].
+    #[inline]
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.iter_pairs().all(|(a, b)| compare(a, b))
+    }
+
+    /// Returns `true` if the keys `f` returns for the items are sorted in ascending order.
+    /// `f` is called once per item.
+    pub fn is_sorted_by_key<K, F>(&self, f: F) -> bool
+    where
+        K: PartialOrd,
+        F: FnMut(&T) -> K,
+    {
+        let mut keys = self.iter().map(f);
+
+        let mut previous = match keys.next() {
+            Some(key) => key,
+            None => return true,
+        };
+
+        keys.all(|key| {
+            let sorted = previous <= key;
+            previous = key;
+            sorted
+        })
+    }
+
+    /// Returns `true` if the front items of the list are equal to the items of `prefix`.
+    ///
+    /// `prefix` can be another list, a slice, or any iterator of items or references to them.
+    /// An empty prefix is a prefix of every list.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let frame = b"HDR\x02ab".iter().copied().collect::<DoublyList<u8>>();
+    ///
+    /// assert!(frame.starts_with(b"HDR"));
+    /// assert!(frame.ends_with(b"ab"));
+    /// assert!(!frame.starts_with(b"HDR\x03"));
+    /// assert!(frame.ends_with(&frame));
+    /// ```
+    pub fn starts_with<I>(&self, prefix: I) -> bool
+    where
+        T: PartialEq,
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let mut items = self.iter();
+
+        prefix
+            .into_iter()
+            .all(|x| items.next().is_some_and(|item| item == x.borrow()))
+    }
+
+    /// Returns `true` if the back items of the list are equal to the items of `suffix`.
+    ///
+    /// Like [`starts_with`](DoublyList::starts_with), but `suffix` is compared back to front,
+    /// so its iterator has to be double-ended.
+    pub fn ends_with<I>(&self, suffix: I) -> bool
+    where
+        T: PartialEq,
+        I: IntoIterator,
+        I::IntoIter: DoubleEndedIterator,
+        I::Item: Borrow<T>,
+    {
+        let mut items = self.iter();
+
+        suffix
+            .into_iter()
+            .rev()
+            .all(|x| items.next_back().is_some_and(|item| item == x.borrow()))
+    }
+
+    /// Returns an iterator that goes over the items front to back, over and over again.
+    ///
+    /// Unlike [`Iterator::cycle`], this follows the link from the back node to the front
+    /// instead of cloning the iterator, and needs no `T: Clone` to do so.
+    /// An empty list yields no items.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let workers = ["a", "b", "c"].iter().copied().collect::<DoublyList<_>>();
+    /// let jobs = (0..5).zip(workers.cycle()).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(jobs, [(0, &"a"), (1, &"b"), (2, &"c"), (3, &"a"), (4, &"b")]);
+    /// ```
+    #[inline(always)]
+    pub fn cycle(&self) -> Cycle<'_, T> {
+        Cycle {
+            head: self.head,
+            cursor: self.head,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the items from the node `from` refers to,
+    /// up to and including the node `to` refers to.
+    ///
+    /// Only the nodes in the range are walked, counting them up front,
+    /// so reaching the range costs nothing with handles to its ends.
+    ///
+    /// # Safety
+    /// `from` and `to` must refer to nodes that are currently linked into this list,
+    /// and `to` must not come before `from`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = DoublyList::new();
+    /// list.push_back(0);
+    /// let from = list.push_back_ref(1);
+    /// list.push_back(2);
+    /// let to = list.push_back_ref(3);
+    /// list.push_back(4);
+    ///
+    /// // Both handles are linked into `list`, `to` coming after `from`.
+    /// assert!(unsafe { list.iter_range(from, to) }.eq(&[1, 2, 3]));
+    /// ```
+    #[inline]
+    pub unsafe fn iter_range(&self, from: NodeRef<T>, to: NodeRef<T>) -> Iter<'_, T> {
+        let len = Self::range_len_private(from.node, to.node);
+        Iter::from_raw_parts(Some(from.node), Some(to.node), len)
+    }
+
+    /// Returns an iterator over mutable references to the items from the node `from` refers to,
+    /// up to and including the node `to` refers to, like [`iter_range`](Self::iter_range).
+    ///
+    /// # Safety
+    /// `from` and `to` must refer to nodes that are currently linked into this list,
+    /// and `to` must not come before `from`.
+    #[inline]
+    pub unsafe fn iter_range_mut(&mut self, from: NodeRef<T>, to: NodeRef<T>) -> IterMut<'_, T> {
+        IterMut {
+            head: Some(from.node),
+            tail: Some(to.node),
+            len: Self::range_len_private(from.node, to.node),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a view of the items within `range`.
+    ///
+    /// Reaching the ends of the range walks at most half of the list,
+    /// after which the view hands out its length, ends and items without walking it again.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = (0..10).collect::<DoublyList<u32>>();
+    /// let slice = list.slice(3..7);
+    ///
+    /// assert_eq!(slice.len(), 4);
+    /// assert_eq!((slice.first(), slice.last()), (Some(&3), Some(&6)));
+    /// assert_eq!(slice.iter().sum::<u32>(), 18);
+    /// ```
+    pub fn slice<R>(&self, range: R) -> ListSlice<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(range, self.len);
+
+        if start == end {
+            return ListSlice::default();
+        }
+
+        // Both ends lie within the list, as checked above.
+        unsafe {
+            let first = seek(self.head, self.tail, self.len, start);
+            let last = seek(self.head, self.tail, self.len, end - 1);
+
+            ListSlice {
+                iter: Iter::from_raw_parts(Some(first), Some(last), end - start),
+            }
+        }
+    }
+
+    /// Returns a view of the items from the node `from` refers to,
+    /// up to and including the node `to` refers to.
+    ///
+    /// # Safety
+    /// `from` and `to` must refer to nodes that are currently linked into this list,
+    /// and `to` must not come before `from`.
+    #[inline]
+    pub unsafe fn slice_between(&self, from: NodeRef<T>, to: NodeRef<T>) -> ListSlice<'_, T> {
+        ListSlice {
+            iter: self.iter_range(from, to),
+        }
+    }
+
+    /// Returns a mutable view of the items within `range`,
+    /// which can also push and remove items, but only within the range.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..6).collect::<DoublyList<u32>>();
+    /// let mut slice = list.slice_mut(2..4);
+    ///
+    /// slice.iter_mut().for_each(|n| *n *= 10);
+    /// slice.push_front(1);
+    /// slice.retain(|&n| n != 30);
+    /// assert_eq!(slice.len(), 2);
+    ///
+    /// // The view links its items back into the list when it is dropped.
+    /// drop(slice);
+    /// assert_eq!(list, [0, 1, 1, 20, 4, 5]);
+    /// ```
+    pub fn slice_mut<R>(&mut self, range: R) -> ListSliceMut<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(range, self.len);
+
+        // The range lies within the list, as checked above,
+        // so do the nodes at its ends and right around it.
+        unsafe {
+            if start == end {
+                let before = start
+                    .checked_sub(1)
+                    .map(|n| seek(self.head, self.tail, self.len, n));
+                let after = (end < self.len).then(|| seek(self.head, self.tail, self.len, end));
+                return ListSliceMut::new(self, before, after, Self::new());
+            }
+
+            let first = seek(self.head, self.tail, self.len, start);
+            let last = seek(self.head, self.tail, self.len, end - 1);
+            let (before, after) = ((*first.as_ptr()).prev, (*last.as_ptr()).next);
+            let nodes = self.cut_private(first, last, end - start);
+
+            ListSliceMut::new(self, before, after, nodes)
+        }
+    }
+
+    /// Returns a mutable view of the items from the node `from` refers to,
+    /// up to and including the node `to` refers to, like [`slice_mut`](Self::slice_mut).
+    ///
+    /// # Safety
+    /// `from` and `to` must refer to nodes that are currently linked into this list,
+    /// and `to` must not come before `from`.
+    #[inline]
+    pub unsafe fn slice_between_mut(
+        &mut self,
+        from: NodeRef<T>,
+        to: NodeRef<T>,
+    ) -> ListSliceMut<'_, T> {
+        let (before, after) = ((*from.node.as_ptr()).prev, (*to.node.as_ptr()).next);
+        let len = Self::range_len_private(from.node, to.node);
+        let nodes = self.cut_private(from.node, to.node, len);
+
+        ListSliceMut::new(self, before, after, nodes)
+    }
+
+    /// Returns the number of nodes from `from` up to and including `to`.
+    ///
+    /// # Safety
+    /// `to` must be linked after `from`, or be `from`.
+    #[inline]
+    unsafe fn range_len_private(from: NonNull<Node<T>>, to: NonNull<Node<T>>) -> usize {
+        let mut node = from;
+        let mut len = 1;
+
+        while !ptr::addr_eq(node.as_ptr(), to.as_ptr()) {
+            node = (*node.as_ptr())
+                .next
+                .expect("the end of a range is not linked after its start");
+            len += 1;
+        }
+
+        len
+    }
+
+    /// Turns the list into an iterator over its nodes, front to back.
+    ///
+    /// The nodes can be pushed into another list without reallocating,
+    /// which is what the `Extend` and `FromIterator` implementations
+    /// over `Box<Node<T>>` do.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = (0..4).collect::<DoublyList<u32>>();
+    ///
+    /// let evens = list
+    ///     .into_nodes()
+    ///     .filter(|node| node.item() % 2 == 0)
+    ///     .collect::<DoublyList<u32>>();
+    ///
+    /// assert!(evens.iter().eq(&[0, 2]));
+    /// ```
+    #[inline(always)]
+    pub fn into_nodes(self) -> IntoNodes<T> {
+        IntoNodes { inner: self }
+    }
+
+    /// Cuts the list into `n` pieces of nearly equal length, front to back.
+    ///
+    /// The first `len % n` pieces hold one item more than the others,
+    /// and when `n` exceeds the length, the last pieces are empty.
+    /// The pieces are cut out of the list by relinking, without reallocating.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = (0..8).collect::<DoublyList<u32>>();
+    /// let parts = list.split_into(3);
+    ///
+    /// assert_eq!(parts, [vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+    /// ```
+    pub fn split_into(mut self, n: usize) -> Vec<Self> {
+        assert!(n != 0, "cannot split a list into 0 pieces");
+
+        let (size, extra) = (self.len / n, self.len % n);
+        let mut pieces = Vec::with_capacity(n);
+
+        for i in 1..n {
+            let len = size + usize::from(i <= extra);
+
+            let piece = match len == self.len {
+                true => mem::take(&mut self),
+                // `len` is less than the length of the list, so the node after the piece exists.
+                false => unsafe {
+                    let at = seek(self.head, self.tail, self.len, len);
+                    self.split_before_private(at, len)
+                },
+            };
+
+            pieces.push(piece);
+        }
+
+        pieces.push(self);
+        pieces
+    }
+
+    /// Splits the list into the segments between the items that match `pred`,
+    /// which are dropped.
+    ///
+    /// Like [`slice::split`], separators at either end or next to each other
+    /// yield empty segments.
+    /// The segments are cut out of the list by relinking, without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = "a,bc,,d".chars().collect::<DoublyList<_>>();
+    /// let words = list
+    ///     .split(|&c| c == ',')
+    ///     .map(|word| word.into_iter().collect::<String>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(words, ["a", "bc", "", "d"]);
+    /// ```
+    #[inline(always)]
+    pub fn split<F>(self, pred: F) -> Split<T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Split {
+            rest: Some(self),
+            pred,
+            inclusive: false,
+        }
+    }
+
+    /// Splits the list into segments that each end with an item that matches `pred`,
+    /// except for the last one if the list doesn't end with a match.
+    ///
+    /// Like [`slice::split_inclusive`], the separators are kept,
+    /// and an empty list yields no segments.
+    #[inline(always)]
+    pub fn split_inclusive<F>(self, pred: F) -> Split<T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Split {
+            rest: Some(self),
+            pred,
+            inclusive: true,
+        }
+    }
+
+    /// Routes every item into a list of the items with the same key, returned by `f`,
+    /// and returns the lists with their keys, in the order their keys first appeared.
+    ///
+    /// The items keep their relative order within each list,
+    /// and are moved by relinking their nodes, without cloning or reallocating.
+    /// Keys are compared with the key of the previous item first,
+    /// and then with every key found so far, so this suits lists with few distinct keys.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let tasks = [("ann", 1), ("bob", 2), ("ann", 3), ("cid", 4), ("bob", 5)];
+    /// let tasks = tasks.iter().copied().collect::<DoublyList<_>>();
+    ///
+    /// let shards = tasks.split_by(|&(owner, _)| owner);
+    /// let shards = shards
+    ///     .into_iter()
+    ///     .map(|(owner, tasks)| (owner, tasks.iter().map(|task| task.1).collect::<Vec<_>>()))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(shards, [("ann", vec![1, 3]), ("bob", vec![2, 5]), ("cid", vec![4])]);
+    /// ```
+    pub fn split_by<K, F>(mut self, mut f: F) -> Vec<(K, Self)>
+    where
+        K: PartialEq,
+        F: FnMut(&T) -> K,
+    {
+        let mut groups: Vec<(K, Self)> = Vec::new();
+        let mut last = 0;
+
+        while let Some(node) = self.pop_front_node_private() {
+            let key = f(&node.item);
+
+            let group = match groups.get(last) {
+                Some((previous, _)) if *previous == key => last,
+                _ => match groups.iter().position(|(other, _)| *other == key) {
+                    Some(group) => group,
+                    None => {
+                        groups.push((key, Self::new()));
+                        groups.len() - 1
+                    }
+                },
+            };
+
+            groups[group].1.push_back_node_private(node);
+            last = group;
+        }
+
+        groups
+    }
+
+    /// Keeps the items for which `pred` returns `true`,
+    /// and returns the others as a new list, in the order they had.
+    ///
+    /// The rejected nodes are relinked into the returned list, not dropped or reallocated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut jobs = (0..6).collect::<DoublyList<u32>>();
+    /// let retries = jobs.retain_into(|job| job % 3 != 0);
+    ///
+    /// assert_eq!(jobs, [1, 2, 4, 5]);
+    /// assert_eq!(retries, [0, 3]);
+    /// ```
+    pub fn retain_into<F>(&mut self, mut pred: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut rejected = Self::new();
+        let mut cursor = self.head;
+
+        while let Some(node) = cursor {
+            // The cursor moves on before the node it was on is unlinked.
+            unsafe {
+                cursor = (*node.as_ptr()).next;
+
+                if !pred(&(*node.as_ptr()).item) {
+                    let node = self.unlink_node_private(node);
+                    rejected.push_back_node_private(node);
+                }
+            }
+        }
+
+        rejected
+    }
+}
+
+/// Holds the nodes taken out of `list` to be sorted, with their keys,
+/// and links them back in front of it when dropped, even if sorting them panicked.
+struct RelinkGuard<'a, K, T: ?Sized> {
+    list: &'a mut DoublyList<T>,
+    nodes: Vec<(K, Box<Node<T>>)>,
+}
+
+impl<K, T: ?Sized> Drop for RelinkGuard<'_, K, T> {
+    fn drop(&mut self) {
+        while let Some((_, node)) = self.nodes.pop() {
+            self.list.push_front_node(node);
+        }
+    }
+}
+
+/// Holds the sorted runs of [`DoublyList::sort_by`],
+/// and links them back into `list` when dropped, even if merging them panicked.
+struct MergeGuard<'a, T: ?Sized> {
+    list: &'a mut DoublyList<T>,
+    carry: DoublyList<T>,
+    runs: Vec<DoublyList<T>>,
+}
+
+impl<T: ?Sized> Drop for MergeGuard<'_, T> {
+    fn drop(&mut self) {
+        self.list.append(&mut self.carry);
+
+        for run in self.runs.iter_mut().rev() {
+            self.list.append(run);
+        }
+    }
+}
+
+impl<T> DoublyList<T> {
+    /// Returns a new list of the `n` items returned by calling `f` `n` times, front to back.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut next = 1;
+    /// let powers = DoublyList::repeat_with(
+    ///     || {
+    ///         next *= 2;
+    ///         next / 2
+    ///     },
+    ///     5,
+    /// );
+    ///
+    /// assert_eq!(powers, [1, 2, 4, 8, 16]);
+    /// ```
+    #[inline]
+    pub fn repeat_with<F>(mut f: F, n: usize) -> Self
+    where
+        F: FnMut() -> T,
+    {
+        let mut list = Self::new();
+
+        for _ in 0..n {
+            list.push_back(f());
+        }
+
+        list
+    }
+
+    #[inline(always)]
+    pub fn push_front(&mut self, item: T) {
+        self.push_front_node(Node::boxed(item));
+    }
+
+    #[inline(always)]
+    pub fn push_back(&mut self, item: T) {
+        self.push_back_node(Node::boxed(item));
+    }
+
+    /// Pushes the item constructed by `f` to the front of the list.
+    /// The node is allocated before `f` is called, see [`Node::boxed_with`].
+    #[inline(always)]
+    pub fn push_front_with<F>(&mut self, f: F)
+    where
+        F: FnOnce() -> T,
+    {
+        self.push_front_node(Node::boxed_with(f));
+    }
+
+    /// Pushes the item constructed by `f` to the back of the list.
+    /// The node is allocated before `f` is called, see [`Node::boxed_with`].
+    #[inline(always)]
+    pub fn push_back_with<F>(&mut self, f: F)
+    where
+        F: FnOnce() -> T,
+    {
+        self.push_back_node(Node::boxed_with(f));
+    }
+
+    /// Pushes `item` to the front of the list,
+    /// and returns a handle to its node.
+    #[inline(always)]
+    pub fn push_front_ref(&mut self, item: T) -> NodeRef<T> {
+        self.push_front_node_ref(Node::boxed(item))
+    }
+
+    /// Pushes `item` to the back of the list,
+    /// and returns a handle to its node.
+    #[inline(always)]
+    pub fn push_back_ref(&mut self, item: T) -> NodeRef<T> {
+        self.push_back_node_ref(Node::boxed(item))
+    }
+
+    /// Inserts `item` right after the node `at` refers to,
+    /// and returns a handle to its node.
+    ///
+    /// # Safety
+    /// `at` must refer to a node that is currently linked into this list.
+    #[inline(always)]
+    pub unsafe fn insert_after(&mut self, at: NodeRef<T>, item: T) -> NodeRef<T> {
+        self.insert_node_after(at, Node::boxed(item))
+    }
+
+    /// Inserts `item` right before the node `at` refers to,
+    /// and returns a handle to its node.
+    ///
+    /// # Safety
+    /// `at` must refer to a node that is currently linked into this list.
+    #[inline(always)]
+    pub unsafe fn insert_before(&mut self, at: NodeRef<T>, item: T) -> NodeRef<T> {
+        self.insert_node_before(at, Node::boxed(item))
+    }
+
+    /// Inserts `item` after the last item that is not greater than it,
+    /// and returns a handle to its node.
+    ///
+    /// A sorted list stays sorted, with equal items in the order they were inserted.
+    /// The list is searched from the back,
+    /// so inserting items that mostly arrive in order takes close to O(1).
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = DoublyList::new();
+    ///
+    /// for n in [3, 1, 4, 1, 5] {
+    ///     list.insert_sorted(n);
+    /// }
+    ///
+    /// assert_eq!(list, [1, 1, 3, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn insert_sorted(&mut self, item: T) -> NodeRef<T>
+    where
+        T: Ord,
+    {
+        self.insert_sorted_by(item, T::cmp)
+    }
+
+    /// Inserts `item` after the last item that `compare` does not order after it,
+    /// and returns a handle to its node. See [`insert_sorted`](DoublyList::insert_sorted).
+    pub fn insert_sorted_by<F>(&mut self, item: T, mut compare: F) -> NodeRef<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut at = self.back_ref();
+
+        while let Some(node) = at {
+            // Every handle is taken from this list while it is walked.
+            unsafe {
+                if compare(self.get(node), &item) != Ordering::Greater {
+                    return self.insert_after(node, item);
+                }
+
+                at = self.prev_ref(node);
+            }
+        }
+
+        self.push_front_ref(item)
+    }
+
+    /// Inserts `item` after the last item whose key is not greater than its key,
+    /// and returns a handle to its node. See [`insert_sorted`](DoublyList::insert_sorted).
+    #[inline]
+    pub fn insert_sorted_by_key<K, F>(&mut self, item: T, mut f: F) -> NodeRef<T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.insert_sorted_by(item, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Unlinks the node `node` refers to, and returns its item.
+    ///
+    /// # Safety
+    /// `node` must refer to a node that is currently linked into this list.
+    #[inline(always)]
+    pub unsafe fn remove(&mut self, node: NodeRef<T>) -> T {
+        self.remove_node(node).into_item()
+    }
+
+    #[inline(always)]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.pop_front_node().map(Node::into_item)
+    }
+
+    #[inline(always)]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.pop_back_node().map(Node::into_item)
+    }
+
+    /// Removes the front item and returns it, if `pred` returns `true` for it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut deadlines = [3, 5, 8].iter().copied().collect::<DoublyList<u32>>();
+    /// let now = 6;
+    ///
+    /// let mut expired = Vec::new();
+    /// while let Some(deadline) = deadlines.pop_front_if(|deadline| *deadline <= now) {
+    ///     expired.push(deadline);
+    /// }
+    ///
+    /// assert_eq!(expired, [3, 5]);
+    /// assert_eq!(deadlines, [8]);
+    /// ```
+    #[inline]
+    pub fn pop_front_if<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        match pred(self.peek_front_mut()?) {
+            true => self.pop_front(),
+            false => None,
+        }
+    }
+
+    /// Removes the back item and returns it, if `pred` returns `true` for it.
+    #[inline]
+    pub fn pop_back_if<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        match pred(self.peek_back_mut()?) {
+            true => self.pop_back(),
+            false => None,
+        }
+    }
+
+    /// Removes the first item, from the front, for which `pred` returns `true`, and returns it.
+    ///
+    /// The list is walked once: the matching node is unlinked where it was found.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (1..7).collect::<DoublyList<u32>>();
+    ///
+    /// assert_eq!(list.extract_first(|n| n % 3 == 0), Some(3));
+    /// assert_eq!(list.extract_last(|n| n % 3 == 0), Some(6));
+    /// assert_eq!(list.extract_first(|n| n % 3 == 0), None);
+    /// assert_eq!(list, [1, 2, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn extract_first<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let node = self.find_node(pred)?;
+        // The handle was just found in this list.
+        Some(unsafe { self.remove(node) })
+    }
+
+    /// Removes the last item for which `pred` returns `true`, searching from the back,
+    /// and returns it.
+    #[inline]
+    pub fn extract_last<F>(&mut self, pred: F) -> Option<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let node = self.rfind_node(pred)?;
+        // Like in `extract_first`.
+        Some(unsafe { self.remove(node) })
+    }
+
+    /// Replaces every item with what `f` returns for it, front to back,
+    /// keeping the items in their nodes.
+    ///
+    /// Unlike `into_iter().map(f).collect()`, this allocates no new nodes.
+    /// If `f` panics, the item it was called with is gone,
+    /// so its node is unlinked and freed, and the other items are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut words = ["a", "b"].iter().map(|s| s.to_string()).collect::<DoublyList<_>>();
+    /// words.map_in_place(|word| word + "!");
+    ///
+    /// assert_eq!(words, ["a!", "b!"]);
+    /// ```
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> T,
+    {
+        /// The node whose item was moved out, while `f` runs.
+        struct Hole<'a, T> {
+            list: &'a mut DoublyList<T>,
+            node: NonNull<Node<T>>,
+        }
+
+        impl<T> Drop for Hole<'_, T> {
+            fn drop(&mut self) {
+                // Only reached when `f` panicked, after the item was moved out of the node,
+                // so the node is freed without dropping its item.
+                unsafe {
+                    let node = Box::into_raw(self.list.unlink_node_private(self.node));
+                    alloc::dealloc(node.cast(), Layout::new::<Node<T>>());
+                }
+            }
+        }
+
+        let mut cursor = self.head;
+
+        while let Some(node) = cursor {
+            // The cursor walks the nodes of the list, and the item is written back
+            // before the next one is read, unless `f` panics and the hole unlinks the node.
+            unsafe {
+                cursor = (*node.as_ptr()).next;
+
+                let item = ptr::addr_of_mut!((*node.as_ptr()).item);
+                let hole = Hole { list: self, node };
+                let mapped = f(item.read());
+
+                mem::forget(hole);
+                item.write(mapped);
+            }
+        }
+    }
+
+    /// Removes the items within `range` from the list, and returns an iterator over them.
+    ///
+    /// The range is cut out of the list up front, by relinking the nodes at its ends once,
+    /// and the items the iterator doesn't yield are dropped along with it.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..6).collect::<DoublyList<u32>>();
+    /// let drained = list.drain(1..4).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(drained, [1, 2, 3]);
+    /// assert_eq!(list, [0, 4, 5]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(range, self.len);
+        let mut inner = Self::new();
+
+        if start != end {
+            let len = end - start;
+
+            // Both ends lie within the list, as checked above.
+            unsafe {
+                let first = seek(self.head, self.tail, self.len, start);
+                let last = seek(self.head, self.tail, self.len, end - 1);
+                let before = mem::take(&mut (*first.as_ptr()).prev);
+                let after = mem::take(&mut (*last.as_ptr()).next);
+
+                match before {
+                    Some(before) => (*before.as_ptr()).next = after,
+                    None => self.head = after,
+                }
+
+                match after {
+                    Some(after) => (*after.as_ptr()).prev = before,
+                    None => self.tail = before,
+                }
+
+                self.len -= len;
+                self.record_unlink(len);
+
+                inner.head = Some(first);
+                inner.tail = Some(last);
+                inner.len = len;
+                inner.record_link(len);
+            }
+        }
+
+        Drain {
+            inner,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Clone> DoublyList<T> {
+    /// Returns a new list holding `n` clones of `elem`.
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = DoublyList::from_elem(0u8, 3);
+    ///
+    /// assert!(list.iter().eq(&[0, 0, 0]));
+    /// ```
+    #[inline]
+    pub fn from_elem(elem: T, n: usize) -> Self {
+        Self::repeat(elem, n)
+    }
+
+    /// Returns a new list holding `n` clones of `elem`,
+    /// of which the last one is `elem` itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let list = DoublyList::repeat(String::from("ab"), 2);
+    ///
+    /// assert_eq!(list, ["ab", "ab"]);
+    /// ```
+    #[inline]
+    pub fn repeat(elem: T, n: usize) -> Self {
+        let mut list = Self::new();
+        list.resize(n, elem);
+        list
+    }
+
+    /// Resizes the list to `new_len` nodes,
+    /// either by pushing clones of `value` to the back, or by truncating it.
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        if new_len <= self.len {
+            return self.truncate(new_len);
+        }
+
+        for _ in self.len + 1..new_len {
+            self.push_back(value.clone());
+        }
+
+        self.push_back(value);
+    }
+
+    /// Pushes clones of `items` to the back of the list, in order.
+    ///
+    /// The clones are linked into a chain of their own,
+    /// which is appended in one go once every item is cloned,
+    /// so a panicking clone leaves the list as it was.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = DoublyList::from_elem(0u8, 1);
+    /// list.extend_from_slice(&[1, 2]);
+    ///
+    /// let copy = list.clone();
+    /// list.extend_from_list(&copy);
+    /// assert_eq!(list, [0, 1, 2, 0, 1, 2]);
+    /// ```
+    #[inline]
+    pub fn extend_from_slice(&mut self, items: &[T]) {
+        let mut chain = items.iter().cloned().collect();
+        self.append(&mut chain);
+    }
+
+    /// Pushes clones of the items of `other` to the back of the list, in order.
+    ///
+    /// Like [`extend_from_slice`](DoublyList::extend_from_slice),
+    /// the list is left as it was if cloning an item panics.
+    #[inline]
+    pub fn extend_from_list(&mut self, other: &Self) {
+        let mut chain = other.iter().cloned().collect();
+        self.append(&mut chain);
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T: ?Sized> DoublyList<T> {
+    /// Shuffles the list, by relinking its nodes in a uniformly random order.
+    ///
+    /// The items stay where they are in memory,
+    /// so this is as cheap for large items as for small ones.
+    pub fn shuffle<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+        use rand::seq::SliceRandom;
+
+        let mut nodes = Vec::with_capacity(self.len);
+
+        while let Some(node) = self.pop_front_node() {
+            nodes.push(node);
+        }
+
+        nodes.shuffle(rng);
+        self.extend(nodes);
+    }
+
+    /// Returns a uniformly random item, or `None` if the list is empty.
+    ///
+    /// The item is reached from whichever end of the list is closer.
+    #[inline]
+    pub fn choose<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<&T> {
+        rand::seq::IteratorRandom::choose(self.iter(), rng)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Send> DoublyList<T> {
+    /// Sorts the list with a stable merge sort, running on the rayon thread pool.
+    ///
+    /// The list is cut in halves that are sorted in parallel,
+    /// down to pieces that are sorted with [`sort_by`](DoublyList::sort_by),
+    /// and merged by relinking their nodes, so no item is moved or cloned.
+    ///
+    /// # Panics
+    /// If the `Ord` implementation of `T` panics, the panic is propagated,
+    /// and the list keeps all its items, in an unspecified order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..10_000).rev().collect::<DoublyList<u32>>();
+    /// list.par_sort();
+    ///
+    /// assert!(list.iter().copied().eq(0..10_000));
+    /// ```
+    #[inline]
+    pub fn par_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.par_sort_by(T::cmp);
+    }
+
+    /// Sorts the list with `compare`, like [`par_sort`](Self::par_sort).
+    ///
+    /// # Panics
+    /// If `compare` panics, the panic is propagated,
+    /// and the list keeps all its items, in an unspecified order.
+    #[inline]
+    pub fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        self.par_merge_sort(&compare);
+    }
+
+    fn par_merge_sort<F>(&mut self, compare: &F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        /// Holds the front half of `list` while both halves are sorted,
+        /// and links it back in front of the list when dropped, even if sorting panicked.
+        struct Halves<'a, T> {
+            front: DoublyList<T>,
+            list: &'a mut DoublyList<T>,
+        }
+
+        impl<T> Drop for Halves<'_, T> {
+            fn drop(&mut self) {
+                self.front.append(self.list);
+                self.list.append(&mut self.front);
+            }
+        }
+
+        // Below this length, handing a half to another thread costs more than it saves.
+        const SEQUENTIAL_LEN: usize = 1 << 12;
+
+        if self.len <= SEQUENTIAL_LEN {
+            return self.sort_by(compare);
+        }
+
+        let half = self.len / 2;
+
+        // The list holds more than `half` nodes, so the node at `half` is linked in,
+        // with `half` nodes in front of it.
+        let front = unsafe {
+            let middle = seek(self.head, self.tail, self.len, half);
+            self.split_before_private(middle, half)
+        };
+
+        let mut halves = Halves { front, list: self };
+        let Halves { front, list } = &mut halves;
+
+        rayon::join(
+            || front.par_merge_sort(compare),
+            || list.par_merge_sort(compare),
+        );
+
+        front.merge_private(list, &mut |a, b| compare(a, b));
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized> DoublyList<T> {
+    /// Pushes `item` to the front of the list, unsizing it into `T`.
+    ///
+    /// This allows pushing a `[u8; 16]` into a `DoublyList<[u8]>`,
+    /// or any concrete type into a `DoublyList<dyn Trait>`.
+    /// On stable, the same can be done with
+    /// `list.push_front_node(Node::boxed(item))` for a concrete `item`.
+    #[inline(always)]
+    pub fn push_front_unsize<U>(&mut self, item: U)
+    where
+        U: core::marker::Unsize<T>,
+    {
+        self.push_front_node(Node::<U, T>::boxed(item));
+    }
+
+    /// Pushes `item` to the back of the list, unsizing it into `T`.
+    ///
+    /// See [`DoublyList::push_front_unsize`].
+    #[inline(always)]
+    pub fn push_back_unsize<U>(&mut self, item: U)
+    where
+        U: core::marker::Unsize<T>,
+    {
+        self.push_back_node(Node::<U, T>::boxed(item));
+    }
+
+    /// Pushes the boxed `item` to the front of the list, unsizing it into `T`.
+    ///
+    /// This builds a `DoublyList<dyn Trait>` out of boxes of any concrete type,
+    /// such as the handlers returned by different plugins.
+    /// The item is moved out of its box into a new node.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut handlers: DoublyList<dyn Fn(u32) -> u32> = DoublyList::new();
+    /// let double: Box<_> = Box::new(|n| n * 2);
+    /// let offset = 10;
+    ///
+    /// handlers.push_back_boxed(double);
+    /// handlers.push_front_boxed(Box::new(move |n| n + offset));
+    ///
+    /// assert_eq!(handlers.iter().fold(1, |n, handler| handler(n)), 22);
+    /// ```
+    #[allow(clippy::boxed_local)]
+    #[inline(always)]
+    pub fn push_front_boxed<U>(&mut self, item: Box<U>)
+    where
+        U: core::marker::Unsize<T>,
+    {
+        self.push_front_unsize(*item);
+    }
+
+    /// Pushes the boxed `item` to the back of the list, unsizing it into `T`.
+    ///
+    /// See [`DoublyList::push_front_boxed`].
+    #[allow(clippy::boxed_local)]
+    #[inline(always)]
+    pub fn push_back_boxed<U>(&mut self, item: Box<U>)
+    where
+        U: core::marker::Unsize<T>,
+    {
+        self.push_back_unsize(*item);
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> DoublyList<T> {
+    /// Turns the list into a stream, that yields its items front to back.
+    #[inline(always)]
+    pub fn into_stream(self) -> IntoStream<T> {
+        IntoStream { inner: self }
+    }
+
+    /// Collects every item of `stream` into a list.
+    ///
+    /// A list also implements `Default` and `Extend`,
+    /// so `StreamExt::collect` works as well.
+    pub async fn from_stream<S>(stream: S) -> Self
+    where
+        S: futures_core::Stream<Item = T>,
+    {
+        let mut stream = core::pin::pin!(stream);
+        let mut list = DoublyList::new();
+
+        while let Some(item) = core::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            list.push_back(item);
+        }
+
+        list
+    }
+}
+
+impl DoublyList<dyn Any> {
+    /// Pushes `item` to the back of the type-erased list.
+    #[inline(always)]
+    pub fn push_any<T: Any>(&mut self, item: T) {
+        self.push_back_node(Node::<T, dyn Any>::boxed(item));
+    }
+
+    /// Returns an iterator over the items in the list of type `T`,
+    /// skipping the items of any other type.
+    #[inline(always)]
+    pub fn iter_downcast<T: Any>(&self) -> IterDowncast<'_, T> {
+        IterDowncast {
+            inner: self.iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Pops the front item of the list if it is of type `T`.
+    /// If the front item is of another type, it is left in the list and `None` is returned.
+    #[inline]
+    pub fn pop_front_downcast<T: Any>(&mut self) -> Option<T> {
+        if !self.peek_front()?.is::<T>() {
+            return None;
+        }
+
+        // The front item was just checked to be a `T`.
+        self.pop_front_node()
+            .map(|node| unsafe { Self::downcast_node(node) })
+    }
+
+    /// Pops the back item of the list if it is of type `T`.
+    /// If the back item is of another type, it is left in the list and `None` is returned.
+    #[inline]
+    pub fn pop_back_downcast<T: Any>(&mut self) -> Option<T> {
+        if !self.peek_back()?.is::<T>() {
+            return None;
+        }
+
+        // The back item was just checked to be a `T`.
+        self.pop_back_node()
+            .map(|node| unsafe { Self::downcast_node(node) })
+    }
+
+    /// Moves the item out of `node`, whose item must be a `T`.
+    ///
+    /// The node was then unsized from a `Node<T, dyn Any>`, so it can be cast back.
+    #[inline(always)]
+    unsafe fn downcast_node<T: Any>(node: Box<Node<dyn Any>>) -> T {
+        Box::from_raw(Box::into_raw(node).cast::<Node<T, dyn Any>>()).item
+    }
+}
+
+#[cfg(not(feature = "unstable"))]
+impl<T: ?Sized> Drop for DoublyList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// Dropping the list only drops its items, which `marker` tells the compiler about,
+// so the items may hold references that already dangle.
+#[cfg(feature = "unstable")]
+unsafe impl<#[may_dangle] T: ?Sized> Drop for DoublyList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a DoublyList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a mut DoublyList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for DoublyList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { inner: self }
+    }
+}
+
+impl<T> Extend<T> for DoublyList<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push_back(item)
+        }
+    }
+}
+
+/// Pushes a copy of every item to the back of the list, like `Vec` does.
+impl<'a, T: Copy + 'a> Extend<&'a T> for DoublyList<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T> FromIterator<T> for DoublyList<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut list = DoublyList::new();
+
+        for item in iter {
+            list.push_back(item)
+        }
+
+        list
+    }
+}
+
+impl<T: ?Sized> Extend<Box<Node<T>>> for DoublyList<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Box<Node<T>>>,
+    {
+        for node in iter {
+            self.push_back_node(node)
+        }
+    }
+}
+
+impl<T: ?Sized> FromIterator<Box<Node<T>>> for DoublyList<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Box<Node<T>>>,
+    {
+        let mut list = DoublyList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Appends every list by relinking its ends,
+/// so this takes O(1) per list, however long the lists are.
+impl<T: ?Sized> Extend<DoublyList<T>> for DoublyList<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = DoublyList<T>>,
+    {
+        for mut list in iter {
+            self.append(&mut list)
+        }
+    }
+}
+
+/// Concatenates the lists, appending each one in O(1).
+impl<T: ?Sized> FromIterator<DoublyList<T>> for DoublyList<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = DoublyList<T>>,
+    {
+        let mut list = DoublyList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for DoublyList<T> {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter::<T>()?.collect()
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter::<T>()?.collect()
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T: quickcheck::Arbitrary> quickcheck::Arbitrary for DoublyList<T> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Vec::<T>::arbitrary(g).into_iter().collect()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let items = self.iter().cloned().collect::<Vec<_>>();
+        Box::new(items.shrink().map(|items| items.into_iter().collect()))
+    }
+}
+
+/// A list is encoded like a `Vec`, as its length followed by its items.
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshSerialize> borsh::BorshSerialize for DoublyList<T> {
+    #[inline]
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        use core::convert::TryFrom;
+
+        let len = u32::try_from(self.len).map_err(|_| std::io::ErrorKind::InvalidData)?;
+        len.serialize(writer)?;
+
+        for item in self {
+            item.serialize(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshDeserialize> borsh::BorshDeserialize for DoublyList<T> {
+    #[inline]
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let len = u32::deserialize_reader(reader)?;
+        (0..len).map(|_| T::deserialize_reader(reader)).collect()
+    }
+}
+
+/// A list is archived as a contiguous sequence of its items,
+/// and deserializing it links up a new node for every item.
+#[cfg(feature = "rkyv")]
+impl<T: rkyv::Archive> rkyv::Archive for DoublyList<T> {
+    type Archived = rkyv::vec::ArchivedVec<T::Archived>;
+    type Resolver = rkyv::vec::VecResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: rkyv::Place<Self::Archived>) {
+        rkyv::vec::ArchivedVec::resolve_from_len(self.len, resolver, out);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, S> rkyv::Serialize<S> for DoublyList<T>
+where
+    T: rkyv::Serialize<S>,
+    S: rkyv::rancor::Fallible + rkyv::ser::Allocator + rkyv::ser::Writer + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        rkyv::vec::ArchivedVec::serialize_from_iter::<T, _, _>(self.iter(), serializer)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, D> rkyv::Deserialize<DoublyList<T>, D> for rkyv::vec::ArchivedVec<T::Archived>
+where
+    T: rkyv::Archive,
+    T::Archived: rkyv::Deserialize<T, D>,
+    D: rkyv::rancor::Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<DoublyList<T>, D::Error> {
+        self.iter()
+            .map(|item| item.deserialize(deserializer))
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ?Sized + serde::Serialize> serde::Serialize for DoublyList<T> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for DoublyList<T> {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut list = DoublyList::new();
+        Self::deserialize_in_place(deserializer, &mut list)?;
+        Ok(list)
+    }
+
+    /// Deserializes the items into the nodes already in `place`, front to back,
+    /// and only allocates nodes for the items beyond its length.
+    /// Nodes left over are dropped.
+    #[inline]
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(InPlaceVisitor { list: place })
+    }
+}
+
+/// Deserializes a sequence into the nodes of a list, see `deserialize_in_place`.
+#[cfg(feature = "serde")]
+struct InPlaceVisitor<'a, T> {
+    list: &'a mut DoublyList<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for InPlaceVisitor<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut reused = 0;
+
+        for item in self.list.iter_mut() {
+            match seq.next_element_seed(InPlaceSeed { item })? {
+                Some(()) => reused += 1,
+                None => break,
+            }
+        }
+
+        if reused < self.list.len() {
+            self.list.truncate(reused);
+            return Ok(());
+        }
+
+        while let Some(item) = seq.next_element()? {
+            self.list.push_back(item);
+        }
+
+        Ok(())
+    }
+}
+
+/// Deserializes an item in place, reusing whatever it owns.
+#[cfg(feature = "serde")]
+struct InPlaceSeed<'a, T> {
+    item: &'a mut T,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::DeserializeSeed<'de> for InPlaceSeed<'_, T> {
+    type Value = ();
+
+    #[inline(always)]
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        T::deserialize_in_place(deserializer, self.item)
+    }
+}
+
+pub struct Iter<'a, T: ?Sized> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+// The iterator hands out `&T`s, so it behaves like a `&T`,
+// which is `Send` and `Sync` only when `T` is `Sync`.
+unsafe impl<T: ?Sized + Sync> Send for Iter<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Iter<'_, T> {}
+
+impl<T: ?Sized> Copy for Iter<'_, T> {}
+
+impl<T: ?Sized> Clone for Iter<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Iter<'_, T> {
+    /// Returns an iterator over the chain of `len` nodes from `head` to `tail`.
+    ///
+    /// # Safety
+    /// The chain must be properly linked,
+    /// and stay alive and unchanged for the lifetime of the iterator.
+    #[inline(always)]
+    pub(crate) const unsafe fn from_raw_parts(head: Link<T>, tail: Link<T>, len: usize) -> Self {
+        Self {
+            head,
+            tail,
+            len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Iter<'a, T> {
+    /// Hands the items to `f` from the front, until it breaks.
+    ///
+    /// This stands in for `try_fold` on stable,
+    /// so the short-circuiting methods walk the links directly too.
+    #[inline]
+    fn walk<B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        F: FnMut(&'a T) -> ControlFlow<B>,
+    {
+        while self.len != 0 {
+            // The head is linked while `len` is nonzero,
+            // and the iterator moves past it before `f` can break.
+            let node = unsafe { &*self.head.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+
+            if let ControlFlow::Break(found) = f(&node.item) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
+
+    /// Hands the items to `f` from the back, until it breaks.
+    #[inline]
+    fn walk_back<B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        F: FnMut(&'a T) -> ControlFlow<B>,
+    {
+        while self.len != 0 {
+            // Mirrors `walk`, from the tail.
+            let node = unsafe { &*self.tail.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+
+            if let ControlFlow::Break(found) = f(&node.item) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the start and end of `range` within a list of `len` items.
+///
+/// # Panics
+/// Panics if the range is out of bounds.
+#[inline]
+fn resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(
+        start <= end,
+        "range start (is {}) should be <= range end (is {})",
+        start,
+        end
+    );
+    assert!(
+        end <= len,
+        "range end (is {}) should be <= len (is {})",
+        end,
+        len
+    );
+
+    (start, end)
+}
+
+/// Returns the node `n` nodes after `head`, in the chain of `len` nodes from `head` to `tail`,
+/// walking from whichever end is closer.
+///
+/// # Safety
+/// The chain must be properly linked, and `n` must be less than `len`.
+#[inline]
+unsafe fn seek<T: ?Sized>(head: Link<T>, tail: Link<T>, len: usize, n: usize) -> NonNull<Node<T>> {
+    let (mut node, steps, forward) = match n <= len - 1 - n {
+        true => (head, n, true),
+        false => (tail, len - 1 - n, false),
+    };
+
+    for _ in 0..steps {
+        node = match forward {
+            true => (*node.unwrap().as_ptr()).next,
+            false => (*node.unwrap().as_ptr()).prev,
+        };
+    }
+
+    node.unwrap()
+}
+
+impl<T: ?Sized + Debug> Debug for Iter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.len).finish()
+    }
+}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.head.map(|node| unsafe {
+                // Need an unbound lifetime to get 'a
+                let node = &*node.as_ptr();
+                self.len -= 1;
+                self.head = node.next;
+                &node.item
+            })
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    /// Returns the back item, without walking the list.
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline(always)]
+    fn count(self) -> usize {
+        self.len
+    }
+
+    /// Walks the chain from `head` to `tail`, without counting down `len`.
+    #[inline]
+    fn fold<B, F>(self, mut acc: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let (mut node, tail) = match (self.head, self.tail) {
+            (Some(head), Some(tail)) if self.len != 0 => (head, tail),
+            _ => return acc,
+        };
+
+        // Every node up to `tail` lies within the chain borrowed for 'a,
+        // so each is linked to the next one until `tail` is reached.
+        unsafe {
+            loop {
+                let current = &*node.as_ptr();
+                let next = current.next;
+                acc = f(acc, &current.item);
+
+                if ptr::addr_eq(node.as_ptr(), tail.as_ptr()) {
+                    return acc;
+                }
+
+                node = next.unwrap_unchecked();
+            }
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn try_fold<B, F, R>(&mut self, mut acc: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        while self.len != 0 {
+            // The head is linked while `len` is nonzero,
+            // and the iterator moves past it before `f` can bail out.
+            let node = unsafe { &*self.head.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            acc = f(acc, &node.item)?;
+        }
+
+        R::from_output(acc)
+    }
+
+    #[inline]
+    fn find<P>(&mut self, mut pred: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.walk(|item| match pred(&item) {
+            true => ControlFlow::Break(item),
+            false => ControlFlow::Continue(()),
+        })
+    }
+
+    #[inline]
+    fn find_map<B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        F: FnMut(Self::Item) -> Option<B>,
+    {
+        self.walk(|item| match f(item) {
+            Some(found) => ControlFlow::Break(found),
+            None => ControlFlow::Continue(()),
+        })
+    }
+
+    #[inline]
+    fn any<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        self.walk(|item| match f(item) {
+            true => ControlFlow::Break(()),
+            false => ControlFlow::Continue(()),
+        })
+        .is_some()
+    }
+
+    #[inline]
+    fn all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        self.walk(|item| match f(item) {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        })
+        .is_none()
+    }
+
+    #[inline]
+    fn position<P>(&mut self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        let mut index = 0;
+
+        self.walk(|item| match pred(item) {
+            true => ControlFlow::Break(index),
+            false => {
+                index += 1;
+                ControlFlow::Continue(())
+            }
+        })
+    }
+
+    #[inline]
+    fn rposition<P>(&mut self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        let mut index = self.len;
+
+        self.walk_back(|item| {
+            index -= 1;
+
+            match pred(item) {
+                true => ControlFlow::Break(index),
+                false => ControlFlow::Continue(()),
+            }
+        })
+    }
+
+    /// Skips `n` items, walking at most half of the remaining nodes.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+
+        // The chain is borrowed for 'a, and `n` lies within it.
+        unsafe {
+            let node = &*seek(self.head, self.tail, self.len, n).as_ptr();
+            self.len -= n + 1;
+            self.head = node.next;
+            Some(&node.item)
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), core::num::NonZero<usize>> {
+        let step = n.min(self.len);
+
+        if step != 0 {
+            self.nth(step - 1);
+        }
+
+        core::num::NonZero::new(n - step).map_or(Ok(()), Err)
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for Iter<'a, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.tail.map(|node| unsafe {
+                // Need an unbound lifetime to get 'a
+                let node = &*node.as_ptr();
+                self.len -= 1;
+                self.tail = node.prev;
+                &node.item
+            })
+        }
+    }
+
+    /// Walks the chain from `tail` back to `head`, without counting down `len`.
+    #[inline]
+    fn rfold<B, F>(self, mut acc: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let (head, mut node) = match (self.head, self.tail) {
+            (Some(head), Some(tail)) if self.len != 0 => (head, tail),
+            _ => return acc,
+        };
+
+        // Mirrors `fold`, following the `prev` links instead.
+        unsafe {
+            loop {
+                let current = &*node.as_ptr();
+                let prev = current.prev;
+                acc = f(acc, &current.item);
+
+                if ptr::addr_eq(node.as_ptr(), head.as_ptr()) {
+                    return acc;
+                }
+
+                node = prev.unwrap_unchecked();
+            }
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn try_rfold<B, F, R>(&mut self, mut acc: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        while self.len != 0 {
+            // Mirrors `try_fold`, from the tail.
+            let node = unsafe { &*self.tail.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            acc = f(acc, &node.item)?;
+        }
+
+        R::from_output(acc)
+    }
+
+    #[inline]
+    fn rfind<P>(&mut self, mut pred: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.walk_back(|item| match pred(&item) {
+            true => ControlFlow::Break(item),
+            false => ControlFlow::Continue(()),
+        })
+    }
+
+    /// Skips `n` items from the back, walking at most half of the remaining nodes.
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+
+        // The chain is borrowed for 'a, and `n` lies within it.
+        unsafe {
+            let node = &*seek(self.head, self.tail, self.len, self.len - 1 - n).as_ptr();
+            self.len -= n + 1;
+            self.tail = node.prev;
+            Some(&node.item)
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), core::num::NonZero<usize>> {
+        let step = n.min(self.len);
+
+        if step != 0 {
+            self.nth_back(step - 1);
+        }
+
+        core::num::NonZero::new(n - step).map_or(Ok(()), Err)
+    }
+}
+
+impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
+impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+
+// `len` counts exactly the nodes left between `head` and `tail`.
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized> core::iter::TrustedLen for Iter<'_, T> {}
+
+/// An iterator over the items of type `T` in a `DoublyList<dyn Any>`.
+pub struct IterDowncast<'a, T> {
+    inner: Iter<'a, dyn Any>,
+    marker: PhantomData<&'a T>,
+}
+
+impl<T> Clone for IterDowncast<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        IterDowncast {
+            inner: self.inner,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for IterDowncast<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IterDowncast")
+            .field(&self.inner.len)
+            .finish()
+    }
+}
+
+impl<'a, T: Any> Iterator for IterDowncast<'a, T> {
+    type Item = &'a T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find_map(|item| item.downcast_ref())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<'a, T: Any> DoubleEndedIterator for IterDowncast<'a, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .rev()
+            .find_map(|item| item.downcast_ref())
+    }
+}
+
+impl<T: Any> FusedIterator for IterDowncast<'_, T> {}
+
+pub struct IterMut<'a, T: ?Sized> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+// The iterator hands out `&mut T`s, so it behaves like a `&mut T`.
+unsafe impl<T: ?Sized + Send> Send for IterMut<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for IterMut<'_, T> {}
+
+impl<T: ?Sized + Debug> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IterMut").field(&self.len).finish()
+    }
+}
+
+impl<'a, T: ?Sized> IterMut<'a, T> {
+    /// Hands the items to `f` from the front, until it breaks, like `Iter::walk`.
+    #[inline]
+    fn walk<B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        F: FnMut(&'a mut T) -> ControlFlow<B>,
+    {
+        while self.len != 0 {
+            // As in `Iter::walk`, but handing out the item mutably.
+            let node = unsafe { &mut *self.head.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+
+            if let ControlFlow::Break(found) = f(&mut node.item) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
+
+    /// Hands the items to `f` from the back, until it breaks.
+    #[inline]
+    fn walk_back<B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        F: FnMut(&'a mut T) -> ControlFlow<B>,
+    {
+        while self.len != 0 {
+            // Mirrors `walk`, from the tail.
+            let node = unsafe { &mut *self.tail.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+
+            if let ControlFlow::Break(found) = f(&mut node.item) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.head.map(|node| unsafe {
+                // Need an unbound lifetime to get 'a
+                let node = &mut *node.as_ptr();
+                self.len -= 1;
+                self.head = node.next;
+                &mut node.item
+            })
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    /// Returns the back item, without walking the list.
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline(always)]
+    fn count(self) -> usize {
+        self.len
+    }
+
+    /// Walks the chain from `head` to `tail` like `Iter::fold`.
+    #[inline]
+    fn fold<B, F>(self, mut acc: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let (mut node, tail) = match (self.head, self.tail) {
+            (Some(head), Some(tail)) if self.len != 0 => (head, tail),
+            _ => return acc,
+        };
+
+        // As in `Iter::fold`, the chain up to `tail` is borrowed for 'a,
+        // and every item is handed out once.
+        unsafe {
+            loop {
+                let current = &mut *node.as_ptr();
+                let next = current.next;
+                acc = f(acc, &mut current.item);
+
+                if ptr::addr_eq(node.as_ptr(), tail.as_ptr()) {
+                    return acc;
+                }
+
+                node = next.unwrap_unchecked();
+            }
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn try_fold<B, F, R>(&mut self, mut acc: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        while self.len != 0 {
+            // As in `Iter::try_fold`, but handing out the item mutably.
+            let node = unsafe { &mut *self.head.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            acc = f(acc, &mut node.item)?;
+        }
+
+        R::from_output(acc)
+    }
+
+    #[inline]
+    fn find<P>(&mut self, mut pred: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.walk(|item| match pred(&item) {
+            true => ControlFlow::Break(item),
+            false => ControlFlow::Continue(()),
+        })
+    }
+
+    #[inline]
+    fn find_map<B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        F: FnMut(Self::Item) -> Option<B>,
+    {
+        self.walk(|item| match f(item) {
+            Some(found) => ControlFlow::Break(found),
+            None => ControlFlow::Continue(()),
+        })
+    }
+
+    #[inline]
+    fn any<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        self.walk(|item| match f(item) {
+            true => ControlFlow::Break(()),
+            false => ControlFlow::Continue(()),
+        })
+        .is_some()
+    }
+
+    #[inline]
+    fn all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        self.walk(|item| match f(item) {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        })
+        .is_none()
+    }
+
+    #[inline]
+    fn position<P>(&mut self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        let mut index = 0;
+
+        self.walk(|item| match pred(item) {
+            true => ControlFlow::Break(index),
+            false => {
+                index += 1;
+                ControlFlow::Continue(())
+            }
+        })
+    }
+
+    #[inline]
+    fn rposition<P>(&mut self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        let mut index = self.len;
+
+        self.walk_back(|item| {
+            index -= 1;
+
+            match pred(item) {
+                true => ControlFlow::Break(index),
+                false => ControlFlow::Continue(()),
+            }
+        })
+    }
+
+    /// Skips `n` items, walking at most half of the remaining nodes.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+
+        // The chain is borrowed for 'a, and `n` lies within it.
+        unsafe {
+            let node = &mut *seek(self.head, self.tail, self.len, n).as_ptr();
+            self.len -= n + 1;
+            self.head = node.next;
+            Some(&mut node.item)
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), core::num::NonZero<usize>> {
+        let step = n.min(self.len);
+
+        if step != 0 {
+            self.nth(step - 1);
+        }
+
+        core::num::NonZero::new(n - step).map_or(Ok(()), Err)
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.tail.map(|node| unsafe {
+                // Need an unbound lifetime to get 'a
+                let node = &mut *node.as_ptr();
+                self.len -= 1;
+                self.tail = node.prev;
+                &mut node.item
+            })
+        }
+    }
+
+    /// Walks the chain from `tail` back to `head` like `Iter::rfold`.
+    #[inline]
+    fn rfold<B, F>(self, mut acc: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let (head, mut node) = match (self.head, self.tail) {
+            (Some(head), Some(tail)) if self.len != 0 => (head, tail),
+            _ => return acc,
+        };
+
+        // Mirrors `fold`, handing out every item once.
+        unsafe {
+            loop {
+                let current = &mut *node.as_ptr();
+                let prev = current.prev;
+                acc = f(acc, &mut current.item);
+
+                if ptr::addr_eq(node.as_ptr(), head.as_ptr()) {
+                    return acc;
+                }
+
+                node = prev.unwrap_unchecked();
+            }
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn try_rfold<B, F, R>(&mut self, mut acc: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        while self.len != 0 {
+            // Mirrors `try_fold`, handing out the item mutably.
+            let node = unsafe { &mut *self.tail.unwrap_unchecked().as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            acc = f(acc, &mut node.item)?;
+        }
+
+        R::from_output(acc)
+    }
+
+    #[inline]
+    fn rfind<P>(&mut self, mut pred: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.walk_back(|item| match pred(&item) {
+            true => ControlFlow::Break(item),
+            false => ControlFlow::Continue(()),
+        })
+    }
+
+    /// Skips `n` items from the back, walking at most half of the remaining nodes.
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+
+        // The chain is borrowed for 'a, and `n` lies within it.
+        unsafe {
+            let node = &mut *seek(self.head, self.tail, self.len, self.len - 1 - n).as_ptr();
+            self.len -= n + 1;
+            self.tail = node.prev;
+            Some(&mut node.item)
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[inline]
+    fn advance_back_by(&mut self, n: usize) -> Result<(), core::num::NonZero<usize>> {
+        let step = n.min(self.len);
+
+        if step != 0 {
+            self.nth_back(step - 1);
+        }
+
+        core::num::NonZero::new(n - step).map_or(Ok(()), Err)
+    }
+}
+
+impl<T: ?Sized> FusedIterator for IterMut<'_, T> {}
+impl<T: ?Sized> ExactSizeIterator for IterMut<'_, T> {}
+
+// Like `Iter`, `len` counts exactly the nodes left.
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized> core::iter::TrustedLen for IterMut<'_, T> {}
+
+/// An iterator over the pairs of adjacent items of a [`DoublyList`],
+/// returned by [`DoublyList::iter_pairs`].
+pub struct Pairs<'a, T: ?Sized> {
+    /// The items from the first of the next pair to the second of the next pair from the back.
+    iter: Iter<'a, T>,
+}
+
+impl<T: ?Sized> Copy for Pairs<'_, T> {}
+
+impl<T: ?Sized> Clone for Pairs<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Pairs<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Pairs").field(&self.len()).finish()
+    }
+}
+
+impl<'a, T: ?Sized> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let second = self.iter.clone().next()?;
+        Some((first, second))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len().saturating_sub(1);
+        (len, Some(len))
+    }
+}
+
+impl<T: ?Sized> DoubleEndedIterator for Pairs<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let second = self.iter.next_back()?;
+        let first = self.iter.clone().next_back()?;
+        Some((first, second))
+    }
+}
+
+impl<T: ?Sized> FusedIterator for Pairs<'_, T> {}
+impl<T: ?Sized> ExactSizeIterator for Pairs<'_, T> {}
+
+/// A cursor over the pairs of adjacent items of a [`DoublyList`],
+/// returned by [`DoublyList::iter_pairs_mut`].
+pub struct PairsMut<'a, T: ?Sized> {
+    head: Link<T>,
+    /// The number of items left, counting the first of the next pair.
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+// The cursor hands out `&mut T`s, so it behaves like a `&mut T`.
+unsafe impl<T: ?Sized + Send> Send for PairsMut<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for PairsMut<'_, T> {}
+
+impl<T: ?Sized + Debug> Debug for PairsMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PairsMut")
+            .field(&self.len.saturating_sub(1))
+            .finish()
+    }
+}
+
+impl<T: ?Sized> PairsMut<'_, T> {
+    /// Returns the next pair of adjacent items,
+    /// or `None` once the second item of the last pair has been returned.
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<(&mut T, &mut T)> {
+        if self.len < 2 {
+            return None;
+        }
+
+        // Both nodes lie within the borrowed chain and are distinct,
+        // and the pair borrows the cursor, so it is gone before they are handed out again.
+        unsafe {
+            let first = &mut *self.head?.as_ptr();
+            let second = &mut *first.next?.as_ptr();
+            self.head = first.next;
+            self.len -= 1;
+            Some((&mut first.item, &mut second.item))
+        }
+    }
+}
+
+pub struct IntoIter<T> {
+    inner: DoublyList<T>,
+}
+
+impl<T: Debug> Debug for IntoIter<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.inner).finish()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.inner.len, Some(self.inner.len))
+    }
+
+    /// Returns the back item, and drops all others.
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline(always)]
+    fn count(self) -> usize {
+        self.inner.len
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.pop_back()
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
 impl<T> ExactSizeIterator for IntoIter<T> {}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Items are popped from the list, whose `len` is exact.
+#[cfg(feature = "unstable")]
+unsafe impl<T> core::iter::TrustedLen for IntoIter<T> {}
+
+/// A view of a run of adjacent items of a [`DoublyList`],
+/// returned by [`DoublyList::slice`] and [`DoublyList::slice_between`].
+///
+/// Like a `&[T]`, a view is cheap to copy, and can be handed around
+/// instead of copying the part of the list it views.
+pub struct ListSlice<'a, T: ?Sized> {
+    iter: Iter<'a, T>,
+}
+
+impl<T: ?Sized> Copy for ListSlice<'_, T> {}
+
+impl<T: ?Sized> Clone for ListSlice<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Default for ListSlice<'_, T> {
+    /// Returns an empty view.
+    #[inline(always)]
+    fn default() -> Self {
+        // An empty chain has nothing to keep alive.
+        Self {
+            iter: unsafe { Iter::from_raw_parts(None, None, 0) },
+        }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for ListSlice<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ?Sized + PartialEq<U>, U: ?Sized> PartialEq<ListSlice<'_, U>> for ListSlice<'_, T> {
+    #[inline]
+    fn eq(&self, other: &ListSlice<'_, U>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for ListSlice<'_, T> {}
+
+impl<'a, T: ?Sized> ListSlice<'a, T> {
+    /// Returns the number of items in the view.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    /// Returns `true` if the view is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the first item of the view.
+    #[inline]
+    pub fn first(&self) -> Option<&'a T> {
+        self.iter.clone().next()
+    }
+
+    /// Returns the last item of the view.
+    #[inline]
+    pub fn last(&self) -> Option<&'a T> {
+        self.iter.clone().next_back()
+    }
+
+    /// Returns an iterator over the items of the view, front to back.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'a, T> {
+        self.iter
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for ListSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &ListSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter
+    }
+}
+
+/// A mutable view of a run of adjacent items of a [`DoublyList`],
+/// returned by [`DoublyList::slice_mut`] and [`DoublyList::slice_between_mut`].
+///
+/// Next to changing its items, the view can push and remove items at its own ends,
+/// or anywhere within it with [`retain`](Self::retain).
+/// The items around the view are out of its reach,
+/// so it can be handed to code that should only edit its part of the list,
+/// and [`split_at_mut`](Self::split_at_mut) hands out two views that can be edited at once.
+///
+/// The view cuts its nodes out of the list, and links them back in when it is dropped,
+/// between the same nodes it was cut from.
+pub struct ListSliceMut<'a, T: ?Sized> {
+    /// The nodes of the view, as a list of their own.
+    nodes: DoublyList<T>,
+    home: Home<'a, T>,
+    /// Where the halves of the view go back to, while it is split.
+    seam: Seam<T>,
+}
+
+/// Where the nodes of a [`ListSliceMut`] go back to when it is dropped.
+enum Home<'a, T: ?Sized> {
+    /// In between the nodes `before` and `after` of `list`,
+    /// where `None` stands for the front or the back.
+    List {
+        list: &'a mut DoublyList<T>,
+        before: Link<T>,
+        after: Link<T>,
+    },
+
+    /// Into `seam.halves[half]`, and then into `nodes` once both halves are back.
+    Half {
+        seam: &'a Seam<T>,
+        half: usize,
+        nodes: NonNull<DoublyList<T>>,
+    },
+}
+
+/*
+ * Each half of a split view only writes its own slot of the seam,
+ * and the half that comes back last joins both slots,
+ * after `pending` tells it the other half is done with its slot.
+ */
+struct Seam<T: ?Sized> {
+    halves: [UnsafeCell<DoublyList<T>>; 2],
+    pending: AtomicUsize,
+}
+
+impl<T: ?Sized> Seam<T> {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self {
+            halves: [
+                UnsafeCell::new(DoublyList::new()),
+                UnsafeCell::new(DoublyList::new()),
+            ],
+            pending: AtomicUsize::new(0),
+        }
+    }
+}
+
+// The view only hands out `&T`s and `&mut T`s, like the `&mut DoublyList<T>` it borrows,
+// and the seam is only touched through `&mut self`, and by the halves as described above.
+unsafe impl<T: ?Sized + Send> Send for ListSliceMut<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for ListSliceMut<'_, T> {}
+
+impl<T: ?Sized + Debug> Debug for ListSliceMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ?Sized> Drop for ListSliceMut<'_, T> {
+    fn drop(&mut self) {
+        match &mut self.home {
+            // Nothing but the view touched the list while it lived,
+            // so `before` and `after` are still linked next to each other.
+            Home::List {
+                list,
+                before,
+                after,
+            } => unsafe { list.splice_private(*before, *after, &mut self.nodes) },
+
+            Home::Half { seam, half, nodes } => {
+                // The slot is only written by this half, and read by the half that comes back last.
+                unsafe { (*seam.halves[*half].get()).append(&mut self.nodes) };
+
+                if seam.pending.fetch_sub(1, atomic::Ordering::AcqRel) == 1 {
+                    // Both halves are back, and the view they were split from is borrowed by them.
+                    unsafe {
+                        let front = &mut *seam.halves[0].get();
+                        front.append(&mut *seam.halves[1].get());
+                        (*nodes.as_ptr()).append(front);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: ?Sized> ListSliceMut<'a, T> {
+    #[inline]
+    fn new(
+        list: &'a mut DoublyList<T>,
+        before: Link<T>,
+        after: Link<T>,
+        nodes: DoublyList<T>,
+    ) -> Self {
+        Self {
+            nodes,
+            home: Home::List {
+                list,
+                before,
+                after,
+            },
+            seam: Seam::new(),
+        }
+    }
+}
+
+impl<T: ?Sized> ListSliceMut<'_, T> {
+    /// Returns the number of items in the view.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the view is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the first item of the view.
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        self.nodes.peek_front()
+    }
+
+    /// Returns a mutable reference to the first item of the view.
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.nodes.peek_front_mut()
+    }
+
+    /// Returns the last item of the view.
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        self.nodes.peek_back()
+    }
+
+    /// Returns a mutable reference to the last item of the view.
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.nodes.peek_back_mut()
+    }
+
+    /// Returns an iterator over the items of the view, front to back.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.nodes.iter()
+    }
+
+    /// Returns an iterator over mutable references to the items of the view, front to back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.nodes.iter_mut()
+    }
+
+    /// Returns an immutable view of the same items.
+    #[inline]
+    pub fn as_slice(&self) -> ListSlice<'_, T> {
+        ListSlice { iter: self.iter() }
+    }
+
+    /// Links `node` in at the front of the view.
+    #[inline]
+    pub fn push_front_node(&mut self, node: Box<Node<T>>) {
+        self.nodes.push_front_node(node);
+    }
+
+    /// Links `node` in at the back of the view.
+    #[inline]
+    pub fn push_back_node(&mut self, node: Box<Node<T>>) {
+        self.nodes.push_back_node(node);
+    }
+
+    /// Unlinks the front node of the view, and returns it.
+    #[inline]
+    pub fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
+        self.nodes.pop_front_node()
+    }
+
+    /// Unlinks the back node of the view, and returns it.
+    #[inline]
+    pub fn pop_back_node(&mut self) -> Option<Box<Node<T>>> {
+        self.nodes.pop_back_node()
+    }
+
+    /// Removes the items of the view for which `f` returns `false`,
+    /// keeping the others in order.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        drop(self.nodes.retain_into(f));
+    }
+
+    /// Splits the view in two at `mid`: one of the first `mid` items, and one of the others.
+    ///
+    /// Both views can be edited at once, even on different threads,
+    /// and neither reaches the items of the other.
+    /// Once both are dropped, their items are back in this view, in order.
+    ///
+    /// # Panics
+    /// Panics if `mid` is greater than the length of the view.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::doublylist::DoublyList;
+    /// let mut list = (0..6).collect::<DoublyList<u32>>();
+    /// let mut slice = list.slice_mut(1..5);
+    ///
+    /// let (mut left, mut right) = slice.split_at_mut(2);
+    /// std::thread::scope(|scope| {
+    ///     scope.spawn(|| left.push_back(10));
+    ///     scope.spawn(|| {
+    ///         right.pop_front();
+    ///         right.push_front(20);
+    ///     });
+    /// });
+    ///
+    /// drop((left, right));
+    /// assert_eq!(slice.len(), 5);
+    ///
+    /// drop(slice);
+    /// assert_eq!(list, [0, 1, 2, 10, 20, 4, 5]);
+    /// ```
+    pub fn split_at_mut(&mut self, mid: usize) -> (ListSliceMut<'_, T>, ListSliceMut<'_, T>) {
+        let len = self.nodes.len();
+        assert!(mid <= len, "mid (is {}) should be <= len (is {})", mid, len);
+
+        let mut front = mem::take(&mut self.nodes);
+
+        let back = match mid < len {
+            // `mid` lies within the nodes, with `mid` nodes in front of it.
+            true => unsafe {
+                let at = seek(front.head, front.tail, len, mid);
+                let front_half = front.split_before_private(at, mid);
+                mem::replace(&mut front, front_half)
+            },
+            false => DoublyList::new(),
+        };
+
+        *self.seam.pending.get_mut() = 2;
+
+        let nodes = NonNull::from(&mut self.nodes);
+        let seam = &self.seam;
+        let half = |half, items| ListSliceMut {
+            nodes: items,
+            home: Home::Half { seam, half, nodes },
+            seam: Seam::new(),
+        };
+
+        (half(0, front), half(1, back))
+    }
+}
+
+impl<T> ListSliceMut<'_, T> {
+    /// Pushes `item` to the front of the view.
+    #[inline(always)]
+    pub fn push_front(&mut self, item: T) {
+        self.push_front_node(Node::boxed(item));
+    }
+
+    /// Pushes `item` to the back of the view.
+    #[inline(always)]
+    pub fn push_back(&mut self, item: T) {
+        self.push_back_node(Node::boxed(item));
+    }
+
+    /// Removes the front item of the view, and returns it.
+    #[inline(always)]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.pop_front_node().map(Node::into_item)
+    }
+
+    /// Removes the back item of the view, and returns it.
+    #[inline(always)]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.pop_back_node().map(Node::into_item)
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a ListSliceMut<'_, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a mut ListSliceMut<'_, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An endless iterator over the items of a [`DoublyList`],
+/// returned by [`DoublyList::cycle`].
+pub struct Cycle<'a, T: ?Sized> {
+    head: Link<T>,
+    /// The node of the next item, `None` when the next item is the front one again.
+    cursor: Link<T>,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+// Like `Iter`, the iterator hands out `&T`s.
+unsafe impl<T: ?Sized + Sync> Send for Cycle<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Cycle<'_, T> {}
+
+impl<T: ?Sized> Copy for Cycle<'_, T> {}
+
+impl<T: ?Sized> Clone for Cycle<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Debug for Cycle<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cycle")
+            .field("empty", &self.head.is_none())
+            .finish()
+    }
+}
+
+impl<'a, T: ?Sized> Iterator for Cycle<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.cursor.or(self.head)?;
+
+        // The list is borrowed for 'a, so all of its nodes stay linked.
+        unsafe {
+            let node = &*node.as_ptr();
+            self.cursor = node.next;
+            Some(&node.item)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.head {
+            None => (0, Some(0)),
+            Some(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T: ?Sized> FusedIterator for Cycle<'_, T> {}
+
+/// An iterator removing a range of items from a [`DoublyList`],
+/// returned by [`DoublyList::drain`].
+pub struct Drain<'a, T> {
+    /// The nodes cut out of the list, which are no longer linked into it.
+    inner: DoublyList<T>,
+    marker: PhantomData<&'a mut DoublyList<T>>,
+}
+
+impl<T: Debug> Debug for Drain<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.inner).finish()
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.inner.len, Some(self.inner.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.pop_back()
+    }
+}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+/// An iterator over the owned nodes of a list, see [`DoublyList::into_nodes`].
+pub struct IntoNodes<T: ?Sized> {
+    inner: DoublyList<T>,
+}
+
+impl<T: ?Sized + Debug> Debug for IntoNodes<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoNodes").field(&self.inner).finish()
+    }
+}
+
+impl<T: ?Sized> Iterator for IntoNodes<T> {
+    type Item = Box<Node<T>>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.pop_front_node()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.inner.len, Some(self.inner.len))
+    }
+}
+
+impl<T: ?Sized> DoubleEndedIterator for IntoNodes<T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.pop_back_node()
+    }
+}
+
+impl<T: ?Sized> FusedIterator for IntoNodes<T> {}
+impl<T: ?Sized> ExactSizeIterator for IntoNodes<T> {}
+
+// Nodes are popped from the list, whose `len` is exact.
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized> core::iter::TrustedLen for IntoNodes<T> {}
+
+/// An iterator over the segments of a list,
+/// see [`DoublyList::split`] and [`DoublyList::split_inclusive`].
+pub struct Split<T: ?Sized, F> {
+    /// The rest of the list, `None` once the last segment has been returned.
+    rest: Option<DoublyList<T>>,
+    pred: F,
+    inclusive: bool,
+}
+
+impl<T: ?Sized + Debug, F> Debug for Split<T, F> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Split")
+            .field("rest", &self.rest)
+            .field("inclusive", &self.inclusive)
+            .finish()
+    }
+}
+
+impl<T: ?Sized, F> Iterator for Split<T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = DoublyList<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.as_mut()?;
+        let mut cursor = rest.head;
+        let mut len = 0;
+
+        while let Some(node) = cursor {
+            // The cursor only walks the nodes linked into `rest`.
+            unsafe {
+                if (self.pred)(&(*node.as_ptr()).item) {
+                    let mut segment = rest.split_before_private(node, len);
+                    let separator = rest.unlink_node_private(node);
+
+                    if self.inclusive {
+                        segment.push_back_node(separator);
+                    }
+
+                    if self.inclusive && rest.is_empty() {
+                        self.rest = None;
+                    }
+
+                    return Some(segment);
+                }
+
+                cursor = (*node.as_ptr()).next;
+            }
+
+            len += 1;
+        }
+
+        let rest = self.rest.take()?;
+        match self.inclusive && rest.is_empty() {
+            true => None,
+            false => Some(rest),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.rest {
+            None => (0, Some(0)),
+            Some(rest) if self.inclusive => (rest.len.min(1), Some(rest.len)),
+            Some(rest) => (1, Some(rest.len + 1)),
+        }
+    }
+}
+
+impl<T: ?Sized, F> FusedIterator for Split<T, F> where F: FnMut(&T) -> bool {}
+
+/// A stream over owned items in the list.
+#[cfg(feature = "futures")]
+pub struct IntoStream<T> {
+    inner: DoublyList<T>,
+}
+
+#[cfg(feature = "futures")]
+impl<T: Debug> Debug for IntoStream<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IntoStream").field(&self.inner).finish()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> futures_core::Stream for IntoStream<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        _: &mut core::task::Context,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        core::task::Poll::Ready(self.inner.pop_front())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.inner.len, Some(self.inner.len))
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<T> futures_core::FusedStream for IntoStream<T> {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        let mut list = DoublyList::new();
+
+        list.push_front(0);
+        list.push_front(10);
+        list.push_back(20);
+        list.push_front(11);
+        list.push_back(21);
+        // list = 11 10 20 21
+        let mut iter = list.iter();
+
+        assert_eq!(iter.next(), Some(&11));
+        assert_eq!(iter.next_back(), Some(&21));
+        assert_eq!(iter.next(), Some(&10));
+        assert_eq!(iter.next_back(), Some(&20));
+        assert_eq!(iter.next_back(), Some(&0));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_unsized_list() {
+        use core::any::Any;
+
+        let mut list = DoublyList::new();
+
+        let node: Box<Node<dyn Any>> = Node::boxed(10usize);
+
+        list.push_front_node(node);
+    }
+
+    #[test]
+    fn test_join() {
+        let list = ["a", "b", "c"].iter().collect::<DoublyList<_>>();
+        assert_eq!(list.join(", ").to_string(), "a, b, c");
+        assert_eq!(format!("{:>2}", list.join("")), " a b c");
+
+        let empty: DoublyList<str> = DoublyList::new();
+        assert_eq!(empty.join(", ").to_string(), "");
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes = [1, 10, 1, 20, 1, 30, 0];
+        let list = DoublyList::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), [&10, &20, &30]);
+
+        let rest = DoublyList::<u8>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert_eq!(
+            rest.iter().collect::<Vec<_>>(),
+            list.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn test_quickcheck() {
+        fn reversed_twice(list: DoublyList<u32>) -> bool {
+            let items = list.iter().copied().collect::<Vec<_>>();
+            let reversed = list.into_iter().rev().collect::<DoublyList<_>>();
+            reversed.into_iter().rev().eq(items)
+        }
+
+        quickcheck::quickcheck(reversed_twice as fn(DoublyList<u32>) -> bool);
+    }
+
+    #[test]
+    fn test_slice_layout() {
+        fn check<T: Default + Copy, const N: usize>() {
+            let node: Box<Node<[T]>> = Node::boxed([T::default(); N]);
+            assert_eq!(
+                Layout::for_value(&*node),
+                slice_node::layout::<T, Node<[T]>>(N)
+            );
+
+            let copy = Node::boxed_slice(&[T::default(); N]);
+            assert_eq!(copy.item().len(), N);
+
+            let moved = Node::from_vec(vec![T::default(); N]);
+            assert_eq!(Layout::for_value(&*moved), Layout::for_value(&*copy));
+        }
+
+        check::<u8, 0>();
+        check::<u8, 13>();
+        check::<u16, 3>();
+        check::<u64, 5>();
+        check::<u128, 2>();
+        check::<(), 7>();
+        check::<(u8, u32), 9>();
+    }
+
+    #[test]
+    fn test_boxed_slice_panic() {
+        use std::{panic, rc::Rc};
+
+        struct Bomb(Rc<()>);
+
+        impl Clone for Bomb {
+            fn clone(&self) -> Self {
+                assert!(Rc::strong_count(&self.0) < 4, "boom");
+                Bomb(self.0.clone())
+            }
+        }
+
+        let counter = Rc::new(());
+        let items = vec![Bomb(counter.clone()), Bomb(counter.clone())];
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| Node::boxed_slice(&items)));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        let node = Node::from_vec(items);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(node);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut list = DoublyList::from_elem(String::from("a"), 2);
+        list.resize(4, String::from("b"));
+        assert!(list.iter().eq(["a", "a", "b", "b"]));
+
+        list.resize(1, String::new());
+        assert!(list.iter().eq(["a"]));
+
+        list.truncate(3);
+        list.resize(1, String::new());
+        assert_eq!(list.len(), 1);
+        list.assert_invariants();
+    }
+
+    #[test]
+    fn test_node_ref() {
+        let mut list = DoublyList::new();
+
+        let a = list.push_back_ref(1);
+        let b = list.push_back_ref(2);
+        let c = list.push_back_ref(3);
+
+        unsafe {
+            *list.get_mut(b) += 10;
+            assert_eq!(list.get(b), &12);
+
+            list.move_to_front(c);
+            assert_eq!(list.iter().collect::<Vec<_>>(), [&3, &1, &12]);
+
+            // The handle and the list both keep pointing at the moved node.
+            *list.get_mut(c) = 3;
+            assert_eq!(list.peek_front(), Some(&3));
+            list.move_to_back(a);
+            *list.get_mut(a) = 1;
+            assert_eq!(list.peek_back(), Some(&1));
+            list.move_to_front(a);
+
+            assert_eq!(list.remove(a), 1);
+            assert_eq!(list.iter().collect::<Vec<_>>(), [&3, &12]);
+
+            assert_eq!(list.remove(c), 3);
+            assert_eq!(list.remove(b), 12);
+        }
+
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_stats() {
+        let mut list = DoublyList::new();
+
+        let a = list.push_back_ref(1);
+        list.push_back(2);
+        list.push_front(0);
+        list.push_back_with(|| 3);
+        unsafe { list.remove(a) };
+
+        assert_eq!(list.stats().linked(), 4);
+        assert_eq!(list.stats().unlinked(), 1);
+        assert_eq!(list.stats().live(), list.len());
+        assert_eq!(list.stats().peak_len(), 4);
+
+        list.clear();
+        assert_eq!(list.stats().live(), 0);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_stream() {
+        use core::{future::Future, pin::pin, task::Context};
+        use futures_core::Stream;
+
+        let mut cx = Context::from_waker(core::task::Waker::noop());
+
+        let list = (0..3).collect::<DoublyList<_>>();
+        let mut stream = list.into_stream();
+        assert_eq!(stream.size_hint(), (3, Some(3)));
+        assert!(pin!(&mut stream).poll_next(&mut cx).is_ready());
+
+        let collected = pin!(DoublyList::from_stream(stream)).poll(&mut cx);
+        match collected {
+            core::task::Poll::Ready(list) => {
+                assert_eq!(list.into_iter().collect::<Vec<_>>(), [1, 2])
+            }
+            core::task::Poll::Pending => panic!("collecting a ready stream is pending"),
+        }
+    }
+
+    #[test]
+    fn test_append_and_insert() {
+        let mut list = (0..3).collect::<DoublyList<_>>();
+        let mut other = (3..6).collect::<DoublyList<_>>();
+
+        list.append(&mut other);
+        list.append(&mut DoublyList::new());
+        other.append(&mut list);
+        assert!(list.is_empty());
+        list.assert_invariants();
+        other.assert_invariants();
+
+        unsafe {
+            let front = other.front_ref().unwrap();
+            let back = other.back_ref().unwrap();
+
+            other.insert_before(front, -1);
+            other.insert_after(back, 6);
+            let second = other.next_ref(front).unwrap();
+            other.insert_after(second, 10);
+            assert_eq!(other.prev_ref(front), other.front_ref());
+        }
+
+        other.assert_invariants();
+        assert_eq!(
+            other.into_iter().collect::<Vec<_>>(),
+            [-1, 0, 1, 10, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_invariants() {
+        let mut list = (0..5).collect::<DoublyList<_>>();
+        list.assert_invariants();
+
+        let node = list.pop_back_node().unwrap();
+        unsafe {
+            // Link a node behind the tail, without updating the list.
+            crate::raw::link_after(list.tail.unwrap(), Node::into_raw(node));
+        }
+
+        let result = std::panic::catch_unwind(|| list.assert_invariants());
+        assert!(result.is_err());
+
+        unsafe {
+            let tail = list.tail.unwrap().as_ref().next.unwrap();
+            crate::raw::unlink(tail);
+            drop(Node::from_raw(tail));
+        }
+        list.assert_invariants();
+    }
+
+    #[test]
+    fn test_push_with() {
+        let mut list = DoublyList::new();
+
+        list.push_back_with(|| [1u8; 4096]);
+        list.push_front_with(|| [2u8; 4096]);
+
+        assert_eq!(list.pop_front().map(|page| page[4095]), Some(2));
+        assert_eq!(list.pop_front().map(|page| page[0]), Some(1));
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_push_unsize() {
+        use core::fmt::Display;
+
+        let mut list: DoublyList<dyn Display> = DoublyList::new();
+        list.push_back_unsize(1);
+        list.push_back_unsize("two");
+        list.push_front_unsize('0');
+
+        let strings = list.iter().map(|item| item.to_string()).collect::<Vec<_>>();
+        assert_eq!(strings, ["0", "1", "two"]);
+
+        let mut bytes: DoublyList<[u8]> = DoublyList::new();
+        bytes.push_back_unsize([1, 2, 3]);
+        bytes.push_back_unsize([4; 16]);
+
+        assert_eq!(bytes.peek_back().map(<[u8]>::len), Some(16));
+
+        bytes.push_front_boxed(Box::new([0; 2]));
+        assert_eq!(bytes.peek_front(), Some(&[0, 0][..]));
+    }
+
+    #[test]
+    fn test_downcast() {
+        let mut list: DoublyList<dyn Any> = DoublyList::new();
+
+        list.push_any(1u32);
+        list.push_any("two");
+        list.push_any(3u32);
+        list.push_any(String::from("four"));
+
+        assert_eq!(list.iter_downcast::<u32>().collect::<Vec<_>>(), [&1, &3]);
+        assert_eq!(
+            list.iter_downcast::<u32>().rev().collect::<Vec<_>>(),
+            [&3, &1]
+        );
+
+        // A mismatch leaves the list untouched.
+        let version = list.version();
+        assert_eq!(list.pop_front_downcast::<&str>(), None);
+        assert_eq!(list.version(), version);
+        assert_eq!(list.pop_front_downcast::<u32>(), Some(1));
+        assert_eq!(list.pop_front_downcast::<&str>(), Some("two"));
+        assert_eq!(
+            list.pop_back_downcast::<String>(),
+            Some(String::from("four"))
+        );
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn test_transfer() {
+        let mut idle = (0..4).collect::<DoublyList<u32>>();
+        let mut active = DoublyList::new();
+        let handles = (0..4)
+            .map(|n| idle.find_node(|&m| m == n).unwrap())
+            .collect::<Vec<_>>();
+        let items = idle
+            .iter()
+            .map(|item| item as *const u32)
+            .collect::<Vec<_>>();
+
+        unsafe {
+            idle.transfer(handles[2], &mut active);
+            idle.transfer(handles[0], &mut active);
+            active.transfer(handles[2], &mut idle);
+
+            // The items stay in their nodes.
+            assert_eq!(active.get(handles[0]) as *const u32, items[0]);
+            assert_eq!(idle.get(handles[2]) as *const u32, items[2]);
+
+            // Writing through the handles leaves the links usable.
+            *active.get_mut(handles[0]) += 10;
+            *idle.get_mut(handles[2]) += 10;
+        }
+
+        idle.assert_invariants();
+        active.assert_invariants();
+        assert_eq!(idle, [1, 3, 12]);
+        assert_eq!(active, [10]);
+    }
+
+    #[test]
+    fn test_iter_range() {
+        let mut list = (0..8).collect::<DoublyList<u32>>();
+        let two = list.find_node(|&n| n == 2).unwrap();
+        let five = list.find_node(|&n| n == 5).unwrap();
+
+        unsafe {
+            assert!(list.iter_range(two, five).eq(&[2, 3, 4, 5]));
+            assert!(list.iter_range(five, five).eq(&[5]));
+            assert_eq!(list.iter_range(two, five).rev().len(), 4);
+
+            list.iter_range_mut(two, five).for_each(|n| *n *= 10);
+        }
+
+        assert_eq!(list, [0, 1, 20, 30, 40, 50, 6, 7]);
+    }
+
+    #[test]
+    fn test_drain() {
+        for start in 0..=5 {
+            for end in start..=5 {
+                let mut list = (0..5).collect::<DoublyList<u32>>();
+                let mut vec = (0..5).collect::<Vec<u32>>();
+
+                assert!(list.drain(start..end).eq(vec.drain(start..end)));
+                list.assert_invariants();
+                assert_eq!(list, vec);
+            }
+        }
+
+        let mut list = (0..5).collect::<DoublyList<u32>>();
+        let mut drain = list.drain(1..=3);
+        assert_eq!((drain.next(), drain.next_back()), (Some(1), Some(3)));
+        drop(drain);
+        assert_eq!(list, [0, 4]);
+        assert!(list.drain(..).eq([0, 4]));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_slice() {
+        let list = (0..6).collect::<DoublyList<u32>>();
+        let vec = (0..6).collect::<Vec<u32>>();
+
+        for start in 0..=6 {
+            for end in start..=6 {
+                let slice = list.slice(start..end);
+                assert!(slice.iter().eq(&vec[start..end]));
+                assert!(slice.iter().rev().eq(vec[start..end].iter().rev()));
+                assert_eq!(slice.first(), vec[start..end].first());
+                assert_eq!(slice.last(), vec[start..end].last());
+            }
+        }
+
+        let (from, to) = (
+            list.find_node(|&n| n == 1).unwrap(),
+            list.back_ref().unwrap(),
+        );
+        let slice = unsafe { list.slice_between(from, to) };
+        assert_eq!(slice, list.slice(1..));
+        assert_ne!(slice, list.slice(..5));
+        assert_eq!(format!("{:?}", list.slice(..=1)), "[0, 1]");
+        assert!(list.slice(2..2).is_empty());
+    }
+
+    #[test]
+    fn test_slice_mut() {
+        for start in 0..=4 {
+            for end in start..=4 {
+                let mut list = (0..4).collect::<DoublyList<u32>>();
+                let mut vec = (0..4).collect::<Vec<u32>>();
+
+                let mut slice = list.slice_mut(start..end);
+                slice.push_back(10);
+                slice.push_front(11);
+                slice.iter_mut().for_each(|n| *n += 100);
+                assert_eq!(slice.pop_back(), Some(110));
+                slice.retain(|n| n % 2 == 1);
+                assert_eq!(slice.len(), slice.iter().count());
+
+                let mut expected = vec![111];
+                expected.extend(
+                    vec[start..end]
+                        .iter()
+                        .map(|n| n + 100)
+                        .filter(|n| n % 2 == 1),
+                );
+                assert_eq!(
+                    slice.as_slice().iter().copied().collect::<Vec<_>>(),
+                    expected
+                );
+                vec.splice(start..end, expected);
+
+                drop(slice);
+                list.assert_invariants();
+                assert_eq!(list, vec);
+            }
+        }
+
+        let mut list = (0..5).collect::<DoublyList<u32>>();
+        let from = list.find_node(|&n| n == 1).unwrap();
+        let to = list.find_node(|&n| n == 3).unwrap();
+        let mut slice = unsafe { list.slice_between_mut(from, to) };
+        while slice.pop_front().is_some() {}
+        assert_eq!(slice.first_mut(), None);
+        drop(slice);
+        assert_eq!(list, [0, 4]);
+    }
+
+    #[test]
+    fn test_split_at_mut() {
+        for mid in 0..=4 {
+            let mut list = (0..6).collect::<DoublyList<u32>>();
+            let mut vec = (0..6).collect::<Vec<u32>>();
+
+            let mut slice = list.slice_mut(1..5);
+            let (mut left, mut right) = slice.split_at_mut(mid);
+            assert!(left.iter().eq(&vec[1..1 + mid]));
+            assert!(right.iter().eq(&vec[1 + mid..5]));
+
+            // Both halves are edited at once, at the seam between them too.
+            let (mut inner, mut outer) = right.split_at_mut(right.len() / 2);
+            left.push_back(10);
+            inner.push_front(11);
+            inner.retain(|n| n % 2 == 1);
+            outer.pop_back();
+            drop((inner, outer));
+            right.push_back(12);
+            drop(left);
+
+            let mut expected = vec[1..1 + mid].to_vec();
+            expected.push(10);
+            let back = &vec[1 + mid..5];
+            let (a, b) = back.split_at(back.len() / 2);
+            expected.extend([11].iter().chain(a).filter(|&n| n % 2 == 1));
+            expected.extend(b.iter().take(b.len().saturating_sub(1)));
+            expected.push(12);
+
+            drop(right);
+            assert!(slice.iter().eq(&expected));
+            slice.pop_front();
+            drop(slice);
+
+            vec.splice(1..5, expected.into_iter().skip(1));
+            list.assert_invariants();
+            assert_eq!(list, vec);
+        }
+
+        let mut list = (0..1000).collect::<DoublyList<u32>>();
+        let mut slice = list.slice_mut(..);
+        let (mut left, mut right) = slice.split_at_mut(500);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| left.iter_mut().for_each(|n| *n *= 2));
+            scope.spawn(|| right.retain(|n| n % 2 == 0));
+        });
+
+        drop((right, left));
+        drop(slice);
+        list.assert_invariants();
+        assert!(list.iter().take(500).copied().eq((0..500).map(|n| n * 2)));
+        assert!(list.iter().skip(500).copied().eq((500..1000).step_by(2)));
+    }
+
+    #[test]
+    fn test_find_node() {
+        let mut list = [1, 2, 3, 2, 1].iter().copied().collect::<DoublyList<u32>>();
+
+        let first = list.find_node(|&n| n == 2).unwrap();
+        let last = list.rfind_node(|&n| n == 2).unwrap();
+        assert_ne!(first, last);
+        assert_eq!(list.find_node(|&n| n > 3), None);
+        assert_eq!(list.rfind_node(|&n| n == 1), list.back_ref());
+
+        unsafe {
+            assert_eq!(list.next_ref(first), list.find_node(|&n| n == 3));
+            *list.get_mut(last) = 4;
+            assert_eq!(list.remove(first), 2);
+        }
+
+        assert_eq!(list, [1, 3, 4, 1]);
+        assert!(list.contains_ref(last));
+        assert!(!list.contains_ref(first));
+        assert!(!DoublyList::new().contains_ref(last));
+        assert_eq!(DoublyList::<u32>::new().rfind_node(|_| true), None);
+    }
+
+    #[test]
+    fn test_node_ref_unsized() {
+        let mut list: DoublyList<[u32]> = DoublyList::new();
+
+        let a = list.push_back_node_ref(Node::boxed([1, 2]));
+        let b = list.push_front_node_ref(Node::boxed([3]));
+
+        unsafe {
+            assert_eq!(list.get(a), &[1, 2][..]);
+            assert_eq!(list.remove_node(b).item(), &[3][..]);
+            assert_eq!(list.peek_front(), Some(&[1, 2][..]));
+        }
+    }
+
+    #[test]
+    fn test_into_nodes() {
+        let mut list: DoublyList<[u32]> = DoublyList::new();
+        list.push_back_node(Node::boxed([1, 2]));
+        list.push_back_node(Node::boxed([3]));
+        list.push_back_node(Node::boxed([4, 5, 6]));
+
+        let addrs = list.iter().map(|item| item.as_ptr()).collect::<Vec<_>>();
+
+        let mut moved = list.into_nodes().rev().collect::<DoublyList<[u32]>>();
+        moved.extend(DoublyList::new().into_nodes());
+        moved.assert_invariants();
+
+        assert!(moved.iter().map(<[u32]>::len).eq([3, 1, 2]));
+        assert!(moved
+            .iter()
+            .map(|item| item.as_ptr())
+            .eq(addrs.into_iter().rev()));
+    }
+
+    #[test]
+    fn test_last_and_count() {
+        let mut list = (0..5).collect::<DoublyList<u32>>();
+
+        let mut iter = list.iter();
+        iter.next_back();
+        assert_eq!(iter.last(), Some(&3));
+        assert_eq!(iter.count(), 4);
+
+        let mut iter = list.iter_mut();
+        iter.next();
+        assert_eq!(iter.last(), Some(&mut 4));
+        assert_eq!(list.iter_mut().count(), 5);
+        assert_eq!(DoublyList::<u32>::new().iter().last(), None);
+
+        let counter = std::rc::Rc::new(());
+        let clones = (0..3).map(|_| counter.clone()).collect::<DoublyList<_>>();
+        assert!(clones.into_iter().last().is_some());
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_nth() {
+        let mut list = (0..10).collect::<DoublyList<u32>>();
+        let mut model = (0..10).collect::<Vec<u32>>();
+
+        for n in 0..4 {
+            let mut iter = list.iter();
+            let mut expected = model.iter();
+
+            assert_eq!(iter.nth(n), expected.nth(n));
+            assert_eq!(iter.nth_back(n + 1), expected.nth_back(n + 1));
+            assert_eq!(iter.len(), expected.len());
+            assert!(iter.eq(expected));
+        }
+
+        assert!(list.iter().step_by(3).eq(model.iter().step_by(3)));
+        assert_eq!(list.iter().nth(10), None);
+
+        let mut iter = list.iter_mut();
+        *iter.nth(7).unwrap() = 0;
+        assert_eq!(iter.nth_back(0), Some(&mut 9));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.nth_back(1), None);
+        assert_eq!(iter.next(), None);
+
+        model[7] = 0;
+        assert!(list.iter().eq(&model));
+
+        #[cfg(feature = "unstable")]
+        {
+            let mut iter = list.iter();
+            assert_eq!(iter.advance_back_by(3), Ok(()));
+            assert_eq!(
+                iter.advance_by(9),
+                core::num::NonZero::new(2).map_or(Ok(()), Err)
+            );
+            assert_eq!(iter.len(), 0);
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_trusted_len() {
+        fn collect<I: core::iter::TrustedLen>(iter: I) -> Vec<I::Item> {
+            iter.collect()
+        }
+
+        let mut list = (0..100).collect::<DoublyList<u32>>();
+        assert_eq!(collect(list.iter().rev()).capacity(), 100);
+        assert_eq!(collect(list.iter_mut()).len(), 100);
+        assert_eq!(collect(list.into_nodes()).len(), 100);
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_may_dangle() {
+        let mut list = DoublyList::new();
+        let mut singly = crate::list::List::new();
+        let name = String::from("borrowed");
+
+        // `name` is dropped before the lists that borrow it.
+        list.push_back(&name);
+        singly.push(&name);
+        assert_eq!(list.peek_front(), singly.peek());
+    }
+
+    // Shortening the lifetime of the items must compile for every owning or shared type.
+    // `IterMut` is left out on purpose: like `&mut T`, it has to be invariant.
+    #[test]
+    fn test_covariance() {
+        fn list<'a>(list: DoublyList<&'static str>) -> DoublyList<&'a str> {
+            list
+        }
+
+        fn iter<'i, 'a>(iter: Iter<'i, &'static str>) -> Iter<'i, &'a str> {
+            iter
+        }
+
+        fn into_iter<'a>(iter: IntoIter<&'static str>) -> IntoIter<&'a str> {
+            iter
+        }
+
+        fn into_nodes<'a>(iter: IntoNodes<&'static str>) -> IntoNodes<&'a str> {
+            iter
+        }
+
+        fn node<'a>(node: Box<Node<&'static str>>) -> Box<Node<&'a str>> {
+            node
+        }
+
+        fn node_ref<'a>(node: NodeRef<&'static str>) -> NodeRef<&'a str> {
+            node
+        }
+
+        let statics = DoublyList::from_iter(["static"]);
+        let local = String::from("local");
+        let local = local.as_str();
+
+        let mut borrowed = list(statics.clone());
+        borrowed.push_back_node(node(Node::boxed("node")));
+        borrowed.extend(into_nodes(statics.clone().into_nodes()));
+        borrowed.push_back(local);
+        assert!(borrowed.iter().eq(&["static", "node", "static", "local"]));
+
+        assert!(iter(statics.iter())
+            .chain([&local])
+            .eq(&["static", "local"]));
+        assert!(into_iter(statics.clone().into_iter())
+            .chain([local])
+            .eq(["static", "local"]));
+
+        let front = node_ref(statics.front_ref().unwrap());
+        assert!(statics.front_ref() == Some(front));
+    }
 
     #[test]
-    fn test_iter() {
+    fn test_send_sync() {
+        use core::cell::Cell;
+        use std::{rc::Rc, sync::MutexGuard};
+
+        fn send<T: Send>() {}
+        fn sync<T: Sync>() {}
+
+        // Resolving `item` is ambiguous exactly when `T` is `Send`, or `Sync`,
+        // which fails to compile.
+        trait AmbiguousIfSend<A> {
+            fn item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfSend<()> for T {}
+        impl<T: ?Sized + Send> AmbiguousIfSend<u8> for T {}
+
+        trait AmbiguousIfSync<A> {
+            fn item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfSync<()> for T {}
+        impl<T: ?Sized + Sync> AmbiguousIfSync<u8> for T {}
+
+        send::<DoublyList<Cell<u8>>>();
+        send::<IterMut<'_, Cell<u8>>>();
+        send::<IntoIter<Cell<u8>>>();
+        send::<Iter<'_, MutexGuard<'_, u8>>>();
+        sync::<Iter<'_, MutexGuard<'_, u8>>>();
+        sync::<IterMut<'_, MutexGuard<'_, u8>>>();
+        send::<NodeRef<u8>>();
+
+        <Iter<'_, Cell<u8>> as AmbiguousIfSend<_>>::item();
+        <Iter<'_, Cell<u8>> as AmbiguousIfSync<_>>::item();
+        <IterMut<'_, Cell<u8>> as AmbiguousIfSync<_>>::item();
+        <IterMut<'_, MutexGuard<'_, u8>> as AmbiguousIfSend<_>>::item();
+        <DoublyList<Rc<u8>> as AmbiguousIfSend<_>>::item();
+        <DoublyList<Cell<u8>> as AmbiguousIfSync<_>>::item();
+    }
+
+    #[test]
+    fn test_eq_slices() {
+        let list = (1..=3).collect::<DoublyList<u32>>();
+
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_eq!(list, &[1, 2, 3][..]);
+        assert_eq!(list, list.clone());
+        assert_ne!(list, [1, 2]);
+        assert_ne!(list, [1, 2, 3, 4]);
+        assert_ne!(list, DoublyList::new());
+        assert_eq!(DoublyList::<u32>::new(), [0u32; 0]);
+
+        let strings = ["a", "b"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<DoublyList<_>>();
+        assert_eq!(strings, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
         let mut list = DoublyList::new();
 
-        list.push_front(0);
-        list.push_front(10);
-        list.push_back(20);
-        list.push_front(11);
-        list.push_back(21);
-        // list = 11 10 20 21
-        let mut iter = list.iter();
+        for n in [5, 3, 8, 3, 1, 9, 5] {
+            list.insert_sorted(n);
+        }
 
-        assert_eq!(iter.next(), Some(&11));
-        assert_eq!(iter.next_back(), Some(&21));
-        assert_eq!(iter.next(), Some(&10));
-        assert_eq!(iter.next_back(), Some(&20));
-        assert_eq!(iter.next_back(), Some(&0));
-        assert_eq!(iter.next_back(), None);
-        assert_eq!(iter.next(), None);
+        assert_eq!(list, [1, 3, 3, 5, 5, 8, 9]);
+
+        // Equal keys keep the order they were inserted in.
+        let mut pairs = DoublyList::new();
+
+        for pair in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')] {
+            pairs.insert_sorted_by_key(pair, |&(key, _)| key);
+        }
+
+        assert_eq!(pairs, [(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+
+        let node = pairs.insert_sorted_by((9, 'z'), |a, b| b.cmp(a));
+        assert_eq!(pairs.front_ref(), Some(node));
     }
 
     #[test]
-    fn test_unsized_list() {
-        use core::any::Any;
+    fn test_extend_from_slice_panic() {
+        struct Bomb(u32);
+
+        impl Clone for Bomb {
+            fn clone(&self) -> Self {
+                assert_ne!(self.0, 2, "boom");
+                Bomb(self.0)
+            }
+        }
 
         let mut list = DoublyList::new();
+        list.extend_from_slice(&[Bomb(0), Bomb(1)]);
 
-        let node: Box<Node<dyn Any>> = Node::boxed(10usize);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.extend_from_slice(&[Bomb(3), Bomb(2), Bomb(4)])
+        }));
 
-        list.push_front_node(node);
+        assert!(result.is_err());
+        assert!(list.iter().map(|bomb| bomb.0).eq([0, 1]));
+        list.assert_invariants();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_in_place() {
+        use serde::de::{value::Error, Deserialize, IntoDeserializer};
+
+        fn words(words: &[&'static str]) -> impl serde::Deserializer<'static, Error = Error> {
+            words.to_vec().into_deserializer()
+        }
+
+        let mut list = DoublyList::<String>::deserialize(words(&["a", "b"])).unwrap();
+        let front = list.front_ref();
+
+        DoublyList::deserialize_in_place(words(&["c", "d", "e"]), &mut list).unwrap();
+        assert_eq!(list, ["c", "d", "e"]);
+        assert_eq!(list.front_ref(), front);
+
+        DoublyList::deserialize_in_place(words(&["f"]), &mut list).unwrap();
+        assert_eq!(list, ["f"]);
+        assert_eq!(list.front_ref(), front);
+        list.assert_invariants();
+    }
+
+    #[test]
+    fn test_fold() {
+        let mut list = (0..10).collect::<DoublyList<u32>>();
+
+        let mut iter = list.iter();
+        iter.next();
+        iter.next_back();
+        assert!(iter.copied().eq(1..9));
+        assert_eq!(iter.rfold(0, |acc, &n| acc * 10 + n), 87654321);
+        assert_eq!(iter.find(|&&n| n == 4), Some(&4));
+        assert_eq!(iter.rposition(|&n| n == 6), Some(1));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.sum::<u32>(), 5);
+
+        let mut iter = list.iter_mut();
+        let stopped = iter.try_for_each(|n| match *n {
+            7 => Err(*n),
+            _ => {
+                *n *= 2;
+                Ok(())
+            }
+        });
+        assert_eq!(stopped, Err(7));
+        iter.rev().for_each(|n| *n += 1);
+        assert!(list.iter().copied().eq([0, 2, 4, 6, 8, 10, 12, 7, 9, 10]));
+
+        // The short-circuiting methods resume right after the item they stopped at.
+        let mut iter = list.iter();
+        assert_eq!(iter.position(|&n| n == 4), Some(2));
+        assert_eq!(iter.find_map(|&n| (n > 7).then_some(n * 2)), Some(16));
+        assert!(iter.any(|&n| n == 12));
+        assert_eq!(iter.rfind(|&&n| n < 10), Some(&9));
+        assert!(iter.all(|&n| n < 9));
+        assert_eq!(iter.rposition(|&n| n == 9), None);
+        assert_eq!(iter.len(), 0);
+
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.rposition(|n| *n == 10), Some(9));
+        *iter.rfind(|n| **n == 7).unwrap() = 14;
+        assert!(iter.all(|n| *n % 2 == 0));
+        assert_eq!(iter.position(|n| *n == 0), None);
+        assert_eq!(list.iter().position(|&n| n == 14), Some(7));
+
+        let mut empty = list.iter();
+        empty.by_ref().for_each(drop);
+        assert_eq!(empty.sum::<u32>(), 0);
+        assert_eq!(list.iter().rev().fold(0, |acc, &n| acc.max(n)), 14);
+    }
+
+    #[test]
+    fn test_split_into() {
+        for len in 0..12u32 {
+            for n in 1..6 {
+                let pieces = (0..len).collect::<DoublyList<u32>>().split_into(n);
+                let lens = pieces.iter().map(DoublyList::len).collect::<Vec<_>>();
+
+                assert_eq!(pieces.len(), n);
+                assert!(lens.windows(2).all(|w| w[0] == w[1] || w[0] == w[1] + 1));
+                pieces.iter().for_each(DoublyList::assert_invariants);
+                assert!(pieces.into_iter().flatten().eq(0..len));
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_by() {
+        let groups = (0..10).collect::<DoublyList<u32>>().split_by(|n| n % 3);
+        let keys = groups.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+
+        assert_eq!(keys, [0, 1, 2]);
+        groups
+            .iter()
+            .for_each(|(_, group)| group.assert_invariants());
+        assert_eq!(groups[0].1, [0, 3, 6, 9]);
+        assert_eq!(groups[1].1, [1, 4, 7]);
+        assert_eq!(groups[2].1, [2, 5, 8]);
+
+        assert!(DoublyList::<u32>::new().split_by(|n| *n).is_empty());
+    }
+
+    #[test]
+    fn test_collect_lists() {
+        let lists = (0..4).map(|n| (0..n).collect::<DoublyList<u32>>());
+        let mut list = lists.collect::<DoublyList<u32>>();
+
+        list.assert_invariants();
+        assert_eq!(list, [0, 0, 1, 0, 1, 2]);
+
+        list.extend(vec![DoublyList::new(), (7..9).collect()]);
+        list.assert_invariants();
+        assert_eq!(list, [0, 0, 1, 0, 1, 2, 7, 8]);
+    }
+
+    #[test]
+    fn test_extend_ref() {
+        let mut list = DoublyList::<u32>::new();
+        list.extend(&[1, 2]);
+        list.extend([3, 4].iter());
+
+        list.assert_invariants();
+        assert_eq!(list, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_is_sorted() {
+        let list = [1, 2, 2, 5].iter().copied().collect::<DoublyList<i32>>();
+
+        assert!(list.is_sorted());
+        assert!(!list.is_sorted_by(|a, b| a < b));
+        assert!(list.is_sorted_by_key(|n| n / 2));
+        assert!(!list.is_sorted_by_key(|n| -n));
+        assert!(DoublyList::<f32>::new().is_sorted());
+        assert!(![1.0, f32::NAN]
+            .iter()
+            .copied()
+            .collect::<DoublyList<_>>()
+            .is_sorted());
+    }
+
+    #[test]
+    fn test_starts_ends_with() {
+        let list = (0..5).collect::<DoublyList<u32>>();
+        let prefix = (0..2).collect::<DoublyList<u32>>();
+
+        assert!(list.starts_with(&prefix) && !prefix.starts_with(&list));
+        assert!(list.starts_with(0..3) && !list.starts_with(1..3));
+        let suffix = [3, 4];
+        assert!(list.ends_with(&suffix[..]) && !list.ends_with([2, 4]));
+        assert!(list.ends_with(&suffix[..0]) && list.starts_with(Vec::<u32>::new()));
+        assert!(!list.ends_with(0..6));
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let mut list = (0..5)
+            .map(|n| (n, Rc::clone(&tracker)))
+            .collect::<DoublyList<_>>();
+
+        let nodes = list.iter().map(|item| item as *const _).collect::<Vec<_>>();
+        list.map_in_place(|(n, rc)| (n * 10, rc));
+        assert!(list.iter().map(|item| item as *const _).eq(nodes));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.map_in_place(|(n, rc)| {
+                assert_ne!(n, 20);
+                (n + 1, rc)
+            })
+        }));
+
+        assert!(result.is_err());
+        list.assert_invariants();
+        assert!(list.iter().map(|(n, _)| *n).eq([1, 11, 30, 40]));
+        assert_eq!(Rc::strong_count(&tracker), 5);
+    }
+
+    #[test]
+    fn test_retain_into() {
+        let mut list = (0..10).collect::<DoublyList<u32>>();
+        let nodes = list.iter().map(|n| n as *const u32).collect::<Vec<_>>();
+
+        let odd = list.retain_into(|n| n % 2 == 0);
+        list.assert_invariants();
+        odd.assert_invariants();
+        assert_eq!(list, [0, 2, 4, 6, 8]);
+        assert_eq!(odd, [1, 3, 5, 7, 9]);
+        assert!(odd.iter().all(|n| core::ptr::eq(n, nodes[*n as usize])));
+
+        assert!(list.retain_into(|_| true).is_empty());
+        assert_eq!(list.retain_into(|_| false), [0, 2, 4, 6, 8]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_swap_nodes() {
+        for i in 0..5 {
+            for j in 0..5 {
+                let mut list = DoublyList::new();
+                let nodes = (0..5).map(|n| list.push_back_ref(n)).collect::<Vec<_>>();
+                let mut model = (0..5).collect::<Vec<u32>>();
+
+                // Every handle was just pushed.
+                unsafe { list.swap_nodes(nodes[i], nodes[j]) };
+                model.swap(i, j);
+
+                list.assert_invariants();
+                assert_eq!(list, model);
+                assert_eq!(unsafe { *list.get(nodes[i]) }, i as u32);
+
+                // Writing through the handles leaves the links usable.
+                for &node in &nodes {
+                    unsafe { *list.get_mut(node) += 10 };
+                }
+                list.assert_invariants();
+                assert!(list.iter().copied().eq(model.iter().map(|n| n + 10)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort() {
+        for len in 0..70u32 {
+            let pairs = (0..len).map(|n| ((n * 37) % 7, n));
+            let mut list = pairs.clone().collect::<DoublyList<_>>();
+            let mut model = pairs.collect::<Vec<_>>();
+
+            list.sort_by_key(|&(key, _)| key);
+            model.sort_by_key(|&(key, _)| key);
+            list.assert_invariants();
+            assert_eq!(list, model);
+
+            list.sort();
+            model.sort();
+            assert_eq!(list, model);
+        }
+
+        let mut words = DoublyList::<str>::new();
+        for word in ["pear", "fig", "apple", "kiwi"].iter() {
+            words.push_back_node(Node::boxed_str(word));
+        }
+        words.sort_by(|a, b| a.len().cmp(&b.len()));
+        assert!(words.iter().eq(["fig", "pear", "kiwi", "apple"]));
+        words.sort();
+        assert!(words.iter().eq(["apple", "fig", "kiwi", "pear"]));
+    }
+
+    #[test]
+    fn test_sort_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut list = (0..50u32).rev().collect::<DoublyList<_>>();
+        let mut calls = 0;
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                calls += 1;
+                match calls {
+                    100 => panic!("boom"),
+                    _ => a.cmp(b),
+                }
+            })
+        }));
+
+        assert!(result.is_err());
+        list.assert_invariants();
+        let mut items = list.iter().copied().collect::<Vec<_>>();
+        items.sort_unstable();
+        assert!(items.into_iter().eq(0..50));
+    }
+
+    #[test]
+    fn test_sort_unstable() {
+        let mut list = (0..100u32)
+            .map(|n| (n * 37) % 100)
+            .collect::<DoublyList<_>>();
+        list.sort_unstable();
+        list.assert_invariants();
+        assert!(list.iter().copied().eq(0..100));
+
+        list.sort_unstable_by_key(|n| core::cmp::Reverse(*n));
+        assert!(list.iter().copied().eq((0..100).rev()));
+
+        let calls = core::cell::Cell::new(0);
+        list.sort_by_cached_key(|n| {
+            calls.set(calls.get() + 1);
+            n % 10
+        });
+        list.assert_invariants();
+        assert_eq!(calls.get(), 100);
+        assert!(list.is_sorted_by_key(|n| n % 10));
+        // Equal keys keep their order.
+        assert!(list.iter().take(10).copied().eq((0..100).step_by(10).rev()));
+    }
+
+    #[test]
+    fn test_sort_unstable_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut list = (0..50u32).rev().collect::<DoublyList<_>>();
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.sort_unstable_by(|a, b| match *a == 25 || *b == 25 {
+                true => panic!("boom"),
+                false => a.cmp(b),
+            })
+        }));
+
+        assert!(result.is_err());
+        list.assert_invariants();
+        let mut items = list.iter().copied().collect::<Vec<_>>();
+        items.sort_unstable();
+        assert!(items.into_iter().eq(0..50));
+
+        let before = list.iter().copied().collect::<Vec<_>>();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by_cached_key(|&n| match n {
+                25 => panic!("boom"),
+                n => n,
+            })
+        }));
+
+        assert!(result.is_err());
+        list.assert_invariants();
+        assert_eq!(list, before);
+    }
+
+    #[test]
+    fn test_extract() {
+        let mut list = [1, 2, 1, 3, 1].iter().copied().collect::<DoublyList<u32>>();
+
+        assert_eq!(list.extract_first(|&n| n == 1), Some(1));
+        assert_eq!(list.extract_last(|&n| n == 1), Some(1));
+        list.assert_invariants();
+        assert_eq!(list, [2, 1, 3]);
+
+        assert_eq!(list.extract_last(|&n| n > 1), Some(3));
+        assert_eq!(list.extract_first(|&n| n > 2), None);
+        assert_eq!(list, [2, 1]);
+    }
+
+    #[test]
+    fn test_add() {
+        let mut list = (0..3).collect::<DoublyList<u32>>();
+        list += DoublyList::new();
+        list += (3..5).collect();
+        list = DoublyList::new() + list + (5..6).collect();
+
+        list.assert_invariants();
+        assert_eq!(list, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let list = (0..3).collect::<DoublyList<u32>>();
+        let mut cycle = list.cycle();
+
+        assert!(cycle.by_ref().take(7).eq(&[0, 1, 2, 0, 1, 2, 0]));
+        assert!(cycle.take(3).eq(&[1, 2, 0]));
+        assert_eq!(list.cycle().size_hint(), (usize::MAX, None));
+
+        let empty = DoublyList::<u32>::new();
+        assert_eq!(empty.cycle().next(), None);
+        assert_eq!(empty.cycle().size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_repeat() {
+        let elem = String::from("elem");
+        let ptr = elem.as_ptr();
+        let list = DoublyList::repeat(elem, 3);
+
+        assert_eq!(list, ["elem"; 3]);
+        assert_eq!(list.peek_back().map(|s| s.as_ptr()), Some(ptr));
+        assert!(DoublyList::repeat(String::new(), 0).is_empty());
+
+        let mut calls = 0;
+        let list = DoublyList::repeat_with(
+            || {
+                calls += 1;
+                calls
+            },
+            4,
+        );
+        list.assert_invariants();
+        assert_eq!(list, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut list = (0..6).collect::<DoublyList<u32>>();
+
+        let [a, b, c] = list.get_disjoint_mut([5, 0, 2]).unwrap();
+        assert_eq!((*a, *b, *c), (5, 0, 2));
+        *a += *b + *c;
+        assert_eq!(list, [0, 1, 2, 3, 4, 7]);
+
+        assert!(list.get_disjoint_mut([]).is_some());
+        assert!(list.get_disjoint_mut([1, 4, 1]).is_none());
+        assert!(list.get_disjoint_mut([6]).is_none());
+
+        let (front, back) = (list.front_ref().unwrap(), list.back_ref().unwrap());
+        unsafe {
+            let [x, y] = list.get_disjoint_nodes_mut([front, back]).unwrap();
+            mem::swap(x, y);
+            assert!(list.get_disjoint_nodes_mut([back, front, back]).is_none());
+        }
+        assert_eq!(list, [7, 1, 2, 3, 4, 0]);
+    }
+
+    #[test]
+    fn test_pop_if() {
+        let mut list = (0..5).collect::<DoublyList<u32>>();
+
+        assert_eq!(list.pop_front_if(|n| *n == 1), None);
+        assert_eq!(list.pop_front_if(|n| *n == 0), Some(0));
+        assert_eq!(list.pop_back_if(|n| *n % 2 == 1), None);
+        assert_eq!(
+            list.pop_back_if(|n| {
+                *n += 1;
+                true
+            }),
+            Some(5)
+        );
+        assert_eq!(list.pop_back_if(|n| mem::replace(n, 0) == 3), Some(0));
+        assert_eq!(list, [1, 2]);
+
+        let mut empty = DoublyList::<u32>::new();
+        assert_eq!(empty.pop_front_if(|_| true), None);
+    }
+
+    #[test]
+    fn test_interleave() {
+        for (mine, theirs) in [(0, 0), (0, 3), (3, 0), (2, 5), (5, 2), (4, 4)] {
+            let mut list = (0..mine).collect::<DoublyList<u32>>();
+            list.interleave((100..100 + theirs).collect());
+            list.assert_invariants();
+
+            let mut expected = Vec::new();
+            for i in 0..mine.max(theirs) {
+                expected.extend((i < mine).then_some(i));
+                expected.extend((i < theirs).then(|| 100 + i));
+            }
+
+            assert_eq!(list, expected);
+        }
+    }
+
+    #[test]
+    fn test_iter_pairs() {
+        for len in 0..5u32 {
+            let mut list = (0..len).collect::<DoublyList<u32>>();
+            let items = list.iter().copied().collect::<Vec<_>>();
+            let windows = items.windows(2).map(|w| (&w[0], &w[1]));
+
+            assert!(list.iter_pairs().eq(windows.clone()));
+            assert!(list.iter_pairs().rev().eq(windows.clone().rev()));
+            assert_eq!(list.iter_pairs().len(), windows.len());
+
+            let mut pairs = list.iter_pairs();
+            let (front, back) = (pairs.next(), pairs.next_back());
+            assert_eq!(front.is_some(), len >= 2);
+            assert_eq!(back.is_some(), len >= 3);
+            assert_eq!(pairs.count(), (len as usize).saturating_sub(3));
+
+            let mut pairs = list.iter_pairs_mut();
+            while let Some((a, b)) = pairs.next() {
+                mem::swap(a, b);
+            }
+            assert!(pairs.next().is_none());
+
+            // Swapping every pair in turn carries the front item to the back.
+            let mut rotated = items.clone();
+            rotated.rotate_left(items.len().min(1));
+            assert_eq!(list, rotated);
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        fn split(items: &[u32], inclusive: bool) -> Vec<Vec<u32>> {
+            let list = items.iter().copied().collect::<DoublyList<_>>();
+            let is_zero = |item: &u32| *item == 0;
+
+            let segments = match inclusive {
+                false => list.split(is_zero).collect::<Vec<_>>(),
+                true => list.split_inclusive(is_zero).collect::<Vec<_>>(),
+            };
+
+            segments
+                .into_iter()
+                .map(|segment| {
+                    segment.assert_invariants();
+                    segment.into_iter().collect()
+                })
+                .collect()
+        }
+
+        let cases: [&[u32]; 6] = [&[], &[0], &[1, 2], &[0, 1, 0], &[1, 0, 0, 2], &[3, 0, 4]];
+
+        for items in cases.iter() {
+            let expected = items.split(|item| *item == 0).map(<[u32]>::to_vec);
+            assert_eq!(split(items, false), expected.collect::<Vec<_>>());
+
+            let expected = items
+                .split_inclusive(|item| *item == 0)
+                .map(<[u32]>::to_vec);
+            assert_eq!(split(items, true), expected.collect::<Vec<_>>());
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_sort() {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::seed_from_u64(11);
+        let items = (0..50_000u32)
+            .map(|i| (rng.random_range(0..100u32), i))
+            .collect::<Vec<_>>();
+
+        let mut list = items.iter().copied().collect::<DoublyList<_>>();
+        list.par_sort_by(|a, b| a.0.cmp(&b.0));
+        list.assert_invariants();
+
+        // Equal keys keep their order, like with a stable slice sort.
+        let mut expected = items;
+        expected.sort_by_key(|&(key, _)| key);
+        assert_eq!(list, expected);
+
+        let mut list = DoublyList::<u32>::new();
+        list.par_sort();
+        assert!(list.is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_sort_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut list = (0..10_000u32).rev().collect::<DoublyList<_>>();
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.par_sort_by(|a, b| match *a == 2_500 || *b == 2_500 {
+                true => panic!("boom"),
+                false => a.cmp(b),
+            })
+        }));
+
+        assert!(result.is_err());
+        list.assert_invariants();
+        assert_eq!(list.len(), 10_000);
+        let mut items = list.iter().copied().collect::<Vec<_>>();
+        items.sort_unstable();
+        assert!(items.into_iter().eq(0..10_000));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_shuffle() {
+        use rand::{rngs::SmallRng, SeedableRng};
+
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut list = (0..64u32).collect::<DoublyList<_>>();
+        let nodes = list
+            .iter()
+            .map(|item| item as *const u32)
+            .collect::<Vec<_>>();
+
+        list.shuffle(&mut rng);
+        list.assert_invariants();
+        assert_ne!(list, (0..64).collect::<Vec<_>>());
+
+        // Every item is still in its own node.
+        for item in &list {
+            assert_eq!(nodes[*item as usize], item as *const u32);
+        }
+
+        let mut seen = [false; 64];
+        for _ in 0..1000 {
+            seen[*list.choose(&mut rng).unwrap() as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(DoublyList::<u32>::new().choose(&mut rng), None);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh() {
+        let list = (0..5u16).collect::<DoublyList<_>>();
+        let bytes = borsh::to_vec(&list).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&vec![0u16, 1, 2, 3, 4]).unwrap());
+
+        let copy = borsh::from_slice::<DoublyList<u16>>(&bytes).unwrap();
+        assert_eq!(copy, list);
+
+        let list = borsh::from_slice::<List<u16>>(&bytes).unwrap();
+        assert_eq!(list, [0, 1, 2, 3, 4]);
+        assert_eq!(borsh::to_vec(&list).unwrap(), bytes);
+
+        assert!(borsh::from_slice::<DoublyList<u16>>(&bytes[..bytes.len() - 1]).is_err());
+        assert!(borsh::from_slice::<List<u16>>(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv() {
+        use rkyv::{rancor::Error, vec::ArchivedVec, Archived};
+
+        let list = ["a", "bc", ""]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<DoublyList<_>>();
+        let bytes = rkyv::to_bytes::<Error>(&list).unwrap();
+
+        let archived = rkyv::access::<ArchivedVec<Archived<String>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[1], "bc");
+
+        let copy = rkyv::deserialize::<DoublyList<String>, Error>(archived).unwrap();
+        assert_eq!(copy, list);
+        copy.assert_invariants();
+
+        let copy = rkyv::deserialize::<List<String>, Error>(archived).unwrap();
+        assert_eq!(copy, ["a", "bc", ""]);
+    }
+
+    #[test]
+    fn test_leak() {
+        static NAMES: std::sync::OnceLock<Iter<'static, String>> = std::sync::OnceLock::new();
+
+        let names = ["a", "b", "c"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<DoublyList<_>>();
+        NAMES.get_or_init(|| names.leak());
+
+        let joined = std::thread::spawn(|| NAMES.get().unwrap().cloned().collect::<String>());
+        assert_eq!(joined.join().unwrap(), "abc");
+        assert_eq!(NAMES.get().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_raw_parts() {
+        let (head, tail, len) = DoublyList::<u64>::new().into_raw_parts();
+        assert_eq!((head, tail, len), (None, None, 0));
+
+        let list = (0..4u64).collect::<DoublyList<_>>();
+        let (head, tail, len) = list.into_raw_parts();
+
+        unsafe {
+            assert_eq!(head.unwrap().as_ref().next().unwrap().as_ref().item(), &1);
+            assert_eq!(tail.unwrap().as_ref().item(), &3);
+
+            let mut list = DoublyList::from_raw_parts(head, tail, len);
+            list.push_front(9);
+            list.assert_invariants();
+            assert_eq!(list, [9, 0, 1, 2, 3]);
+
+            #[cfg(feature = "stats")]
+            assert_eq!(list.stats().live(), 5);
+        }
+
+        #[cfg(feature = "ffi")]
+        {
+            let link = mem::size_of::<Link<u64>>();
+            assert_eq!(mem::offset_of!(Node<u64>, next), 0);
+            assert_eq!(mem::offset_of!(Node<u64>, prev), link);
+            assert_eq!(mem::offset_of!(Node<u64>, item), 2 * link);
+        }
     }
 }
@@ -0,0 +1,263 @@
+//! A list of items that expire at a deadline.
+
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+};
+use std::time::Instant;
+
+use crate::doublylist::{self, DoublyList};
+
+struct Entry<T, D> {
+    deadline: D,
+    item: T,
+}
+
+/// A list of items, each expiring at a deadline of type `D`.
+///
+/// Items are kept in the order of their deadlines,
+/// with items of equal deadlines in the order they were inserted.
+/// An item expires once `now` has reached its deadline,
+/// and draining the expired items takes O(expired).
+/// Inserting searches from the back of the list,
+/// so items inserted mostly in deadline order, like sessions
+/// that all live equally long, are inserted in O(1).
+///
+/// # Examples
+/// ```
+/// # use lists::expiring::ExpiringList;
+/// let mut sessions: ExpiringList<&str, u64> = ExpiringList::new();
+///
+/// sessions.insert(30, "bob");
+/// sessions.insert(10, "alice");
+/// sessions.insert(50, "carol");
+///
+/// let expired = sessions.expire(30).map(|(_, user)| user).collect::<Vec<_>>();
+/// assert_eq!(expired, ["alice", "bob"]);
+/// assert_eq!(sessions.next_deadline(), Some(&50));
+/// ```
+pub struct ExpiringList<T, D = Instant> {
+    entries: DoublyList<Entry<T, D>>,
+}
+
+impl<T, D> Default for ExpiringList<T, D> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug, D: Debug> Debug for ExpiringList<T, D> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T, D> ExpiringList<T, D> {
+    /// Returns a new empty list.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            entries: DoublyList::new(),
+        }
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.len() == 0
+    }
+
+    /// Returns the earliest deadline in the list.
+    #[inline]
+    pub fn next_deadline(&self) -> Option<&D> {
+        self.entries.peek_front().map(|entry| &entry.deadline)
+    }
+
+    /// Returns the item with the earliest deadline, and its deadline.
+    #[inline]
+    pub fn peek(&self) -> Option<(&D, &T)> {
+        self.entries
+            .peek_front()
+            .map(|entry| (&entry.deadline, &entry.item))
+    }
+
+    /// Removes all items from the list.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns an iterator over the items of the list and their deadlines,
+    /// in the order of their deadlines.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T, D> {
+        Iter {
+            entries: self.entries.iter(),
+        }
+    }
+}
+
+impl<T, D: Ord> ExpiringList<T, D> {
+    /// Inserts `item`, expiring at `deadline`.
+    #[inline]
+    pub fn insert(&mut self, deadline: D, item: T) {
+        self.entries
+            .insert_sorted_by(Entry { deadline, item }, |a, b| a.deadline.cmp(&b.deadline));
+    }
+
+    /// Removes the item with the earliest deadline, if it has expired at `now`,
+    /// and returns it with its deadline.
+    #[inline]
+    pub fn pop_expired(&mut self, now: &D) -> Option<(D, T)> {
+        if self.next_deadline()? > now {
+            return None;
+        }
+
+        self.entries
+            .pop_front()
+            .map(|entry| (entry.deadline, entry.item))
+    }
+
+    /// Returns an iterator removing the items that have expired at `now`,
+    /// in the order of their deadlines.
+    ///
+    /// Expired items that the iterator does not get to stay in the list.
+    #[inline(always)]
+    pub fn expire(&mut self, now: D) -> Expire<'_, T, D> {
+        Expire { list: self, now }
+    }
+}
+
+/// An iterator over the items of an [`ExpiringList`] and their deadlines.
+pub struct Iter<'a, T, D> {
+    entries: doublylist::Iter<'a, Entry<T, D>>,
+}
+
+impl<T, D> Copy for Iter<'_, T, D> {}
+
+impl<T, D> Clone for Iter<'_, T, D> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, D> Iterator for Iter<'a, T, D> {
+    type Item = (&'a D, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next()
+            .map(|entry| (&entry.deadline, &entry.item))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T, D> DoubleEndedIterator for Iter<'_, T, D> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|entry| (&entry.deadline, &entry.item))
+    }
+}
+
+impl<T, D> ExactSizeIterator for Iter<'_, T, D> {}
+
+impl<T, D> FusedIterator for Iter<'_, T, D> {}
+
+/// An iterator removing the expired items of an [`ExpiringList`],
+/// returned by [`ExpiringList::expire`].
+pub struct Expire<'a, T, D> {
+    list: &'a mut ExpiringList<T, D>,
+    now: D,
+}
+
+impl<T, D: Debug> Debug for Expire<'_, T, D> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Expire").field("now", &self.now).finish()
+    }
+}
+
+impl<T, D: Ord> Iterator for Expire<'_, T, D> {
+    type Item = (D, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_expired(&self.now)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.len()))
+    }
+}
+
+impl<T, D: Ord> FusedIterator for Expire<'_, T, D> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_expire_in_order() {
+        let mut list = ExpiringList::new();
+
+        for n in 0..100u64 {
+            list.insert((n * 37) % 50, n);
+        }
+
+        let mut expired = Vec::new();
+
+        for now in (0..60).step_by(7) {
+            for (deadline, n) in list.expire(now) {
+                assert!(deadline <= now);
+                expired.push((deadline, n));
+            }
+
+            assert!(list.next_deadline().is_none_or(|&deadline| deadline > now));
+        }
+
+        let mut expected = (0..100).map(|n| ((n * 37) % 50, n)).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(expired, expected);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_instants() {
+        let start = Instant::now();
+        let mut list = ExpiringList::new();
+
+        list.insert(start + Duration::from_secs(2), "late");
+        list.insert(start + Duration::from_secs(1), "early");
+        list.insert(start + Duration::from_secs(1), "early too");
+
+        assert_eq!(list.pop_expired(&start), None);
+        assert!(list
+            .iter()
+            .map(|(_, item)| *item)
+            .eq(["early", "early too", "late"]));
+
+        let mut expire = list.expire(start + Duration::from_secs(1));
+        assert_eq!(expire.next().map(|(_, item)| item), Some("early"));
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.peek().map(|(_, item)| *item), Some("early too"));
+    }
+}
@@ -0,0 +1,819 @@
+//! A persistent sequence, as a 2-3 finger tree annotated with sizes.
+
+use core::{
+    fmt::{self, Debug},
+    iter::{FromIterator, FusedIterator},
+    mem,
+};
+use std::rc::Rc;
+
+/// A node of the tree, holding either a single item,
+/// or two or three nodes of the level below.
+enum Node<T> {
+    Leaf(T),
+    Branch {
+        size: usize,
+        children: Vec<Rc<Node<T>>>,
+    },
+}
+
+impl<T> Node<T> {
+    #[inline(always)]
+    fn size(&self) -> usize {
+        match self {
+            Node::Leaf(_) => 1,
+            Node::Branch { size, .. } => *size,
+        }
+    }
+
+    /// Returns the `index`-th item below the node.
+    fn get(&self, mut index: usize) -> &T {
+        match self {
+            Node::Leaf(item) => item,
+            Node::Branch { children, .. } => {
+                for child in children {
+                    if index < child.size() {
+                        return child.get(index);
+                    }
+
+                    index -= child.size();
+                }
+
+                unreachable!("index out of bounds of the node")
+            }
+        }
+    }
+
+    /// Returns the first item below the node.
+    #[inline]
+    fn first(&self) -> &T {
+        match self {
+            Node::Leaf(item) => item,
+            Node::Branch { children, .. } => children[0].first(),
+        }
+    }
+
+    /// Returns the last item below the node.
+    #[inline]
+    fn last(&self) -> &T {
+        match self {
+            Node::Leaf(item) => item,
+            Node::Branch { children, .. } => children[children.len() - 1].last(),
+        }
+    }
+}
+
+type Digit<T> = Vec<Rc<Node<T>>>;
+
+#[inline(always)]
+fn branch<T>(children: Digit<T>) -> Rc<Node<T>> {
+    Rc::new(Node::Branch {
+        size: digit_size(&children),
+        children,
+    })
+}
+
+#[inline(always)]
+fn digit_size<T>(digit: &[Rc<Node<T>>]) -> usize {
+    digit.iter().map(|node| node.size()).sum()
+}
+
+/// Returns the children of a node above the leaves.
+#[inline]
+fn children<T>(node: &Node<T>) -> Digit<T> {
+    match node {
+        Node::Branch { children, .. } => children.clone(),
+        Node::Leaf(_) => unreachable!("leaves only live at the top level"),
+    }
+}
+
+/// Groups at least 2 nodes into nodes of 2 or 3.
+fn group<T>(mut nodes: Digit<T>) -> Digit<T> {
+    let mut groups = Vec::with_capacity(nodes.len() / 2);
+    let mut rest = nodes.len();
+    let mut drain = nodes.drain(..);
+
+    while rest > 0 {
+        let take = match rest {
+            2 | 4 => 2,
+            _ => 3,
+        };
+
+        groups.push(branch(drain.by_ref().take(take).collect()));
+        rest -= take;
+    }
+
+    groups
+}
+
+/// Splits `digit` around the node holding the `index`-th item below it.
+fn split_digit<T>(mut digit: Digit<T>, mut index: usize) -> (Digit<T>, Rc<Node<T>>, Digit<T>) {
+    let mut at = 0;
+
+    while index >= digit[at].size() {
+        index -= digit[at].size();
+        at += 1;
+    }
+
+    let right = digit.split_off(at + 1);
+    let node = digit.pop().unwrap();
+    (digit, node, right)
+}
+
+/*
+ * The levels of the tree all hold `Rc<Node<T>>`s:
+ * leaves at the top level, branches of leaves one level down, and so on.
+ * Every level keeps one to four nodes in its prefix and suffix,
+ * and pushes groups of three into the level below when a digit overflows,
+ * which keeps pushing and popping amortized O(1).
+ */
+enum Tree<T> {
+    Empty,
+    Single(Rc<Node<T>>),
+    Deep {
+        size: usize,
+        prefix: Digit<T>,
+        middle: Rc<Tree<T>>,
+        suffix: Digit<T>,
+    },
+}
+
+impl<T> Clone for Tree<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Tree::Empty => Tree::Empty,
+            Tree::Single(node) => Tree::Single(Rc::clone(node)),
+            Tree::Deep {
+                size,
+                prefix,
+                middle,
+                suffix,
+            } => Tree::Deep {
+                size: *size,
+                prefix: prefix.clone(),
+                middle: Rc::clone(middle),
+                suffix: suffix.clone(),
+            },
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    #[inline(always)]
+    fn size(&self) -> usize {
+        match self {
+            Tree::Empty => 0,
+            Tree::Single(node) => node.size(),
+            Tree::Deep { size, .. } => *size,
+        }
+    }
+
+    #[inline]
+    fn deep(prefix: Digit<T>, middle: Tree<T>, suffix: Digit<T>) -> Self {
+        Tree::Deep {
+            size: digit_size(&prefix) + middle.size() + digit_size(&suffix),
+            prefix,
+            middle: Rc::new(middle),
+            suffix,
+        }
+    }
+
+    /// Takes the tree out of `middle`, cloning its top level if it is shared.
+    #[inline]
+    fn unshare(middle: Rc<Tree<T>>) -> Self {
+        Rc::try_unwrap(middle).unwrap_or_else(|middle| (*middle).clone())
+    }
+
+    #[inline]
+    fn from_digit(digit: Digit<T>) -> Self {
+        digit.into_iter().fold(Tree::Empty, Tree::push_back)
+    }
+
+    /// Like [`Tree::deep`], but with a prefix that may be empty.
+    fn deep_front(prefix: Digit<T>, middle: Tree<T>, suffix: Digit<T>) -> Self {
+        if !prefix.is_empty() {
+            return Tree::deep(prefix, middle, suffix);
+        }
+
+        match middle.pop_front() {
+            None => Tree::from_digit(suffix),
+            Some((node, middle)) => Tree::deep(children(&node), middle, suffix),
+        }
+    }
+
+    /// Like [`Tree::deep`], but with a suffix that may be empty.
+    fn deep_back(prefix: Digit<T>, middle: Tree<T>, suffix: Digit<T>) -> Self {
+        if !suffix.is_empty() {
+            return Tree::deep(prefix, middle, suffix);
+        }
+
+        match middle.pop_back() {
+            None => Tree::from_digit(prefix),
+            Some((node, middle)) => Tree::deep(prefix, middle, children(&node)),
+        }
+    }
+
+    fn push_front(self, node: Rc<Node<T>>) -> Self {
+        match self {
+            Tree::Empty => Tree::Single(node),
+            Tree::Single(other) => Tree::deep(vec![node], Tree::Empty, vec![other]),
+            Tree::Deep {
+                size,
+                mut prefix,
+                middle,
+                suffix,
+            } => {
+                let size = size + node.size();
+                let middle = if prefix.len() == 4 {
+                    let overflow = prefix.split_off(1);
+                    Rc::new(Tree::unshare(middle).push_front(branch(overflow)))
+                } else {
+                    middle
+                };
+
+                prefix.insert(0, node);
+                Tree::Deep {
+                    size,
+                    prefix,
+                    middle,
+                    suffix,
+                }
+            }
+        }
+    }
+
+    fn push_back(self, node: Rc<Node<T>>) -> Self {
+        match self {
+            Tree::Empty => Tree::Single(node),
+            Tree::Single(other) => Tree::deep(vec![other], Tree::Empty, vec![node]),
+            Tree::Deep {
+                size,
+                prefix,
+                middle,
+                mut suffix,
+            } => {
+                let size = size + node.size();
+                let middle = if suffix.len() == 4 {
+                    let last = suffix.pop().unwrap();
+                    let overflow = mem::replace(&mut suffix, vec![last]);
+                    Rc::new(Tree::unshare(middle).push_back(branch(overflow)))
+                } else {
+                    middle
+                };
+
+                suffix.push(node);
+                Tree::Deep {
+                    size,
+                    prefix,
+                    middle,
+                    suffix,
+                }
+            }
+        }
+    }
+
+    fn pop_front(self) -> Option<(Rc<Node<T>>, Self)> {
+        match self {
+            Tree::Empty => None,
+            Tree::Single(node) => Some((node, Tree::Empty)),
+            Tree::Deep {
+                mut prefix,
+                middle,
+                suffix,
+                ..
+            } => {
+                let node = prefix.remove(0);
+                Some((
+                    node,
+                    Tree::deep_front(prefix, Tree::unshare(middle), suffix),
+                ))
+            }
+        }
+    }
+
+    fn pop_back(self) -> Option<(Rc<Node<T>>, Self)> {
+        match self {
+            Tree::Empty => None,
+            Tree::Single(node) => Some((node, Tree::Empty)),
+            Tree::Deep {
+                prefix,
+                middle,
+                mut suffix,
+                ..
+            } => {
+                let node = suffix.pop().unwrap();
+                Some((node, Tree::deep_back(prefix, Tree::unshare(middle), suffix)))
+            }
+        }
+    }
+
+    /// Joins `left`, the nodes of `between`, and `right` into one tree.
+    fn concat(left: Self, between: Digit<T>, right: Self) -> Self {
+        match (left, right) {
+            (Tree::Empty, right) => between.into_iter().rev().fold(right, Tree::push_front),
+            (left, Tree::Empty) => between.into_iter().fold(left, Tree::push_back),
+            (Tree::Single(node), right) => between
+                .into_iter()
+                .rev()
+                .fold(right, Tree::push_front)
+                .push_front(node),
+            (left, Tree::Single(node)) => between
+                .into_iter()
+                .fold(left, Tree::push_back)
+                .push_back(node),
+            (
+                Tree::Deep {
+                    prefix,
+                    middle: left_middle,
+                    suffix: mut nodes,
+                    ..
+                },
+                Tree::Deep {
+                    prefix: right_prefix,
+                    middle: right_middle,
+                    suffix,
+                    ..
+                },
+            ) => {
+                nodes.extend(between);
+                nodes.extend(right_prefix);
+
+                let middle = Tree::concat(
+                    Tree::unshare(left_middle),
+                    group(nodes),
+                    Tree::unshare(right_middle),
+                );
+                Tree::deep(prefix, middle, suffix)
+            }
+        }
+    }
+
+    /// Splits the tree around the node holding the `index`-th item below it.
+    /// The tree must hold more than `index` items.
+    fn split(self, index: usize) -> (Self, Rc<Node<T>>, Self) {
+        match self {
+            Tree::Empty => unreachable!("splitting an empty tree"),
+            Tree::Single(node) => (Tree::Empty, node, Tree::Empty),
+            Tree::Deep {
+                prefix,
+                middle,
+                suffix,
+                ..
+            } => {
+                let prefix_size = digit_size(&prefix);
+                let middle_size = middle.size();
+
+                if index < prefix_size {
+                    let (left, node, right) = split_digit(prefix, index);
+                    let right = Tree::deep_front(right, Tree::unshare(middle), suffix);
+                    (Tree::from_digit(left), node, right)
+                } else if index < prefix_size + middle_size {
+                    let index = index - prefix_size;
+                    let (left_middle, nodes, right_middle) = Tree::unshare(middle).split(index);
+
+                    let index = index - left_middle.size();
+                    let (left, node, right) = split_digit(children(&nodes), index);
+
+                    let left = Tree::deep_back(prefix, left_middle, left);
+                    let right = Tree::deep_front(right, right_middle, suffix);
+                    (left, node, right)
+                } else {
+                    let index = index - prefix_size - middle_size;
+                    let (left, node, right) = split_digit(suffix, index);
+                    let left = Tree::deep_back(prefix, Tree::unshare(middle), left);
+                    (left, node, Tree::from_digit(right))
+                }
+            }
+        }
+    }
+
+    /// Returns the `index`-th item below the tree.
+    /// The tree must hold more than `index` items.
+    fn get(&self, mut index: usize) -> &T {
+        match self {
+            Tree::Empty => unreachable!("indexing an empty tree"),
+            Tree::Single(node) => node.get(index),
+            Tree::Deep {
+                prefix,
+                middle,
+                suffix,
+                ..
+            } => {
+                for node in prefix {
+                    if index < node.size() {
+                        return node.get(index);
+                    }
+
+                    index -= node.size();
+                }
+
+                if index < middle.size() {
+                    return middle.get(index);
+                }
+
+                index -= middle.size();
+
+                for node in suffix {
+                    if index < node.size() {
+                        return node.get(index);
+                    }
+
+                    index -= node.size();
+                }
+
+                unreachable!("index out of bounds of the tree")
+            }
+        }
+    }
+}
+
+/// Returns the item of a leaf, cloning it if the leaf is shared.
+#[inline]
+fn into_item<T: Clone>(leaf: Rc<Node<T>>) -> T {
+    match Rc::try_unwrap(leaf) {
+        Ok(Node::Leaf(item)) => item,
+        Err(leaf) => leaf.first().clone(),
+        Ok(Node::Branch { .. }) => unreachable!("branches only live below the top level"),
+    }
+}
+
+/// A persistent sequence, supporting fast access at both ends, splitting and concatenating.
+///
+/// Pushing and popping at either end take amortized O(1),
+/// and indexing, [`split_off`](FingerTree::split_off) and [`append`](FingerTree::append)
+/// take O(log n).
+///
+/// The tree is persistent: cloning takes O(1), and a clone shares all of its nodes
+/// with the original, until either of them is changed.
+/// A change then only copies the O(log n) nodes along its path,
+/// and never affects the other tree.
+///
+/// # Examples
+/// ```
+/// # use lists::fingertree::FingerTree;
+/// let mut tree = (0..10).collect::<FingerTree<u32>>();
+/// let old = tree.clone();
+///
+/// let back = tree.split_off(5);
+/// tree.push_front(99);
+///
+/// assert_eq!(tree, [99, 0, 1, 2, 3, 4]);
+/// assert_eq!(back.get(0), Some(&5));
+/// assert_eq!(old.len(), 10);
+///
+/// tree.append(back);
+/// assert_eq!(tree.pop_back(), Some(9));
+/// assert_eq!(tree.len(), 10);
+/// ```
+pub struct FingerTree<T> {
+    tree: Tree<T>,
+}
+
+impl<T> Clone for FingerTree<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<T> Default for FingerTree<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for FingerTree<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for FingerTree<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for FingerTree<T> {}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for FingerTree<T> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for FingerTree<T> {
+    #[inline(always)]
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T> FingerTree<T> {
+    /// Returns a new empty tree.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { tree: Tree::Empty }
+    }
+
+    /// Returns the number of items in the tree.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Returns `true` if the tree is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        matches!(self.tree, Tree::Empty)
+    }
+
+    /// Applies `f` to the tree, which is left empty while `f` runs.
+    #[inline(always)]
+    fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(Tree<T>) -> Tree<T>,
+    {
+        self.tree = f(mem::replace(&mut self.tree, Tree::Empty));
+    }
+
+    /// Pushes `item` to the front of the tree.
+    #[inline]
+    pub fn push_front(&mut self, item: T) {
+        self.update(|tree| tree.push_front(Rc::new(Node::Leaf(item))));
+    }
+
+    /// Pushes `item` to the back of the tree.
+    #[inline]
+    pub fn push_back(&mut self, item: T) {
+        self.update(|tree| tree.push_back(Rc::new(Node::Leaf(item))));
+    }
+
+    /// Returns a reference to the front item.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        match &self.tree {
+            Tree::Empty => None,
+            Tree::Single(node) => Some(node.first()),
+            Tree::Deep { prefix, .. } => Some(prefix[0].first()),
+        }
+    }
+
+    /// Returns a reference to the back item.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        match &self.tree {
+            Tree::Empty => None,
+            Tree::Single(node) => Some(node.last()),
+            Tree::Deep { suffix, .. } => Some(suffix[suffix.len() - 1].last()),
+        }
+    }
+
+    /// Returns a reference to the `index`-th item, in O(log n).
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(self.tree.get(index))
+    }
+
+    /// Moves all items of `other` to the back of the tree, in O(log n).
+    #[inline]
+    pub fn append(&mut self, other: Self) {
+        self.update(|tree| Tree::concat(tree, Vec::new(), other.tree));
+    }
+
+    /// Splits the tree in two at `at`, in O(log n).
+    /// Returns the items from `at` on, and keeps the items before it.
+    ///
+    /// # Panics
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "split index {} out of bounds of {}", at, len);
+
+        if at == len {
+            return Self::new();
+        }
+
+        let (left, node, right) = mem::replace(&mut self.tree, Tree::Empty).split(at);
+        self.tree = left;
+
+        Self {
+            tree: right.push_front(node),
+        }
+    }
+
+    /// Returns an iterator over the items of the tree.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            stack: vec![Frame::Tree(&self.tree)],
+            len: self.len(),
+        }
+    }
+}
+
+impl<T: Clone> FingerTree<T> {
+    /// Removes the front item of the tree, and returns it.
+    /// The item is cloned if it is shared with another tree.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        let (leaf, tree) = mem::replace(&mut self.tree, Tree::Empty).pop_front()?;
+        self.tree = tree;
+        Some(into_item(leaf))
+    }
+
+    /// Removes the back item of the tree, and returns it.
+    /// The item is cloned if it is shared with another tree.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        let (leaf, tree) = mem::replace(&mut self.tree, Tree::Empty).pop_back()?;
+        self.tree = tree;
+        Some(into_item(leaf))
+    }
+}
+
+impl<T> Extend<T> for FingerTree<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for FingerTree<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<'a, T> IntoIterator for &'a FingerTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A part of the tree an [`Iter`] has yet to walk.
+enum Frame<'a, T> {
+    Tree(&'a Tree<T>),
+    Node(&'a Node<T>),
+}
+
+/// An iterator over the items of a [`FingerTree`].
+pub struct Iter<'a, T> {
+    stack: Vec<Frame<'a, T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.pop()? {
+                Frame::Node(Node::Leaf(item)) => {
+                    self.len -= 1;
+                    return Some(item);
+                }
+                Frame::Node(Node::Branch { children, .. }) => {
+                    self.stack
+                        .extend(children.iter().rev().map(|node| Frame::Node(node)));
+                }
+                Frame::Tree(Tree::Empty) => {}
+                Frame::Tree(Tree::Single(node)) => self.stack.push(Frame::Node(node)),
+                Frame::Tree(Tree::Deep {
+                    prefix,
+                    middle,
+                    suffix,
+                    ..
+                }) => {
+                    self.stack
+                        .extend(suffix.iter().rev().map(|node| Frame::Node(node)));
+                    self.stack.push(Frame::Tree(middle));
+                    self.stack
+                        .extend(prefix.iter().rev().map(|node| Frame::Node(node)));
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_against_vecdeque() {
+        let mut tree = FingerTree::new();
+        let mut model = VecDeque::new();
+
+        for n in 0..3000u32 {
+            match n % 7 {
+                0..=2 => {
+                    tree.push_back(n);
+                    model.push_back(n);
+                }
+                3 | 4 => {
+                    tree.push_front(n);
+                    model.push_front(n);
+                }
+                5 => assert_eq!(tree.pop_front(), model.pop_front()),
+                _ => assert_eq!(tree.pop_back(), model.pop_back()),
+            }
+
+            if n % 97 == 0 {
+                let at = n as usize % (model.len() + 1);
+                let back = tree.split_off(at);
+                let model_back = model.split_off(at);
+
+                assert!(tree.iter().eq(&model));
+                assert!(back.iter().eq(&model_back));
+
+                tree.append(back);
+                model.extend(model_back);
+            }
+
+            assert_eq!(tree.len(), model.len());
+            assert_eq!(tree.front(), model.front());
+            assert_eq!(tree.back(), model.back());
+        }
+
+        assert!(tree.iter().eq(&model));
+        assert!((0..model.len()).all(|i| tree.get(i) == model.get(i)));
+        assert_eq!(tree.get(model.len()), None);
+    }
+
+    #[test]
+    fn test_persistence() {
+        let tree = (0..1000).map(|n| n.to_string()).collect::<FingerTree<_>>();
+        let mut versions = vec![tree.clone()];
+
+        for round in 0..20 {
+            let mut next = versions[round].clone();
+            next.pop_front();
+            let back = next.split_off(round * 37 % next.len());
+            next.push_back(format!("round {}", round));
+            next.append(back);
+            versions.push(next);
+        }
+
+        // Every version popped one item and pushed one, without touching the others.
+        assert!(tree
+            .iter()
+            .zip(0..1000)
+            .all(|(item, n)| *item == n.to_string()));
+        assert!(versions.iter().all(|version| version.len() == 1000));
+        assert_eq!(versions[0], tree);
+
+        for (round, version) in versions.iter().enumerate().skip(1) {
+            let pushed = format!("round {}", round - 1);
+            assert_eq!(version.iter().filter(|item| **item == pushed).count(), 1);
+            assert!(versions[..round]
+                .iter()
+                .all(|older| older.iter().all(|item| *item != pushed)));
+        }
+
+        let mut concatenated = FingerTree::new();
+
+        for version in &versions {
+            concatenated.append(version.clone());
+        }
+
+        assert_eq!(concatenated.len(), 1000 * versions.len());
+        assert_eq!(concatenated.get(1000 * 20), versions[20].front());
+    }
+}
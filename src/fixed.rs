@@ -0,0 +1,532 @@
+//! A doubly linked list of fixed capacity, that never allocates.
+
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::MaybeUninit,
+};
+
+/// The index used as a link to no slot.
+const NIL: usize = usize::MAX;
+
+struct Slot<T> {
+    item: MaybeUninit<T>,
+    next: usize,
+    prev: usize,
+}
+
+impl<T> Slot<T> {
+    const VACANT: Self = Self {
+        item: MaybeUninit::uninit(),
+        next: NIL,
+        prev: NIL,
+    };
+}
+
+/// A doubly linked list holding at most `N` items,
+/// whose nodes live in an array inside the list itself, linked by index.
+///
+/// No operation allocates, so the list works without a heap,
+/// and fits in a `static` through its const [`new`](FixedList::new).
+/// Pushing into a full list panics,
+/// while the `try_` variants hand the item back instead.
+///
+/// # Examples
+/// ```
+/// # use lists::fixed::FixedList;
+/// let mut list = FixedList::<u8, 3>::new();
+///
+/// list.push_back(2);
+/// list.push_front(1);
+/// list.insert(2, 3);
+/// assert_eq!(list.try_push_back(4), Err(4));
+///
+/// assert_eq!(list.remove(1), Some(2));
+/// assert!(list.iter().eq(&[1, 3]));
+/// ```
+pub struct FixedList<T, const N: usize> {
+    slots: [Slot<T>; N],
+    head: usize,
+    tail: usize,
+    len: usize,
+
+    /// The first slot of the chain of released slots, linked by `next`.
+    free: usize,
+
+    /// The number of slots ever taken; the slots from here on were never used.
+    touched: usize,
+
+    /// The list drops the items in its slots.
+    marker: PhantomData<T>,
+}
+
+impl<T, const N: usize> Default for FixedList<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for FixedList<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T, const N: usize> FixedList<T, N> {
+    /// Returns a new empty list.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            slots: [Slot::VACANT; N],
+            head: NIL,
+            tail: NIL,
+            len: 0,
+            free: NIL,
+            touched: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if no more items fit in the list.
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the number of items the list can hold.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Takes a vacant slot and moves `item` into it, or returns `item` if the list is full.
+    fn take_slot(&mut self, item: T) -> Result<usize, T> {
+        let idx = if self.free != NIL {
+            let idx = self.free;
+            self.free = self.slots[idx].next;
+            idx
+        } else if self.touched < N {
+            self.touched += 1;
+            self.touched - 1
+        } else {
+            return Err(item);
+        };
+
+        self.slots[idx].item.write(item);
+        Ok(idx)
+    }
+
+    /// Links the occupied slot `idx` in before the slot `at`,
+    /// or at the back if `at` is `NIL`.
+    fn link_before(&mut self, idx: usize, at: usize) {
+        let prev = match at {
+            NIL => self.tail,
+            at => self.slots[at].prev,
+        };
+
+        self.slots[idx].prev = prev;
+        self.slots[idx].next = at;
+
+        match prev {
+            NIL => self.head = idx,
+            prev => self.slots[prev].next = idx,
+        }
+
+        match at {
+            NIL => self.tail = idx,
+            at => self.slots[at].prev = idx,
+        }
+
+        self.len += 1;
+    }
+
+    /// Unlinks the occupied slot `idx`, releases it, and returns its item.
+    fn release(&mut self, idx: usize) -> T {
+        let Slot { next, prev, .. } = self.slots[idx];
+
+        match prev {
+            NIL => self.head = next,
+            prev => self.slots[prev].next = next,
+        }
+
+        match next {
+            NIL => self.tail = prev,
+            next => self.slots[next].prev = prev,
+        }
+
+        self.len -= 1;
+        self.slots[idx].next = self.free;
+        self.free = idx;
+
+        // The slot was occupied, and is vacant from now on.
+        unsafe { self.slots[idx].item.assume_init_read() }
+    }
+
+    /// Returns the slot holding the item at `index`, walking from the nearest end.
+    ///
+    /// `index` must be less than `len()`.
+    fn slot_at(&self, index: usize) -> usize {
+        if index < self.len / 2 {
+            (0..index).fold(self.head, |idx, _| self.slots[idx].next)
+        } else {
+            (index + 1..self.len).fold(self.tail, |idx, _| self.slots[idx].prev)
+        }
+    }
+
+    /// Returns a reference to the item in the occupied slot `idx`.
+    #[inline(always)]
+    fn item(&self, idx: usize) -> &T {
+        // Only occupied slots are linked into the list.
+        unsafe { self.slots[idx].item.assume_init_ref() }
+    }
+
+    /// Returns a mutable reference to the item in the occupied slot `idx`.
+    #[inline(always)]
+    fn item_mut(&mut self, idx: usize) -> &mut T {
+        // Only occupied slots are linked into the list.
+        unsafe { self.slots[idx].item.assume_init_mut() }
+    }
+
+    /// Pushes `item` to the front of the list, or returns it if the list is full.
+    #[inline]
+    pub fn try_push_front(&mut self, item: T) -> Result<(), T> {
+        let idx = self.take_slot(item)?;
+        self.link_before(idx, self.head);
+        Ok(())
+    }
+
+    /// Pushes `item` to the back of the list, or returns it if the list is full.
+    #[inline]
+    pub fn try_push_back(&mut self, item: T) -> Result<(), T> {
+        let idx = self.take_slot(item)?;
+        self.link_before(idx, NIL);
+        Ok(())
+    }
+
+    /// Inserts `item` at `index`, or returns it if the list is full.
+    ///
+    /// # Panics
+    /// Panics if `index` is larger than `len()`.
+    pub fn try_insert(&mut self, index: usize, item: T) -> Result<(), T> {
+        assert!(
+            index <= self.len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len
+        );
+
+        let at = match index == self.len {
+            true => NIL,
+            false => self.slot_at(index),
+        };
+
+        let idx = self.take_slot(item)?;
+        self.link_before(idx, at);
+        Ok(())
+    }
+
+    /// Pushes `item` to the front of the list.
+    ///
+    /// # Panics
+    /// Panics if the list is full.
+    #[inline]
+    pub fn push_front(&mut self, item: T) {
+        if self.try_push_front(item).is_err() {
+            panic!("list is full (capacity is {})", N)
+        }
+    }
+
+    /// Pushes `item` to the back of the list.
+    ///
+    /// # Panics
+    /// Panics if the list is full.
+    #[inline]
+    pub fn push_back(&mut self, item: T) {
+        if self.try_push_back(item).is_err() {
+            panic!("list is full (capacity is {})", N)
+        }
+    }
+
+    /// Inserts `item` at `index`.
+    ///
+    /// # Panics
+    /// Panics if the list is full, or if `index` is larger than `len()`.
+    #[inline]
+    pub fn insert(&mut self, index: usize, item: T) {
+        if self.try_insert(index, item).is_err() {
+            panic!("list is full (capacity is {})", N)
+        }
+    }
+
+    /// Pops the front item of the list.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        match self.head {
+            NIL => None,
+            head => Some(self.release(head)),
+        }
+    }
+
+    /// Pops the back item of the list.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        match self.tail {
+            NIL => None,
+            tail => Some(self.release(tail)),
+        }
+    }
+
+    /// Removes and returns the item at `index`.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let idx = self.slot_at(index);
+        Some(self.release(idx))
+    }
+
+    /// Returns a reference to the front item of the list.
+    #[inline]
+    pub fn peek_front(&self) -> Option<&T> {
+        match self.head {
+            NIL => None,
+            head => Some(self.item(head)),
+        }
+    }
+
+    /// Returns a mutable reference to the front item of the list.
+    #[inline]
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        match self.head {
+            NIL => None,
+            head => Some(self.item_mut(head)),
+        }
+    }
+
+    /// Returns a reference to the back item of the list.
+    #[inline]
+    pub fn peek_back(&self) -> Option<&T> {
+        match self.tail {
+            NIL => None,
+            tail => Some(self.item(tail)),
+        }
+    }
+
+    /// Returns a mutable reference to the back item of the list.
+    #[inline]
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        match self.tail {
+            NIL => None,
+            tail => Some(self.item_mut(tail)),
+        }
+    }
+
+    /// Returns a reference to the item at `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+
+        Some(self.item(self.slot_at(index)))
+    }
+
+    /// Returns a mutable reference to the item at `index`.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let idx = self.slot_at(index);
+        Some(self.item_mut(idx))
+    }
+
+    /// Removes all items from the list.
+    #[inline]
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Drops the items in the occupied slots where they are, without moving them out,
+    /// as they may hold references that dangle while the list is dropped.
+    fn drop_items(&mut self) {
+        let mut idx = self.head;
+
+        while idx != NIL {
+            let slot = &mut self.slots[idx];
+            idx = slot.next;
+
+            // Only occupied slots are linked into the list, and the list is never used again.
+            unsafe { slot.item.assume_init_drop() };
+        }
+    }
+
+    /// Returns an iterator over the items of the list.
+    #[inline(always)]
+    pub const fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(not(feature = "unstable"))]
+impl<T, const N: usize> Drop for FixedList<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.drop_items();
+    }
+}
+
+// Dropping the list only drops the items in its slots, which `marker` tells the compiler about,
+// so the items may hold references that already dangle.
+#[cfg(feature = "unstable")]
+unsafe impl<#[may_dangle] T, const N: usize> Drop for FixedList<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.drop_items();
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a FixedList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the items of a [`FixedList`].
+pub struct Iter<'a, T, const N: usize> {
+    list: &'a FixedList<T, N>,
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Clone for Iter<'_, T, N> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let idx = self.front;
+        self.front = self.list.slots[idx].next;
+        self.len -= 1;
+        Some(self.list.item(idx))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for Iter<'_, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let idx = self.back;
+        self.back = self.list.slots[idx].prev;
+        self.len -= 1;
+        Some(self.list.item(idx))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {}
+
+impl<T, const N: usize> FusedIterator for Iter<'_, T, N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against_vec() {
+        let mut list = FixedList::<String, 16>::new();
+        let mut model = Vec::new();
+
+        for step in 0..200usize {
+            let item = step.to_string();
+            let at = (step * 7919) % (model.len() + 1);
+
+            match list.try_insert(at, item.clone()) {
+                Ok(()) => model.insert(at, item),
+                Err(back) => {
+                    assert_eq!(back, item);
+                    assert!(list.is_full());
+
+                    let at = (step * 104_729) % model.len();
+                    assert_eq!(list.remove(at), Some(model.remove(at)));
+                }
+            }
+
+            assert!(list.iter().eq(&model));
+            assert!(list.iter().rev().eq(model.iter().rev()));
+        }
+
+        assert_eq!(list.pop_back(), model.pop());
+        assert_eq!(list.pop_front(), Some(model.remove(0)));
+        assert_eq!(list.get(3), model.get(3));
+    }
+
+    #[test]
+    fn test_static() {
+        use std::sync::Mutex;
+
+        static LIST: Mutex<FixedList<u32, 4>> = Mutex::new(FixedList::new());
+
+        let mut list = LIST.lock().unwrap();
+        list.push_back(1);
+        list.push_front(0);
+        assert_eq!(list.peek_back(), Some(&1));
+        assert_eq!(list.capacity(), 4);
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_may_dangle() {
+        let mut list = FixedList::<_, 2>::new();
+        let name = String::from("borrowed");
+
+        // `name` is dropped before the list that borrows it.
+        list.push_back(&name);
+        assert_eq!(list.peek_back(), Some(&&name));
+    }
+}
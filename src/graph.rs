@@ -0,0 +1,395 @@
+//! A directed graph, where every vertex keeps its outgoing edges in a [`DoublyList`].
+
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::doublylist::{self, DoublyList, NodeRef};
+
+/// The index of a vertex in a [`Graph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VertexId(usize);
+
+impl VertexId {
+    /// Returns the index of the vertex, counting from 0 in the order vertices were added.
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+struct Edge<E> {
+    to: VertexId,
+    weight: E,
+}
+
+struct Vertex<V, E> {
+    value: V,
+    edges: DoublyList<Edge<E>>,
+}
+
+/// The edge an [`EdgeId`] refers to, while the edge is in the graph.
+struct EdgeSlot<E> {
+    generation: usize,
+    node: Option<NodeRef<Edge<E>>>,
+}
+
+/// Hands out the ids of graphs, so an [`EdgeId`] is never taken for an edge of another graph.
+/// Id 0 is left for graphs without edges.
+static NEXT_GRAPH_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// A handle to an edge of a [`Graph`], to remove it in O(1).
+///
+/// The handle knows the graph it came from, and the generation of the edge,
+/// so using it with another graph, or after the edge was removed, is caught.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EdgeId {
+    graph: usize,
+    from: VertexId,
+    slot: usize,
+    generation: usize,
+}
+
+impl EdgeId {
+    /// Returns the vertex the edge starts at.
+    #[inline(always)]
+    pub const fn from(self) -> VertexId {
+        self.from
+    }
+}
+
+/// A directed graph, with values of type `V` on its vertices and weights of type `E` on its edges.
+///
+/// Every vertex owns a [`DoublyList`] of its outgoing edges,
+/// so adding an edge takes O(1), and so does removing one through its [`EdgeId`].
+/// Next to the lists, the graph keeps a slot for every edge,
+/// which checks that an [`EdgeId`] still refers to an edge of this graph.
+///
+/// # Examples
+/// ```
+/// # use lists::graph::Graph;
+/// let mut graph = Graph::new();
+/// let a = graph.add_vertex("a");
+/// let b = graph.add_vertex("b");
+/// let c = graph.add_vertex("c");
+///
+/// graph.add_edge(a, b, 1);
+/// let edge = graph.add_edge(a, c, 2);
+///
+/// assert_eq!(graph.remove_edge(edge), Some(2));
+/// assert_eq!(graph.remove_edge(edge), None);
+///
+/// let neighbors = graph.neighbors(a).collect::<Vec<_>>();
+/// assert_eq!(neighbors, [(b, &1)]);
+/// assert_eq!(graph[neighbors[0].0], "b");
+/// ```
+pub struct Graph<V, E> {
+    vertices: Vec<Vertex<V, E>>,
+    slots: Vec<EdgeSlot<E>>,
+    free: Vec<usize>,
+    edges: usize,
+    id: usize,
+}
+
+impl<V, E> Default for Graph<V, E> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Debug, E: Debug> Debug for Graph<V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.vertices.iter().map(|vertex| {
+                let edges = vertex.edges.iter().map(|edge| (edge.to.0, &edge.weight));
+                (&vertex.value, edges.collect::<Vec<_>>())
+            }))
+            .finish()
+    }
+}
+
+impl<V, E> core::ops::Index<VertexId> for Graph<V, E> {
+    type Output = V;
+
+    #[inline(always)]
+    fn index(&self, vertex: VertexId) -> &Self::Output {
+        &self.vertices[vertex.0].value
+    }
+}
+
+impl<V, E> core::ops::IndexMut<VertexId> for Graph<V, E> {
+    #[inline(always)]
+    fn index_mut(&mut self, vertex: VertexId) -> &mut Self::Output {
+        &mut self.vertices[vertex.0].value
+    }
+}
+
+impl<V, E> Graph<V, E> {
+    /// Returns a new graph without vertices.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            edges: 0,
+            id: 0,
+        }
+    }
+
+    /// Returns the number of vertices.
+    #[inline(always)]
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Returns the number of edges.
+    #[inline(always)]
+    pub const fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    /// Adds a vertex holding `value`, without edges.
+    #[inline]
+    pub fn add_vertex(&mut self, value: V) -> VertexId {
+        self.vertices.push(Vertex {
+            value,
+            edges: DoublyList::new(),
+        });
+
+        VertexId(self.vertices.len() - 1)
+    }
+
+    /// Returns an iterator over the ids of all vertices, in the order they were added.
+    #[inline]
+    pub fn vertices(&self) -> impl Iterator<Item = VertexId> + '_ {
+        (0..self.vertices.len()).map(VertexId)
+    }
+
+    /// Adds an edge from `from` to `to`, with weight `weight`, and returns a handle to it.
+    ///
+    /// # Panics
+    /// Panics if either vertex is not in the graph.
+    #[inline]
+    pub fn add_edge(&mut self, from: VertexId, to: VertexId, weight: E) -> EdgeId {
+        assert!(to.0 < self.vertices.len(), "the vertex is not in the graph");
+
+        let node = self.vertices[from.0]
+            .edges
+            .push_back_ref(Edge { to, weight });
+
+        if self.id == 0 {
+            self.id = NEXT_GRAPH_ID.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].node = Some(node);
+                slot
+            }
+            None => {
+                self.slots.push(EdgeSlot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                self.slots.len() - 1
+            }
+        };
+
+        self.edges += 1;
+
+        EdgeId {
+            graph: self.id,
+            from,
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Returns the node of the edge `edge` refers to,
+    /// or `None` if it is not an edge of this graph, or was removed.
+    #[inline]
+    fn node(&self, edge: EdgeId) -> Option<NodeRef<Edge<E>>> {
+        if edge.graph != self.id {
+            return None;
+        }
+
+        let slot = self.slots.get(edge.slot)?;
+
+        match slot.generation == edge.generation {
+            true => slot.node,
+            false => None,
+        }
+    }
+
+    /// Removes the edge `edge` refers to, and returns its weight.
+    ///
+    /// Returns `None` if `edge` refers to an edge of another graph,
+    /// or to an edge that was removed already.
+    #[inline]
+    pub fn remove_edge(&mut self, edge: EdgeId) -> Option<E> {
+        let node = self.node(edge)?;
+
+        let slot = &mut self.slots[edge.slot];
+        slot.node = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(edge.slot);
+        self.edges -= 1;
+
+        // The slot held the node, so it is still linked into the edges of `from`.
+        Some(unsafe { self.vertices[edge.from.0].edges.remove(node) }.weight)
+    }
+
+    /// Returns the vertex the edge `edge` leads to, and its weight.
+    ///
+    /// Like [`remove_edge`](Graph::remove_edge),
+    /// returns `None` if `edge` does not refer to an edge of this graph.
+    #[inline]
+    pub fn edge(&self, edge: EdgeId) -> Option<(VertexId, &E)> {
+        let node = self.node(edge)?;
+
+        // The slot holds the node, so it is linked into the edges of `from`.
+        let edge = unsafe { self.vertices[edge.from.0].edges.get(node) };
+        Some((edge.to, &edge.weight))
+    }
+
+    /// Returns an iterator over the outgoing edges of `vertex`,
+    /// yielding the vertex every edge leads to, and its weight,
+    /// in the order the edges were added.
+    ///
+    /// # Panics
+    /// Panics if `vertex` is not in the graph.
+    #[inline]
+    pub fn neighbors(&self, vertex: VertexId) -> Neighbors<'_, E> {
+        Neighbors {
+            iter: self.vertices[vertex.0].edges.iter(),
+        }
+    }
+
+    /// Returns the number of outgoing edges of `vertex`.
+    ///
+    /// # Panics
+    /// Panics if `vertex` is not in the graph.
+    #[inline(always)]
+    pub fn out_degree(&self, vertex: VertexId) -> usize {
+        self.vertices[vertex.0].edges.len()
+    }
+}
+
+/// An iterator over the outgoing edges of a vertex, returned by [`Graph::neighbors`].
+pub struct Neighbors<'a, E> {
+    iter: doublylist::Iter<'a, Edge<E>>,
+}
+
+impl<E> Copy for Neighbors<'_, E> {}
+
+impl<E> Clone for Neighbors<'_, E> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Debug> Debug for Neighbors<'_, E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (VertexId, &'a E);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|edge| (edge.to, &edge.weight))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<E> DoubleEndedIterator for Neighbors<'_, E> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|edge| (edge.to, &edge.weight))
+    }
+}
+
+impl<E> ExactSizeIterator for Neighbors<'_, E> {}
+
+impl<E> FusedIterator for Neighbors<'_, E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges() {
+        let mut graph = Graph::new();
+        let vertices = (0..4).map(|n| graph.add_vertex(n)).collect::<Vec<_>>();
+
+        let mut edges = Vec::new();
+        for &from in &vertices {
+            for &to in &vertices {
+                if from != to {
+                    edges.push(graph.add_edge(from, to, graph[from] * 10 + graph[to]));
+                }
+            }
+        }
+
+        assert_eq!(graph.edge_count(), 12);
+
+        for edge in edges.iter().filter(|edge| edge.from().index() != 1) {
+            let (to, _) = graph.edge(*edge).unwrap();
+            if to.index() % 2 == 0 {
+                assert!(graph.remove_edge(*edge).is_some());
+            }
+        }
+
+        assert_eq!(graph.edge_count(), 8);
+        assert!(graph
+            .neighbors(vertices[0])
+            .eq([(vertices[1], &1), (vertices[3], &3)]));
+        assert!(graph
+            .neighbors(vertices[1])
+            .map(|(_, weight)| *weight)
+            .eq([10, 12, 13]));
+        assert!(graph
+            .neighbors(vertices[2])
+            .rev()
+            .eq([(vertices[3], &23), (vertices[1], &21)]));
+        assert_eq!(graph.out_degree(vertices[3]), 1);
+    }
+
+    #[test]
+    fn test_stale_edge() {
+        let mut graph = Graph::new();
+        let a = graph.add_vertex('a');
+        let b = graph.add_vertex('b');
+
+        let edge = graph.add_edge(a, b, 1);
+        assert_eq!(graph.remove_edge(edge), Some(1));
+        assert_eq!(graph.remove_edge(edge), None);
+
+        // The new edge reuses the slot of the removed one.
+        let reused = graph.add_edge(b, a, 2);
+        assert_eq!(graph.edge(edge), None);
+        assert_eq!(graph.edge(reused), Some((a, &2)));
+
+        let mut other = Graph::new();
+        let c = other.add_vertex('c');
+        other.add_edge(c, c, 3);
+        assert_eq!(other.edge(reused), None);
+        assert_eq!(other.remove_edge(reused), None);
+        assert_eq!(Graph::<char, u32>::new().edge(reused), None);
+
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(other.edge_count(), 1);
+    }
+}
@@ -0,0 +1,684 @@
+//! A lock-free stack and queue, that reclaim popped nodes with hazard pointers.
+
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+/*
+ * A thread reading a node first publishes it in a hazard of its slot,
+ * and then checks that the node is still where it was loaded from.
+ * If it is, the node cannot have been unlinked before the hazard was published,
+ * so any thread that scans the slots after unlinking it sees the hazard.
+ *
+ * A thread pushing a node protects the node it links the new one to in the same way.
+ * Otherwise that node could be popped and freed, and a new node allocated at its address
+ * could take its place again, so the exchange would succeed
+ * and link the pushed node to the freed one (ABA).
+ *
+ * Unlinked nodes are retired onto the slot of the thread that unlinked them,
+ * instead of being freed. Once that list outgrows twice the number of hazards,
+ * the thread frees the nodes no hazard holds, and keeps the others.
+ * A scan walks every slot once and every node retired onto its own slot once,
+ * which bounds the pause it causes, and no thread ever waits on another.
+ *
+ * Publishing a hazard, checking the node, unlinking it and reading the hazards are `SeqCst`,
+ * so these operations are seen in one order by all threads.
+ */
+
+/// The number of hazards in a slot. Popping from a queue protects two nodes.
+const HAZARDS: usize = 2;
+
+/// A set of hazards, owned by one thread at a time.
+pub(crate) struct Slot<N> {
+    hazards: [AtomicPtr<N>; HAZARDS],
+    active: AtomicBool,
+    /// The nodes retired by the owners of the slot, which are not freed yet.
+    retired: UnsafeCell<Vec<*mut N>>,
+    /// The slot added before this one. Slots are never removed until the domain is dropped.
+    next: *mut Slot<N>,
+}
+
+impl<N> Slot<N> {
+    /// Publishes the node `src` points to in hazard `index`, and returns it
+    /// once `src` still points to it after it was published.
+    pub(crate) fn protect(&self, index: usize, src: &AtomicPtr<N>) -> *mut N {
+        let mut node = src.load(Ordering::SeqCst);
+
+        loop {
+            self.hazards[index].store(node, Ordering::SeqCst);
+
+            match src.load(Ordering::SeqCst) {
+                current if current == node => return node,
+                current => node = current,
+            }
+        }
+    }
+
+    /// Clears the hazards, and hands the slot back.
+    #[inline]
+    pub(crate) fn release(&self) {
+        self.clear();
+        self.active.store(false, Ordering::Release);
+    }
+
+    #[inline]
+    fn clear(&self) {
+        self.hazards
+            .iter()
+            .for_each(|hazard| hazard.store(ptr::null_mut(), Ordering::SeqCst));
+    }
+}
+
+/// The slots of a lock-free structure, through which nodes of type `N` are reclaimed.
+///
+/// Retired nodes are freed as a `Box<N>`.
+pub(crate) struct Domain<N> {
+    slots: AtomicPtr<Slot<N>>,
+    slot_count: AtomicUsize,
+    retired_count: AtomicUsize,
+    marker: PhantomData<Box<N>>,
+}
+
+impl<N> Domain<N> {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Self {
+            slots: AtomicPtr::new(ptr::null_mut()),
+            slot_count: AtomicUsize::new(0),
+            retired_count: AtomicUsize::new(0),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of retired nodes that are not freed yet.
+    #[inline]
+    pub(crate) fn retired(&self) -> usize {
+        self.retired_count.load(Ordering::Relaxed)
+    }
+
+    /// Claims a free slot, or adds a new one.
+    pub(crate) fn acquire(&self) -> &Slot<N> {
+        let mut cursor = self.slots.load(Ordering::Acquire);
+
+        // Slots are only freed when the domain is dropped.
+        while let Some(slot) = unsafe { cursor.as_ref() } {
+            if !slot.active.load(Ordering::Relaxed)
+                && slot
+                    .active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return slot;
+            }
+
+            cursor = slot.next;
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            hazards: [const { AtomicPtr::new(ptr::null_mut()) }; HAZARDS],
+            active: AtomicBool::new(true),
+            retired: UnsafeCell::new(Vec::new()),
+            next: ptr::null_mut(),
+        }));
+
+        let mut head = self.slots.load(Ordering::Relaxed);
+
+        loop {
+            // The slot is not shared until the exchange succeeds.
+            unsafe { (*slot).next = head };
+
+            match self
+                .slots
+                .compare_exchange_weak(head, slot, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+
+        self.slot_count.fetch_add(1, Ordering::Relaxed);
+        unsafe { &*slot }
+    }
+
+    /// Returns `true` if any hazard holds `node`.
+    pub(crate) fn is_protected(&self, node: *mut N) -> bool {
+        self.hazards().any(|hazard| hazard == node)
+    }
+
+    /// Retires `node` onto `slot`, and frees the nodes retired there
+    /// that no hazard holds, once enough of them are retired.
+    ///
+    /// # Safety
+    /// `slot` must be claimed from this domain by the calling thread,
+    /// and `node` must be a leaked `Box`, that is unlinked and retired only once.
+    pub(crate) unsafe fn retire(&self, slot: &Slot<N>, node: *mut N) {
+        // The claimed slot is owned by this thread.
+        let retired = &mut *slot.retired.get();
+        retired.push(node);
+        self.retired_count.fetch_add(1, Ordering::Relaxed);
+
+        if retired.len() >= 2 * HAZARDS * self.slot_count.load(Ordering::Relaxed) + 32 {
+            let mut hazards = self.hazards().collect::<Vec<_>>();
+            hazards.sort_unstable();
+
+            retired.retain(|&node| {
+                if hazards.binary_search(&node).is_ok() {
+                    return true;
+                }
+
+                // No thread holds the node, and it was retired only once.
+                drop(Box::from_raw(node));
+                self.retired_count.fetch_sub(1, Ordering::Relaxed);
+                false
+            });
+        }
+    }
+
+    /// Returns an iterator over the non-null hazards of all slots.
+    fn hazards(&self) -> impl Iterator<Item = *mut N> + '_ {
+        let head = self.slots.load(Ordering::Acquire);
+
+        // Slots are only freed when the domain is dropped.
+        core::iter::successors(unsafe { head.as_ref() }, |slot| unsafe {
+            slot.next.as_ref()
+        })
+        .flat_map(|slot| slot.hazards.iter())
+        .map(|hazard| hazard.load(Ordering::SeqCst))
+        .filter(|hazard| !hazard.is_null())
+    }
+}
+
+impl<N> Drop for Domain<N> {
+    fn drop(&mut self) {
+        let mut slot = *self.slots.get_mut();
+
+        // Owning the domain rules out other threads, so every slot and retired node can be freed.
+        while !slot.is_null() {
+            let boxed = unsafe { Box::from_raw(slot) };
+            slot = boxed.next;
+
+            boxed
+                .retired
+                .into_inner()
+                .into_iter()
+                .for_each(|node| drop(unsafe { Box::from_raw(node) }));
+        }
+    }
+}
+
+struct Node<T> {
+    item: ManuallyDrop<T>,
+    /// The node below this one, written once before the node is pushed.
+    next: *mut Node<T>,
+}
+
+/// A lock-free stack, whose popped nodes are freed once no thread looks at them anymore.
+///
+/// Pushing and popping take a single compare-and-swap on the head when uncontended,
+/// and both claim a hazard slot to protect the head they look at.
+/// Freeing popped nodes is deferred until a bounded number of them are retired,
+/// and then takes time proportional to that number and the number of slots,
+/// without epochs, and without ever waiting for other threads.
+///
+/// # Examples
+/// ```
+/// # use lists::hazard::HazardStack;
+/// use std::{sync::Arc, thread};
+///
+/// let stack = Arc::new(HazardStack::new());
+///
+/// let pushers = (0..4)
+///     .map(|t| {
+///         let stack = Arc::clone(&stack);
+///         thread::spawn(move || (0..100).for_each(|n| stack.push(t * 100 + n)))
+///     })
+///     .collect::<Vec<_>>();
+///
+/// pushers.into_iter().for_each(|pusher| pusher.join().unwrap());
+///
+/// let mut popped = std::iter::from_fn(|| stack.pop()).collect::<Vec<_>>();
+/// popped.sort_unstable();
+/// assert!(popped.into_iter().eq(0..400));
+/// ```
+pub struct HazardStack<T> {
+    head: AtomicPtr<Node<T>>,
+    domain: Domain<Node<T>>,
+}
+
+// Items are moved in and out of the stack, from any thread.
+unsafe impl<T: Send> Send for HazardStack<T> {}
+unsafe impl<T: Send> Sync for HazardStack<T> {}
+
+impl<T> Default for HazardStack<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for HazardStack<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HazardStack")
+            .field("empty", &self.is_empty())
+            .field("retired", &self.domain.retired())
+            .finish()
+    }
+}
+
+impl<T> HazardStack<T> {
+    /// Returns a new empty stack.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            domain: Domain::new(),
+        }
+    }
+
+    /// Returns `true` if the stack was empty when it was looked at.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    /// Pushes `item` onto the top of the stack.
+    pub fn push(&self, item: T) {
+        let node = Box::into_raw(Box::new(Node {
+            item: ManuallyDrop::new(item),
+            next: ptr::null_mut(),
+        }));
+
+        let slot = self.domain.acquire();
+
+        loop {
+            let head = slot.protect(0, &self.head);
+
+            // The node is not shared until the exchange succeeds.
+            unsafe { (*node).next = head };
+
+            if self
+                .head
+                .compare_exchange(head, node, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        slot.release();
+    }
+
+    /// Removes the top item of the stack, and returns it.
+    pub fn pop(&self) -> Option<T> {
+        let slot = self.domain.acquire();
+
+        let node = loop {
+            let head = slot.protect(0, &self.head);
+
+            if head.is_null() {
+                break None;
+            }
+
+            // The hazard keeps `head` from being freed, and `next` never changes once pushed.
+            let next = unsafe { (*head).next };
+
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                break Some(head);
+            }
+        };
+
+        slot.clear();
+
+        // This thread unlinked the node, so it alone takes the item out and retires it,
+        // while others may still read `next`.
+        let item = node.map(|node| unsafe {
+            let item = ManuallyDrop::into_inner(ptr::read(ptr::addr_of!((*node).item)));
+            self.domain.retire(slot, node);
+            item
+        });
+
+        slot.release();
+        item
+    }
+}
+
+impl<T> Drop for HazardStack<T> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+
+        // Owning the stack rules out other threads, so every node can be freed.
+        while !node.is_null() {
+            let mut boxed = unsafe { Box::from_raw(node) };
+            node = boxed.next;
+            unsafe { ManuallyDrop::drop(&mut boxed.item) };
+        }
+    }
+}
+
+struct QueueNode<T> {
+    /// The item, until the node becomes the front sentinel of the queue.
+    item: MaybeUninit<T>,
+    /// The node behind this one, which is set once, from null.
+    next: AtomicPtr<QueueNode<T>>,
+}
+
+impl<T> QueueNode<T> {
+    #[inline(always)]
+    fn new(item: MaybeUninit<T>) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            item,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+/*
+ * The queue is a Michael-Scott queue: `head` points to a sentinel,
+ * whose item was taken out, or never there, and the items live in the nodes behind it.
+ * Popping swings `head` to the node behind the sentinel, takes its item,
+ * and retires the old sentinel.
+ *
+ * `tail` points to the last node, or to the one right before it, while a push is halfway.
+ * A thread that finds `tail` lagging swings it forward before it tries anything else,
+ * so no thread waits for a push that stalled halfway.
+ * Popping never swings `head` past `tail`, so `tail` never points to a retired node.
+ */
+
+/// A lock-free queue, whose popped nodes are freed once no thread looks at them anymore.
+///
+/// Pushing links a node in behind the back, and popping swings the front past it,
+/// each with a single compare-and-swap when uncontended.
+/// Like [`HazardStack`], popped nodes are freed through hazard pointers,
+/// in bounded pauses that never wait for other threads.
+///
+/// # Examples
+/// ```
+/// # use lists::hazard::HazardQueue;
+/// use std::{sync::Arc, thread};
+///
+/// let queue = Arc::new(HazardQueue::new());
+///
+/// let producer = {
+///     let queue = Arc::clone(&queue);
+///     thread::spawn(move || (0..100).for_each(|n| queue.push(n)))
+/// };
+///
+/// producer.join().unwrap();
+///
+/// // Items come out in the order they were pushed.
+/// assert!(std::iter::from_fn(|| queue.pop()).eq(0..100));
+/// ```
+pub struct HazardQueue<T> {
+    head: AtomicPtr<QueueNode<T>>,
+    tail: AtomicPtr<QueueNode<T>>,
+    domain: Domain<QueueNode<T>>,
+}
+
+// Items are moved in and out of the queue, from any thread.
+unsafe impl<T: Send> Send for HazardQueue<T> {}
+unsafe impl<T: Send> Sync for HazardQueue<T> {}
+
+impl<T> Default for HazardQueue<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for HazardQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HazardQueue")
+            .field("empty", &self.is_empty())
+            .field("retired", &self.domain.retired())
+            .finish()
+    }
+}
+
+impl<T> HazardQueue<T> {
+    /// Returns a new empty queue.
+    #[inline]
+    pub fn new() -> Self {
+        let sentinel = QueueNode::new(MaybeUninit::uninit());
+
+        Self {
+            head: AtomicPtr::new(sentinel),
+            tail: AtomicPtr::new(sentinel),
+            domain: Domain::new(),
+        }
+    }
+
+    /// Returns `true` if the queue was empty when it was looked at.
+    pub fn is_empty(&self) -> bool {
+        let slot = self.domain.acquire();
+        let head = slot.protect(0, &self.head);
+
+        // The hazard keeps `head` from being freed.
+        let empty = unsafe { (*head).next.load(Ordering::SeqCst) }.is_null();
+
+        slot.release();
+        empty
+    }
+
+    /// Pushes `item` onto the back of the queue.
+    pub fn push(&self, item: T) {
+        let node = QueueNode::new(MaybeUninit::new(item));
+        let slot = self.domain.acquire();
+
+        loop {
+            let tail = slot.protect(0, &self.tail);
+
+            // The hazard keeps `tail` from being freed.
+            let next = unsafe { &(*tail).next };
+
+            match next.compare_exchange(ptr::null_mut(), node, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => {
+                    // Failing means another thread already swung `tail` forward.
+                    let _ =
+                        self.tail
+                            .compare_exchange(tail, node, Ordering::SeqCst, Ordering::Relaxed);
+                    break;
+                }
+                Err(behind) => {
+                    let _ = self.tail.compare_exchange(
+                        tail,
+                        behind,
+                        Ordering::SeqCst,
+                        Ordering::Relaxed,
+                    );
+                }
+            }
+        }
+
+        slot.release();
+    }
+
+    /// Removes the front item of the queue, and returns it.
+    pub fn pop(&self) -> Option<T> {
+        let slot = self.domain.acquire();
+
+        let item = loop {
+            let head = slot.protect(0, &self.head);
+
+            // The hazard keeps `head` from being freed.
+            let next = unsafe { (*head).next.load(Ordering::SeqCst) };
+
+            if next.is_null() {
+                break None;
+            }
+
+            // While `head` is still the sentinel, `next` is linked behind it,
+            // so publishing it before checking that protects it, like `protect` does.
+            slot.hazards[1].store(next, Ordering::SeqCst);
+
+            if self.head.load(Ordering::SeqCst) != head {
+                continue;
+            }
+
+            let tail = self.tail.load(Ordering::SeqCst);
+
+            if tail == head {
+                let _ = self
+                    .tail
+                    .compare_exchange(tail, next, Ordering::SeqCst, Ordering::Relaxed);
+                continue;
+            }
+
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                // This thread made `next` the sentinel, so it alone takes its item out,
+                // before the hazard on it is cleared and another pop may retire it.
+                let item = unsafe { ptr::read(ptr::addr_of!((*next).item)).assume_init() };
+                slot.clear();
+
+                // Only the thread that swung `head` past the old sentinel retires it.
+                unsafe { self.domain.retire(slot, head) };
+                break Some(item);
+            }
+        };
+
+        slot.release();
+        item
+    }
+}
+
+impl<T> Drop for HazardQueue<T> {
+    fn drop(&mut self) {
+        // Owning the queue rules out other threads, so every node can be freed.
+        // The sentinel holds no item, and every node behind it does.
+        unsafe {
+            let sentinel = Box::from_raw(*self.head.get_mut());
+            let mut node = sentinel.next.load(Ordering::Relaxed);
+
+            while !node.is_null() {
+                let boxed = Box::from_raw(node);
+                node = boxed.next.load(Ordering::Relaxed);
+                drop(boxed.item.assume_init());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, thread};
+
+    #[test]
+    fn test_concurrent() {
+        let stack = Arc::new(HazardStack::new());
+        let tracker = Arc::new(());
+
+        let workers = (0..4)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                let tracker = Arc::clone(&tracker);
+
+                thread::spawn(move || {
+                    let mut popped = 0;
+
+                    for _ in 0..2000 {
+                        stack.push(Arc::clone(&tracker));
+                        stack.push(Arc::clone(&tracker));
+                        popped += stack.pop().is_some() as usize;
+                    }
+
+                    popped
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let popped = workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .sum::<usize>();
+
+        assert_eq!(popped, 8000);
+        assert_eq!(Arc::strong_count(&tracker), 8001);
+
+        let mut left = 0;
+        while stack.pop().is_some() {
+            left += 1;
+        }
+
+        assert_eq!(left, 8000);
+        assert!(stack.is_empty());
+
+        (0..10).for_each(|_| stack.push(Arc::clone(&tracker)));
+        drop(stack);
+        assert_eq!(Arc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_queue_concurrent() {
+        let queue = Arc::new(HazardQueue::new());
+        let tracker = Arc::new(());
+
+        let producers = (0..2)
+            .map(|t| {
+                let queue = Arc::clone(&queue);
+                let tracker = Arc::clone(&tracker);
+
+                thread::spawn(move || {
+                    (0..2000).for_each(|n| queue.push((t, n, Arc::clone(&tracker))));
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let consumers = (0..2)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+
+                thread::spawn(move || {
+                    let mut last = [None; 2];
+                    let mut popped = 0;
+
+                    for _ in 0..2000 {
+                        if let Some((t, n, _)) = queue.pop() {
+                            // Items of one producer come out in the order they were pushed.
+                            assert!(last[t].is_none_or(|last| last < n));
+                            last[t] = Some(n);
+                            popped += 1;
+                        }
+                    }
+
+                    popped
+                })
+            })
+            .collect::<Vec<_>>();
+
+        producers
+            .into_iter()
+            .for_each(|producer| producer.join().unwrap());
+
+        let popped = consumers
+            .into_iter()
+            .map(|consumer| consumer.join().unwrap())
+            .sum::<usize>();
+
+        let mut left = 0;
+        while queue.pop().is_some() {
+            left += 1;
+        }
+
+        assert_eq!(popped + left, 4000);
+        assert!(queue.is_empty());
+
+        (0..10).for_each(|n| queue.push((0, n, Arc::clone(&tracker))));
+        drop(queue);
+        assert_eq!(Arc::strong_count(&tracker), 1);
+    }
+}
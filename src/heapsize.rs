@@ -0,0 +1,159 @@
+//! Measuring the heap memory owned by values.
+
+use core::mem;
+
+use crate::{doublylist::DoublyList, list::List};
+
+/// Reports the number of bytes a value owns on the heap.
+///
+/// The inline size of the value itself is not included,
+/// as it is already accounted for by whatever holds the value.
+/// For the lists of this crate, this is the memory of all nodes,
+/// plus the heap memory of all items.
+///
+/// # Examples
+/// ```
+/// # use lists::{doublylist::DoublyList, heapsize::HeapSize};
+/// let mut list = DoublyList::new();
+/// list.push_back(String::with_capacity(100));
+/// list.push_back(String::with_capacity(20));
+///
+/// assert_eq!(list.heap_size(), list.memory_usage() + 120);
+/// ```
+pub trait HeapSize {
+    /// Returns the number of bytes owned on the heap.
+    fn heap_size(&self) -> usize;
+}
+
+macro_rules! impl_inline {
+    ($($ty:ty),*) => {
+        $(
+            impl HeapSize for $ty {
+                #[inline(always)]
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_inline!(
+    (),
+    bool,
+    char,
+    str,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+/// Borrowed data is owned by someone else.
+impl<T: ?Sized> HeapSize for &T {
+    #[inline(always)]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: HeapSize> HeapSize for [T] {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.iter().map(HeapSize::heap_size).sum()
+    }
+}
+
+impl<T: HeapSize, const N: usize> HeapSize for [T; N] {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self[..].heap_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+impl<T: ?Sized + HeapSize> HeapSize for Box<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        mem::size_of_val(&**self) + (**self).heap_size()
+    }
+}
+
+impl HeapSize for String {
+    #[inline(always)]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self[..].heap_size()
+    }
+}
+
+impl<T: ?Sized + HeapSize> HeapSize for List<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.memory_usage() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: ?Sized + HeapSize> HeapSize for DoublyList<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.memory_usage() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doublylist::{Link, Node};
+
+    #[test]
+    fn test_memory_usage() {
+        let mut list = List::new();
+        list.push(vec![String::with_capacity(8), String::with_capacity(8)]);
+        list.push(Vec::with_capacity(3));
+
+        let node = mem::size_of::<crate::list::Node<Vec<String>>>();
+        assert_eq!(list.memory_usage(), 2 * node);
+
+        let items = (3 + 2) * mem::size_of::<String>() + 2 * 8;
+        assert_eq!(list.heap_size(), 2 * node + items);
+
+        let mut slices = DoublyList::<[u16]>::new();
+        slices.push_back_node(Node::from_vec(vec![1, 2, 3]));
+        slices.push_back_node(Node::boxed_slice(&[]));
+
+        let header = 2 * mem::size_of::<Link<[u16]>>();
+        let expected = (header + 3 * 2).next_multiple_of(mem::align_of::<usize>()) + header;
+        assert_eq!(slices.memory_usage(), expected);
+        assert_eq!(slices.heap_size(), expected);
+    }
+}
@@ -0,0 +1,162 @@
+//! A list of callbacks, invoked in the order they were registered.
+
+use core::fmt::{self, Debug};
+
+use crate::veclist::{Index, VecList};
+
+/// A handle to a callback registered with a [`HookList`], to unregister it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HookId(Index);
+
+/// A list of callbacks taking `Args`, invoked in the order they were registered.
+///
+/// Registering and unregistering a callback take O(1).
+/// The callbacks are kept in a [`VecList`],
+/// so a [`HookId`] is never mistaken for a callback registered after it was unregistered.
+///
+/// # Examples
+/// ```
+/// # use lists::hook::HookList;
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let mut hooks = HookList::new();
+///
+/// let first = Rc::clone(&log);
+/// let id = hooks.register(move |n: u32| first.borrow_mut().push(n));
+/// let second = Rc::clone(&log);
+/// hooks.register(move |n: u32| second.borrow_mut().push(n * 10));
+///
+/// hooks.invoke_all(1);
+/// assert!(hooks.unregister(id));
+/// hooks.invoke_all(2);
+///
+/// assert_eq!(*log.borrow(), [1, 10, 20]);
+/// ```
+pub struct HookList<'a, Args> {
+    hooks: VecList<Box<dyn FnMut(Args) + 'a>>,
+}
+
+impl<Args> Default for HookList<'_, Args> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Args> Debug for HookList<'_, Args> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HookList")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<'a, Args> HookList<'a, Args> {
+    /// Returns a new list without callbacks.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            hooks: VecList::new(),
+        }
+    }
+
+    /// Returns the number of registered callbacks.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Returns `true` if no callbacks are registered.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Registers `hook` after all other callbacks, and returns a handle to unregister it.
+    #[inline]
+    pub fn register<F>(&mut self, hook: F) -> HookId
+    where
+        F: FnMut(Args) + 'a,
+    {
+        HookId(self.hooks.push_back(Box::new(hook)))
+    }
+
+    /// Unregisters the callback `id`.
+    /// Returns `false` if it was already unregistered.
+    #[inline]
+    pub fn unregister(&mut self, id: HookId) -> bool {
+        self.hooks.remove(id.0).is_some()
+    }
+
+    /// Returns `true` if the callback `id` is still registered.
+    #[inline(always)]
+    pub fn is_registered(&self, id: HookId) -> bool {
+        self.hooks.contains(id.0)
+    }
+
+    /// Unregisters all callbacks.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.hooks.clear();
+    }
+
+    /// Invokes every callback with a clone of `args`, in the order they were registered.
+    pub fn invoke_all(&mut self, args: Args)
+    where
+        Args: Clone,
+    {
+        let mut hooks = self.hooks.iter_mut();
+
+        // Saves a clone, by moving `args` into the last callback.
+        let last = hooks.next_back();
+
+        for hook in hooks {
+            hook(args.clone());
+        }
+
+        if let Some(hook) = last {
+            hook(args);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_order() {
+        let mut calls = Vec::new();
+
+        {
+            let calls = core::cell::RefCell::new(&mut calls);
+            let calls = &calls;
+            let mut hooks = HookList::new();
+
+            let ids = (0..5)
+                .map(|n| hooks.register(move |arg: &str| calls.borrow_mut().push((n, arg.len()))))
+                .collect::<Vec<_>>();
+
+            assert!(hooks.unregister(ids[1]));
+            assert!(hooks.unregister(ids[3]));
+            assert!(!hooks.unregister(ids[3]));
+            assert_eq!(hooks.len(), 3);
+
+            hooks.invoke_all("abc");
+
+            // The slot of a removed callback is reused, but its handle stays dead.
+            let late = hooks.register(move |arg: &str| calls.borrow_mut().push((9, arg.len())));
+            assert!(!hooks.is_registered(ids[3]));
+            assert!(hooks.is_registered(late));
+
+            hooks.invoke_all("");
+        }
+
+        assert_eq!(
+            calls,
+            [(0, 3), (2, 3), (4, 3), (0, 0), (2, 0), (4, 0), (9, 0)]
+        );
+    }
+}
@@ -0,0 +1,545 @@
+//! Positional access into a [`DoublyList`], through evenly spaced fingers.
+
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+use crate::doublylist::{DoublyList, Iter, NodeRef, Version};
+
+/// The smallest distance between two fingers.
+const MIN_STRIDE: usize = 8;
+
+/// A [`DoublyList`] with an index of "finger" handles,
+/// pointing at every `stride`-th node.
+///
+/// With fingers `k` nodes apart, [`get`](IndexedList::get) walks at most `k / 2` nodes,
+/// and [`insert`](IndexedList::insert) and [`remove`](IndexedList::remove)
+/// additionally shift every finger behind the edit by one node,
+/// costing O(len / k + k) instead of O(len).
+/// The stride is kept near the square root of the length,
+/// by rebuilding the index whenever the length has doubled or halved twice.
+///
+/// The list can still be edited freely through [`list_mut`](IndexedList::list_mut),
+/// after which the index is rebuilt on the next positional edit.
+///
+/// # Examples
+/// ```
+/// # use lists::indexed::IndexedList;
+/// let mut list = (0..100_000).collect::<IndexedList<u32>>();
+///
+/// list.insert(50_000, 7);
+/// assert_eq!(list.get(50_000), Some(&7));
+/// assert_eq!(list.remove(50_001), Some(50_000));
+/// assert_eq!(list.get(99_999), Some(&99_999));
+/// ```
+pub struct IndexedList<T> {
+    list: DoublyList<T>,
+
+    /// `fingers[j]` is the node at index `j * stride`.
+    fingers: Vec<NodeRef<T>>,
+
+    stride: usize,
+
+    /// The version of the list the fingers were last updated for.
+    /// Any other version means they have to be rebuilt before they can be used,
+    /// and so does `None`, after the list was handed out by `list_mut`.
+    version: Option<Version>,
+}
+
+impl<T> Default for IndexedList<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for IndexedList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<T> From<DoublyList<T>> for IndexedList<T> {
+    #[inline]
+    fn from(list: DoublyList<T>) -> Self {
+        let mut list = Self {
+            version: Some(list.version()),
+            list,
+            fingers: Vec::new(),
+            stride: MIN_STRIDE,
+        };
+
+        list.rebuild();
+        list
+    }
+}
+
+impl<T> FromIterator<T> for IndexedList<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from(iter.into_iter().collect::<DoublyList<T>>())
+    }
+}
+
+impl<T> IndexedList<T> {
+    /// Returns a new empty list.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        let list = DoublyList::new();
+
+        Self {
+            version: Some(list.version()),
+            list,
+            fingers: Vec::new(),
+            stride: MIN_STRIDE,
+        }
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.list.len() == 0
+    }
+
+    /// Returns a reference to the underlying list.
+    #[inline(always)]
+    pub const fn as_list(&self) -> &DoublyList<T> {
+        &self.list
+    }
+
+    /// Returns a mutable reference to the underlying list.
+    ///
+    /// This invalidates the index, which is rebuilt in O(n) on the next positional edit,
+    /// as the list may be replaced by another one with the same version.
+    /// Until then, [`get`](IndexedList::get) walks the list from its nearest end.
+    #[inline(always)]
+    pub fn list_mut(&mut self) -> &mut DoublyList<T> {
+        self.version = None;
+        &mut self.list
+    }
+
+    /// Returns the underlying list.
+    #[inline(always)]
+    pub fn into_inner(self) -> DoublyList<T> {
+        self.list
+    }
+
+    /// Returns an iterator over the items of the list.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    /// Rebuilds the fingers, with a stride fitting the current length.
+    fn rebuild(&mut self) {
+        self.stride = self.len().isqrt().max(MIN_STRIDE);
+        self.fingers.clear();
+
+        let mut cursor = self.list.front_ref();
+        let mut index = 0usize;
+
+        // Every handle comes from walking the list.
+        while let Some(node) = cursor {
+            if index.is_multiple_of(self.stride) {
+                self.fingers.push(node);
+            }
+
+            cursor = unsafe { self.list.next_ref(node) };
+            index += 1;
+        }
+
+        self.version = Some(self.list.version());
+    }
+
+    /// Returns `true` if the list changed behind the back of the fingers.
+    #[inline(always)]
+    fn is_dirty(&self) -> bool {
+        self.version
+            .is_none_or(|version| self.list.check_version(version).is_err())
+    }
+
+    #[inline(always)]
+    fn ensure_index(&mut self) {
+        if self.is_dirty() {
+            self.rebuild();
+        }
+    }
+
+    /// Returns the index of the first finger at or behind `index`.
+    #[inline(always)]
+    fn first_finger_from(&self, index: usize) -> usize {
+        index.div_ceil(self.stride).min(self.fingers.len())
+    }
+
+    /// Returns the handle of the node at `index`,
+    /// starting the walk from the nearest finger or end of the list.
+    ///
+    /// `index` must be less than `len()`.
+    fn node_at(&self, index: usize) -> NodeRef<T> {
+        let last = self.len() - 1;
+
+        let (mut node, mut steps, forward) = match self.is_dirty() {
+            true if index <= last - index => (self.list.front_ref(), index, true),
+            true => (self.list.back_ref(), last - index, false),
+            false => {
+                let finger = index / self.stride;
+                let offset = index % self.stride;
+
+                match self.fingers.get(finger + 1) {
+                    Some(&next) if self.stride - offset < offset => {
+                        (Some(next), self.stride - offset, false)
+                    }
+                    None if last - index < offset => (self.list.back_ref(), last - index, false),
+                    _ => (Some(self.fingers[finger]), offset, true),
+                }
+            }
+        };
+
+        // Both the fingers and the ends are linked into the list,
+        // and `index` lies within it.
+        unsafe {
+            while steps != 0 {
+                node = match forward {
+                    true => self.list.next_ref(node.unwrap()),
+                    false => self.list.prev_ref(node.unwrap()),
+                };
+                steps -= 1;
+            }
+
+            node.unwrap()
+        }
+    }
+
+    /// Returns a reference to the item at `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        // The node was just found in the list.
+        Some(unsafe { self.list.get(self.node_at(index)) })
+    }
+
+    /// Returns a mutable reference to the item at `index`.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let node = self.node_at(index);
+
+        // The node was just found in the list.
+        Some(unsafe { self.list.get_mut(node) })
+    }
+
+    /// Inserts `item` at `index`, shifting all items after it back by one.
+    ///
+    /// # Panics
+    /// Panics if `index` is larger than `len()`.
+    pub fn insert(&mut self, index: usize, item: T) {
+        assert!(
+            index <= self.len(),
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len()
+        );
+
+        self.ensure_index();
+
+        if index == self.len() {
+            self.list.push_back(item);
+        } else {
+            let at = self.node_at(index);
+
+            // The node was just found in the list.
+            unsafe { self.list.insert_before(at, item) };
+        }
+
+        // Every finger at or behind `index` now points one node too far.
+        let from = self.first_finger_from(index);
+
+        for finger in &mut self.fingers[from..] {
+            // A node at or behind `index` has at least the new node before it.
+            *finger = unsafe { self.list.prev_ref(*finger).unwrap() };
+        }
+
+        let last = self.len() - 1;
+
+        if last.is_multiple_of(self.stride) {
+            self.fingers.extend(self.list.back_ref());
+        }
+
+        self.version = Some(self.list.version());
+
+        if self.len() > 4 * self.stride * self.stride {
+            self.rebuild();
+        }
+    }
+
+    /// Pushes `item` to the back of the list.
+    #[inline]
+    pub fn push_back(&mut self, item: T) {
+        self.insert(self.len(), item)
+    }
+
+    /// Removes and returns the item at `index`, shifting all items after it forward by one.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        self.ensure_index();
+
+        let node = self.node_at(index);
+
+        // Every finger at or behind `index` has to point one node further,
+        // which is found before the node is unlinked.
+        let from = self.first_finger_from(index);
+
+        for j in from..self.fingers.len() {
+            // The fingers are linked into the list.
+            match unsafe { self.list.next_ref(self.fingers[j]) } {
+                Some(next) => self.fingers[j] = next,
+                None => self.fingers.truncate(j),
+            }
+        }
+
+        // The node was just found in the list.
+        let item = unsafe { self.list.remove(node) };
+        self.version = Some(self.list.version());
+
+        if self.stride > MIN_STRIDE && 4 * self.len() < self.stride * self.stride {
+            self.rebuild();
+        }
+
+        Some(item)
+    }
+
+    /// Searches a sorted list for `item`, like [`slice::binary_search`].
+    ///
+    /// Returns `Ok` with the index of a matching item, or `Err` with the index
+    /// where `item` could be inserted to keep the list sorted.
+    /// If several items match, any one of them may be found.
+    ///
+    /// The fingers are searched by bisection, and only the nodes between two of them are walked,
+    /// which takes O(log n + √n) comparisons.
+    /// If the index is out of date, the list is walked from its front instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::indexed::IndexedList;
+    /// let mut list = (0..1000).map(|n| n * 2).collect::<IndexedList<u32>>();
+    ///
+    /// assert_eq!(list.binary_search(&500), Ok(250));
+    /// assert_eq!(list.binary_search(&501), Err(251));
+    ///
+    /// let at = list.binary_search(&501).unwrap_or_else(|at| at);
+    /// list.insert(at, 501);
+    /// assert!(list.as_list().is_sorted());
+    /// ```
+    #[inline]
+    pub fn binary_search(&self, item: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|probe| probe.cmp(item))
+    }
+
+    /// Searches a sorted list with a comparator function, like [`slice::binary_search_by`].
+    /// See [`binary_search`](IndexedList::binary_search).
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        if self.is_dirty() {
+            return self.search_from(self.list.front_ref(), 0, f);
+        }
+
+        // The fingers are linked into the list.
+        let block = self
+            .fingers
+            .partition_point(|&finger| f(unsafe { self.list.get(finger) }) == Ordering::Less);
+
+        match block.checked_sub(1) {
+            None => self.search_from(self.list.front_ref(), 0, f),
+            // The finger is linked, and orders before the item, so the search continues after it.
+            Some(finger) => unsafe {
+                let next = self.list.next_ref(self.fingers[finger]);
+                self.search_from(next, finger * self.stride + 1, f)
+            },
+        }
+    }
+
+    /// Searches a sorted list for the key `f` returns, like [`slice::binary_search_by_key`].
+    /// See [`binary_search`](IndexedList::binary_search).
+    #[inline]
+    pub fn binary_search_by_key<K, F>(&self, key: &K, mut f: F) -> Result<usize, usize>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.binary_search_by(|probe| f(probe).cmp(key))
+    }
+
+    /// Walks the list from `cursor`, the node at `index`,
+    /// up to the first item that `f` does not order before the searched item.
+    fn search_from<F>(
+        &self,
+        mut cursor: Option<NodeRef<T>>,
+        mut index: usize,
+        mut f: F,
+    ) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        // Every handle comes from walking the list.
+        while let Some(node) = cursor {
+            match f(unsafe { self.list.get(node) }) {
+                Ordering::Less => {}
+                Ordering::Equal => return Ok(index),
+                Ordering::Greater => return Err(index),
+            }
+
+            cursor = unsafe { self.list.next_ref(node) };
+            index += 1;
+        }
+
+        Err(index)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IndexedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against_vec() {
+        let mut list = IndexedList::new();
+        let mut model = Vec::new();
+
+        for step in 0..3000usize {
+            let at = (step * 7919) % (model.len() + 1);
+            list.insert(at, step);
+            model.insert(at, step);
+
+            if step % 3 == 0 {
+                let at = (step * 104_729) % model.len();
+                assert_eq!(list.remove(at), Some(model.remove(at)));
+            }
+
+            if step % 500 == 0 {
+                list.list_mut().push_front(step);
+                model.insert(0, step);
+            }
+        }
+
+        assert!((0..model.len()).all(|idx| list.get(idx) == Some(&model[idx])));
+        assert_eq!(list.get(model.len()), None);
+
+        while let Some(item) = list.remove(model.len() / 2) {
+            assert_eq!(item, model.remove(model.len() / 2));
+        }
+
+        assert!(model.is_empty());
+        list.as_list().assert_invariants();
+    }
+
+    #[test]
+    fn test_binary_search() {
+        let model = (0..500u32).map(|n| n / 3 * 2).collect::<Vec<_>>();
+        let mut list = model.iter().copied().collect::<IndexedList<_>>();
+
+        for dirty in [false, true] {
+            assert_eq!(list.is_dirty(), dirty);
+
+            for x in 0..model.last().unwrap() + 3 {
+                match list.binary_search(&x) {
+                    Ok(at) => assert_eq!(model[at], x),
+                    Err(at) => assert_eq!(model.binary_search(&x), Err(at)),
+                }
+            }
+
+            // Relinks nodes without changing the order, which dirties the index.
+            let front = list.list_mut().pop_front().unwrap();
+            list.list_mut().push_front(front);
+        }
+
+        assert_eq!(IndexedList::new().binary_search(&0), Err(0));
+
+        // The new list has the same version as the old one, but none of its nodes.
+        let mut list = (0..100).collect::<IndexedList<u32>>();
+        let other = (100..200).collect::<DoublyList<u32>>();
+        assert_eq!(other.version(), list.as_list().version());
+
+        *list.list_mut() = other;
+        assert_eq!(list.binary_search(&150), Ok(50));
+        assert_eq!(list.binary_search(&99), Err(0));
+        assert_eq!(list.binary_search(&200), Err(100));
+    }
+
+    #[test]
+    fn test_replace_list() {
+        let mut list = (0..100).collect::<IndexedList<u32>>();
+        let other = (100..200).collect::<DoublyList<u32>>();
+        assert_eq!(other.version(), list.as_list().version());
+
+        // The fingers point into the old list, which is dropped here.
+        *list.list_mut() = other;
+        assert!(list.is_dirty());
+        assert_eq!(list.get(50), Some(&150));
+
+        list.insert(0, 0);
+        assert_eq!(list.get(51), Some(&150));
+    }
+
+    #[test]
+    fn test_fingers() {
+        let mut list = (0..1000).collect::<IndexedList<_>>();
+        assert_eq!(list.stride, 31);
+        assert_eq!(list.fingers.len(), 33);
+
+        *list.get_mut(500).unwrap() = 0;
+        assert!(!list.is_dirty());
+
+        list.list_mut().push_back(0);
+        assert!(list.is_dirty());
+        list.remove(1000);
+        list.remove(0);
+        list.insert(10, 0);
+
+        let mut walk = list.iter().step_by(list.stride);
+
+        for &finger in &list.fingers {
+            assert!(core::ptr::eq(
+                unsafe { list.list.get(finger) },
+                walk.next().unwrap()
+            ));
+        }
+
+        assert_eq!(walk.next(), None);
+    }
+}
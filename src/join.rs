@@ -0,0 +1,50 @@
+//! Formatting helpers shared by the list types.
+
+use core::fmt::{self, Debug, Display};
+
+/// Displays the items of a list, with a separator in between.
+///
+/// Returned by [`List::join`](crate::list::List::join)
+/// and [`DoublyList::join`](crate::doublylist::DoublyList::join).
+/// Formatting options, like width and precision,
+/// are applied to every item.
+#[derive(Clone, Copy)]
+pub struct Join<'s, I> {
+    iter: I,
+    separator: &'s str,
+}
+
+impl<'s, I> Join<'s, I> {
+    #[inline(always)]
+    pub(crate) const fn new(iter: I, separator: &'s str) -> Self {
+        Self { iter, separator }
+    }
+}
+
+impl<'a, I, T> Display for Join<'_, I>
+where
+    I: Iterator<Item = &'a T> + Clone,
+    T: ?Sized + Display + 'a,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut iter = self.iter.clone();
+
+        if let Some(first) = iter.next() {
+            Display::fmt(first, f)?;
+
+            for item in iter {
+                f.write_str(self.separator)?;
+                Display::fmt(item, f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<I> Debug for Join<'_, I> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Join").field(&self.separator).finish()
+    }
+}
@@ -0,0 +1,213 @@
+//! An undo/redo history, built from two [`DoublyList`]s.
+
+use core::fmt::{self, Debug};
+
+use crate::doublylist::{DoublyList, Iter};
+
+/// An undo/redo history of edits of type `T`.
+///
+/// Recorded edits go onto the undo stack.
+/// Undoing moves the newest edit over to the redo stack, and redoing moves it back,
+/// relinking its node instead of allocating a new one.
+/// Recording a new edit discards the redo stack,
+/// and once the undo stack is deeper than the depth of the journal,
+/// its oldest edit is discarded.
+///
+/// # Examples
+/// ```
+/// # use lists::journal::Journal;
+/// let mut text = String::new();
+/// let mut journal = Journal::new();
+///
+/// for word in ["hello", " world"] {
+///     text.push_str(word);
+///     journal.record(word);
+/// }
+///
+/// if let Some(word) = journal.undo() {
+///     text.truncate(text.len() - word.len());
+/// }
+/// assert_eq!(text, "hello");
+///
+/// if let Some(word) = journal.redo() {
+///     text.push_str(word);
+/// }
+/// assert_eq!(text, "hello world");
+/// ```
+pub struct Journal<T> {
+    /// The edits that can be undone, newest at the back.
+    undo: DoublyList<T>,
+
+    /// The edits that can be redone, next one at the back.
+    redo: DoublyList<T>,
+
+    depth: usize,
+}
+
+impl<T> Default for Journal<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for Journal<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Journal")
+            .field("undo", &self.undo)
+            .field("redo", &self.redo)
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+impl<T> Journal<T> {
+    /// Returns a new empty journal, that keeps every edit.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self::with_depth(usize::MAX)
+    }
+
+    /// Returns a new empty journal, that keeps at most `depth` edits to undo.
+    #[inline(always)]
+    pub const fn with_depth(depth: usize) -> Self {
+        Self {
+            undo: DoublyList::new(),
+            redo: DoublyList::new(),
+            depth,
+        }
+    }
+
+    /// Returns the most edits the journal keeps to undo.
+    #[inline(always)]
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Sets the most edits the journal keeps to undo,
+    /// discarding the oldest edits that don't fit anymore.
+    #[inline]
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Returns the number of edits that can be undone.
+    #[inline(always)]
+    pub const fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Returns the number of edits that can be redone.
+    #[inline(always)]
+    pub const fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Returns `true` if there is an edit to undo.
+    #[inline(always)]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is an edit to redo.
+    #[inline(always)]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Records `edit` as the newest edit, and discards the edits that could be redone.
+    ///
+    /// Returns the oldest edit, if it no longer fits within the depth of the journal.
+    pub fn record(&mut self, edit: T) -> Option<T> {
+        self.redo.clear();
+
+        if self.depth == 0 {
+            return Some(edit);
+        }
+
+        let discarded = if self.undo.len() == self.depth {
+            self.undo.pop_front()
+        } else {
+            None
+        };
+
+        self.undo.push_back(edit);
+        discarded
+    }
+
+    /// Moves the newest edit over to the redo stack, and returns it, to be reverted.
+    #[inline]
+    pub fn undo(&mut self) -> Option<&T> {
+        let edit = self.undo.pop_back_node()?;
+        self.redo.push_back_node(edit);
+        self.redo.peek_back()
+    }
+
+    /// Moves the last undone edit back to the undo stack, and returns it, to be applied again.
+    #[inline]
+    pub fn redo(&mut self) -> Option<&T> {
+        let edit = self.redo.pop_back_node()?;
+        self.undo.push_back_node(edit);
+        self.undo.peek_back()
+    }
+
+    /// Returns an iterator over the edits that can be undone, oldest first.
+    #[inline(always)]
+    pub fn undo_iter(&self) -> Iter<'_, T> {
+        self.undo.iter()
+    }
+
+    /// Returns an iterator over the edits that can be redone, the one redone last first.
+    #[inline(always)]
+    pub fn redo_iter(&self) -> Iter<'_, T> {
+        self.redo.iter()
+    }
+
+    /// Discards all edits.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut journal = Journal::with_depth(3);
+
+        for edit in 0..5u32 {
+            assert_eq!(journal.record(edit), edit.checked_sub(3));
+        }
+
+        assert!(journal.undo_iter().eq(&[2, 3, 4]));
+        assert_eq!(journal.undo(), Some(&4));
+        assert_eq!(journal.undo(), Some(&3));
+        assert_eq!(journal.redo(), Some(&3));
+        assert_eq!(journal.undo(), Some(&3));
+        assert_eq!(journal.undo(), Some(&2));
+        assert_eq!(journal.undo(), None);
+        assert!(journal.redo_iter().eq(&[4, 3, 2]));
+
+        assert_eq!(journal.redo(), Some(&2));
+        assert_eq!(journal.record(9), None);
+        assert!(!journal.can_redo());
+        assert_eq!(journal.redo(), None);
+        assert!(journal.undo_iter().eq(&[2, 9]));
+
+        journal.set_depth(1);
+        assert!(journal.undo_iter().eq(&[9]));
+
+        let mut closed = Journal::with_depth(0);
+        assert_eq!(closed.record(1), Some(1));
+        assert!(!closed.can_undo());
+    }
+}
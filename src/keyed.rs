@@ -0,0 +1,336 @@
+//! A list of values that can be found by key in O(1), built on a [`LinkedHashMap`].
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::Hash,
+    iter::FusedIterator,
+};
+
+use crate::linked_hash_map::{self, LinkedHashMap};
+
+/// A doubly linked list of values, each with a unique key.
+///
+/// The values live in a [`LinkedHashMap`], which keeps the node of every key,
+/// so values are found, removed and moved to either end by key in O(1),
+/// while iterating goes in list order.
+/// Pushing a key that is already in the list replaces its value,
+/// and moves its node to that end without allocating,
+/// which suits caches, deduplicating queues and rate limiters.
+///
+/// A key whose `Hash`, `Eq` or `Clone` implementations disagree with each other
+/// leads to unspecified results, but never to undefined behavior.
+///
+/// # Examples
+/// ```
+/// # use lists::keyed::KeyedList;
+/// let mut recent = KeyedList::new();
+///
+/// for (page, visits) in [("a", 1), ("b", 1), ("a", 2), ("c", 1)] {
+///     recent.push_front(page, visits);
+/// }
+///
+/// let pages = recent.iter().map(|(&page, &visits)| (page, visits));
+/// assert!(pages.eq([("c", 1), ("a", 2), ("b", 1)]));
+///
+/// recent.move_to_front("b");
+/// assert_eq!(recent.pop_back(), Some(("a", 2)));
+/// assert_eq!(recent.get("b"), Some(&1));
+/// ```
+pub struct KeyedList<K, V> {
+    map: LinkedHashMap<K, V>,
+}
+
+impl<K, V> Default for KeyedList<K, V> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for KeyedList<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> KeyedList<K, V> {
+    /// Returns a new empty list.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            map: LinkedHashMap::new(),
+        }
+    }
+
+    /// Returns the number of values in the list.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all values from the list.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the front value and its key.
+    #[inline(always)]
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.map.front()
+    }
+
+    /// Returns the back value and its key.
+    #[inline(always)]
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.map.back()
+    }
+
+    /// Returns an iterator over the values and their keys, in list order.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: self.map.iter(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> KeyedList<K, V> {
+    /// Returns `true` if the list holds a value for `key`.
+    #[inline(always)]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the value of `key`.
+    #[inline(always)]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns a mutable reference to the value of `key`.
+    #[inline(always)]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get_mut(key)
+    }
+
+    /// Pushes `value` with `key` to the front of the list.
+    /// If the list held `key` already, its node is moved to the front,
+    /// and the old value is replaced and returned.
+    #[inline(always)]
+    pub fn push_front(&mut self, key: K, value: V) -> Option<V> {
+        self.map.insert_front(key, value)
+    }
+
+    /// Pushes `value` with `key` to the back of the list.
+    /// If the list held `key` already, its node is moved to the back,
+    /// and the old value is replaced and returned.
+    #[inline(always)]
+    pub fn push_back(&mut self, key: K, value: V) -> Option<V> {
+        self.map.insert_back(key, value)
+    }
+
+    /// Removes the value of `key` from the list, and returns it.
+    #[inline(always)]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(key)
+    }
+
+    /// Removes the front value, and returns it with its key.
+    #[inline(always)]
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        self.map.pop_front()
+    }
+
+    /// Removes the back value, and returns it with its key.
+    #[inline(always)]
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        self.map.pop_back()
+    }
+
+    /// Moves the value of `key` to the front of the list.
+    /// Returns `false` if the list holds no value for `key`.
+    #[inline(always)]
+    pub fn move_to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.to_front(key)
+    }
+
+    /// Moves the value of `key` to the back of the list.
+    /// Returns `false` if the list holds no value for `key`.
+    #[inline(always)]
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.to_back(key)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a KeyedList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the values of a [`KeyedList`] and their keys, in list order.
+pub struct Iter<'a, K, V> {
+    entries: linked_hash_map::Iter<'a, K, V>,
+}
+
+impl<K, V> Copy for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        hash::Hasher,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// A key equal to every other key, that hashes differently every time.
+    #[derive(Clone, Debug)]
+    struct Fickle;
+
+    impl Hash for Fickle {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            static HASHES: AtomicU64 = AtomicU64::new(0);
+            HASHES.fetch_add(1, Ordering::Relaxed).hash(state);
+        }
+    }
+
+    impl PartialEq for Fickle {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Eq for Fickle {}
+
+    #[test]
+    fn test_fickle_key() {
+        let mut list = KeyedList::new();
+
+        for n in 0..200 {
+            if n % 2 == 0 {
+                list.push_front(Fickle, n);
+            } else {
+                list.push_back(Fickle, n);
+            }
+
+            for _ in 0..n % 4 {
+                list.get_mut(&Fickle);
+                list.move_to_front(&Fickle);
+                list.move_to_back(&Fickle);
+            }
+
+            if n % 7 == 0 {
+                list.remove(&Fickle);
+            }
+            if n % 3 == 0 {
+                list.pop_front();
+                list.pop_back();
+            }
+        }
+
+        while list.pop_front().is_some() {}
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_queue() {
+        let mut queue = KeyedList::new();
+
+        for n in 0..100u32 {
+            assert_eq!(queue.push_back(n % 10, n), n.checked_sub(10));
+        }
+
+        assert_eq!(queue.len(), 10);
+        assert!(queue
+            .iter()
+            .map(|(&k, &v)| (k, v))
+            .eq((0..10).map(|k| (k, 90 + k))));
+
+        assert!(queue.move_to_front(&9));
+        assert!(queue.move_to_back(&0));
+        assert!(!queue.move_to_back(&10));
+        assert_eq!(queue.remove(&5), Some(95));
+        assert_eq!(queue.remove(&5), None);
+        *queue.get_mut(&1).unwrap() = 0;
+
+        assert_eq!(queue.pop_front(), Some((9, 99)));
+        assert_eq!(queue.pop_back(), Some((0, 90)));
+        assert!(queue.iter().map(|(&k, _)| k).eq([1, 2, 3, 4, 6, 7, 8]));
+        assert_eq!(queue.front(), Some((&1, &0)));
+        assert!(!queue.contains_key(&9));
+
+        assert_eq!(queue.push_front(4, 40), Some(94));
+        assert_eq!(queue.push_back(1, 10), Some(0));
+        assert!(queue.iter().map(|(&k, _)| k).eq([4, 2, 3, 6, 7, 8, 1]));
+        assert_eq!(queue.len(), 7);
+    }
+}
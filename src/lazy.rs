@@ -0,0 +1,317 @@
+//! A lazily computed, possibly infinite, persistent list.
+
+use core::{
+    cell::{Cell, OnceCell},
+    fmt::{self, Debug},
+    iter::FusedIterator,
+};
+use std::rc::Rc;
+
+/// The value of a forced cell: nothing, or an item followed by the rest of the list.
+type Forced<'a, T> = Option<(T, LazyList<'a, T>)>;
+
+type Thunk<'a, T> = Box<dyn FnOnce() -> Forced<'a, T> + 'a>;
+
+struct Node<'a, T: 'a> {
+    /// The closure computing the cell, until it is forced.
+    thunk: Cell<Option<Thunk<'a, T>>>,
+    value: OnceCell<Forced<'a, T>>,
+}
+
+impl<T> Drop for Node<'_, T> {
+    // Drops the forced tail iteratively, for as long as this node owns it alone,
+    // instead of recursing once per cell.
+    fn drop(&mut self) {
+        let mut tail = match self.value.take() {
+            Some(Some((_, tail))) => tail,
+            _ => return,
+        };
+
+        while let Ok(mut node) = Rc::try_unwrap(tail.node) {
+            tail = match node.value.take() {
+                Some(Some((_, next))) => next,
+                _ => return,
+            };
+        }
+    }
+}
+
+/// A list whose cells are computed on demand, and remembered once computed.
+///
+/// Every cell holds a closure computing the next item and the rest of the list,
+/// which runs the first time the cell is forced, by looking at it.
+/// That makes the list as long as its closures keep producing items,
+/// which may be forever.
+///
+/// Cloning a list takes O(1), and shares all cells, forced or not.
+/// Items are only ever computed once, whichever clone forces them.
+///
+/// # Examples
+/// ```
+/// # use lists::lazy::LazyList;
+/// let naturals = LazyList::iterate(0u64, |n| n + 1);
+/// let squares = naturals.map(|n| n * n);
+///
+/// assert!(squares.take(4).iter().eq(&[0, 1, 4, 9]));
+/// assert_eq!(naturals.iter().nth(1000), Some(&1000));
+/// ```
+pub struct LazyList<'a, T: 'a> {
+    node: Rc<Node<'a, T>>,
+}
+
+impl<T> Clone for LazyList<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node),
+        }
+    }
+}
+
+impl<T> Default for LazyList<'_, T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::nil()
+    }
+}
+
+impl<T: Debug> Debug for LazyList<'_, T> {
+    /// Formats the items forced so far, followed by `..` if the list may go on.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut cursor = self;
+
+        while let Some(forced) = cursor.node.value.get() {
+            match forced {
+                None => return list.finish(),
+                Some((item, tail)) => {
+                    list.entry(item);
+                    cursor = tail;
+                }
+            }
+        }
+
+        list.entry(&format_args!("..")).finish()
+    }
+}
+
+impl<'a, T> LazyList<'a, T> {
+    #[inline(always)]
+    fn forced(value: Forced<'a, T>) -> Self {
+        Self {
+            node: Rc::new(Node {
+                thunk: Cell::new(None),
+                value: OnceCell::from(value),
+            }),
+        }
+    }
+
+    /// Returns a list computed by `f` once it is forced.
+    /// `f` returns `None` for an empty list,
+    /// or the first item together with the rest of the list.
+    #[inline]
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Option<(T, Self)> + 'a,
+    {
+        Self {
+            node: Rc::new(Node {
+                thunk: Cell::new(Some(Box::new(f))),
+                value: OnceCell::new(),
+            }),
+        }
+    }
+
+    /// Returns an empty list.
+    #[inline(always)]
+    pub fn nil() -> Self {
+        Self::forced(None)
+    }
+
+    /// Returns the list starting with `item`, followed by `tail`.
+    #[inline(always)]
+    pub fn cons(item: T, tail: Self) -> Self {
+        Self::forced(Some((item, tail)))
+    }
+
+    /// Returns a list of the items of `iter`,
+    /// which is only advanced as far as the list is forced.
+    pub fn from_iter_lazy<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: 'a,
+    {
+        fn go<'a, I: Iterator + 'a>(mut iter: I) -> LazyList<'a, I::Item> {
+            LazyList::new(move || {
+                let item = iter.next()?;
+                Some((item, go(iter)))
+            })
+        }
+
+        go(iter.into_iter())
+    }
+
+    /// Returns the infinite list of `first`, `f(first)`, `f(f(first))`, and so on.
+    pub fn iterate<F>(first: T, f: F) -> Self
+    where
+        F: Fn(&T) -> T + 'a,
+    {
+        fn go<'a, T>(item: T, f: Rc<dyn Fn(&T) -> T + 'a>) -> LazyList<'a, T> {
+            LazyList::new(move || {
+                let next = f(&item);
+                Some((item, go(next, f)))
+            })
+        }
+
+        go(first, Rc::new(f))
+    }
+
+    /// Computes the first cell of the list, unless it was already computed.
+    ///
+    /// # Panics
+    /// Panics if the cell is forced again while it is being computed.
+    #[inline]
+    fn force(&self) -> &Forced<'a, T> {
+        self.node.value.get_or_init(|| {
+            let thunk = self.node.thunk.take();
+            thunk.expect("a lazy list was forced while it was being computed")()
+        })
+    }
+
+    /// Returns `true` if the first cell of the list was computed already.
+    #[inline(always)]
+    pub fn is_forced(&self) -> bool {
+        self.node.value.get().is_some()
+    }
+
+    /// Returns `true` if the list is empty, forcing its first cell.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.force().is_none()
+    }
+
+    /// Returns the first item, forcing the first cell.
+    #[inline]
+    pub fn head(&self) -> Option<&T> {
+        self.force().as_ref().map(|(item, _)| item)
+    }
+
+    /// Returns the list after the first item, forcing the first cell.
+    #[inline]
+    pub fn tail(&self) -> Option<&Self> {
+        self.force().as_ref().map(|(_, tail)| tail)
+    }
+
+    /// Returns an iterator over the items of the list, forcing cells as it goes.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter { cursor: Some(self) }
+    }
+
+    /// Returns the list of `f` applied to every item, computed on demand.
+    pub fn map<U, F>(&self, f: F) -> LazyList<'a, U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        fn go<'a, T: 'a, U: 'a>(
+            list: LazyList<'a, T>,
+            f: Rc<dyn Fn(&T) -> U + 'a>,
+        ) -> LazyList<'a, U> {
+            LazyList::new(move || {
+                let (item, tail) = list.force().as_ref()?;
+                Some((f(item), go(tail.clone(), f)))
+            })
+        }
+
+        go(self.clone(), Rc::new(f))
+    }
+
+    /// Returns the list of the first `n` items, computed on demand.
+    pub fn take(&self, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let list = self.clone();
+
+        LazyList::new(move || {
+            if n == 0 {
+                return None;
+            }
+
+            let (item, tail) = list.force().as_ref()?;
+            Some((item.clone(), tail.take(n - 1)))
+        })
+    }
+}
+
+/// An iterator over the items of a [`LazyList`], forcing its cells.
+pub struct Iter<'l, 'a, T> {
+    cursor: Option<&'l LazyList<'a, T>>,
+}
+
+impl<T> Clone for Iter<'_, '_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            cursor: self.cursor,
+        }
+    }
+}
+
+impl<'l, T> Iterator for Iter<'l, '_, T> {
+    type Item = &'l T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.cursor?.force() {
+            None => {
+                self.cursor = None;
+                None
+            }
+            Some((item, tail)) => {
+                self.cursor = Some(tail);
+                Some(item)
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, '_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memoized() {
+        let calls = Cell::new(0);
+        let list = LazyList::from_iter_lazy((0..10).inspect(|_| calls.set(calls.get() + 1)));
+
+        assert!(!list.is_forced());
+        assert_eq!(format!("{:?}", list), "[..]");
+
+        let evens = list.map(|n| n * 2);
+        assert!(evens.take(3).iter().eq(&[0, 2, 4]));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(format!("{:?}", list), "[0, 1, 2, ..]");
+
+        let copy = list.clone();
+        assert_eq!(copy.iter().count(), 10);
+        assert!(list.iter().copied().eq(0..10));
+        assert_eq!(calls.get(), 10);
+        assert_eq!(format!("{:?}", list.take(2)), "[..]");
+
+        let list = LazyList::cons(1, LazyList::cons(2, LazyList::nil()));
+        assert_eq!(list.tail().and_then(LazyList::head), Some(&2));
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+    }
+
+    #[test]
+    fn test_long_drop() {
+        let list = LazyList::iterate(0u64, |n| n + 1);
+        assert_eq!(list.iter().nth(1_000_000), Some(&1_000_000));
+
+        let fibonacci = LazyList::iterate((0u64, 1u64), |&(a, b)| (b, a + b)).map(|&(a, _)| a);
+        assert!(fibonacci.take(8).iter().eq(&[0, 1, 1, 2, 3, 5, 8, 13]));
+    }
+}
@@ -0,0 +1,440 @@
+//! A least frequently used cache, built on buckets of [`DoublyList`]s.
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::Hash,
+};
+use std::collections::HashMap;
+
+use crate::doublylist::{DoublyList, NodeRef};
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+}
+
+/// The entries used equally often, most recently used first.
+struct Bucket<K, V> {
+    frequency: u64,
+    entries: DoublyList<Entry<K, V>>,
+}
+
+impl<K, V> Bucket<K, V> {
+    #[inline(always)]
+    const fn new(frequency: u64) -> Self {
+        Self {
+            frequency,
+            entries: DoublyList::new(),
+        }
+    }
+}
+
+/// Where an entry is linked into the cache.
+struct Location<K, V> {
+    bucket: NodeRef<Bucket<K, V>>,
+    entry: NodeRef<Entry<K, V>>,
+}
+
+impl<K, V> Copy for Location<K, V> {}
+
+impl<K, V> Clone for Location<K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Moves the entry at `location` into the bucket of the next frequency,
+/// and returns where it went.
+///
+/// # Safety
+/// `location` must refer to an entry linked into a bucket of `buckets`.
+unsafe fn touch<K, V>(
+    buckets: &mut DoublyList<Bucket<K, V>>,
+    location: Location<K, V>,
+) -> Location<K, V> {
+    let bucket = buckets.get_mut(location.bucket);
+    let frequency = bucket.frequency.saturating_add(1);
+    let entry = bucket.entries.remove_node(location.entry);
+    let emptied = bucket.entries.is_empty();
+
+    let next = match buckets.next_ref(location.bucket) {
+        Some(next) if buckets.get(next).frequency == frequency => next,
+        _ => buckets.insert_after(location.bucket, Bucket::new(frequency)),
+    };
+
+    let entry = buckets.get_mut(next).entries.push_front_node_ref(entry);
+
+    if emptied {
+        buckets.remove(location.bucket);
+    }
+
+    Location {
+        bucket: next,
+        entry,
+    }
+}
+
+/// Removes the location of `entry` from `locations`, looking it up by `key`.
+///
+/// A key whose `Hash` or `Eq` disagrees with its clone may be stored under another hash,
+/// or match the location of another entry.
+/// Then the location found is put back, and the locations are searched for `entry`,
+/// so no location outlives the entry it refers to.
+fn remove_location<K: Hash + Eq, V>(
+    locations: &mut HashMap<K, Location<K, V>>,
+    key: &K,
+    entry: NodeRef<Entry<K, V>>,
+) {
+    match locations.remove_entry(key) {
+        Some((_, location)) if location.entry == entry => return,
+        Some((other, location)) => {
+            locations.insert(other, location);
+        }
+        None => {}
+    }
+
+    locations.retain(|_, location| location.entry != entry);
+}
+
+/*
+ * The buckets are sorted by frequency, and only exist while they hold entries.
+ * A used entry moves from its bucket to the front of the bucket right after it,
+ * which is created if it doesn't hold the next frequency yet.
+ * The entry to evict is the back entry of the front bucket:
+ * among the least frequently used entries, the one used longest ago.
+ */
+/// A cache holding up to a fixed number of entries,
+/// that evicts the least frequently used entry to make room for a new one.
+///
+/// Among entries used equally often, the one used longest ago is evicted first.
+/// Getting, putting and evicting an entry all take O(1).
+///
+/// A key whose `Hash`, `Eq` or `Clone` implementations disagree with each other
+/// leads to unspecified results, but never to undefined behavior.
+///
+/// # Examples
+/// ```
+/// # use lists::lfu::LfuCache;
+/// let mut cache = LfuCache::new(2);
+///
+/// cache.put("a", 1);
+/// cache.put("b", 2);
+/// assert_eq!(cache.get("a"), Some(&1));
+///
+/// // "b" was used least often, so it makes room for "c".
+/// cache.put("c", 3);
+/// assert_eq!(cache.get("b"), None);
+/// assert_eq!(cache.frequency("a"), Some(2));
+/// ```
+pub struct LfuCache<K, V> {
+    buckets: DoublyList<Bucket<K, V>>,
+    locations: HashMap<K, Location<K, V>>,
+    capacity: usize,
+}
+
+impl<K: Debug, V: Debug> Debug for LfuCache<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self
+            .buckets
+            .iter()
+            .flat_map(|bucket| bucket.entries.iter())
+            .map(|entry| (&entry.key, &entry.value));
+
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<K, V> LfuCache<K, V> {
+    /// Returns a new empty cache, holding up to `capacity` entries.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buckets: DoublyList::new(),
+            locations: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Returns the number of entries in the cache.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Returns the most entries the cache holds.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes all entries from the cache.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.locations.clear();
+        self.buckets.clear();
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> LfuCache<K, V> {
+    /// Returns the value of `key` without counting it as used.
+    #[inline]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.locations.get(key)?;
+
+        // The map only holds entries linked into their bucket.
+        unsafe {
+            let bucket = self.buckets.get(location.bucket);
+            Some(&bucket.entries.get(location.entry).value)
+        }
+    }
+
+    /// Returns how often `key` was used, counting the put that inserted it.
+    #[inline]
+    pub fn frequency<Q>(&self, key: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.locations.get(key)?;
+
+        // The map only holds buckets linked into the cache.
+        Some(unsafe { self.buckets.get(location.bucket) }.frequency)
+    }
+
+    /// Counts `key` as used, and returns its value.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Counts `key` as used, and returns a mutable reference to its value.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.locations.get_mut(key)?;
+
+        // The map only holds entries linked into their bucket.
+        unsafe {
+            *location = touch(&mut self.buckets, *location);
+
+            let bucket = self.buckets.get_mut(location.bucket);
+            Some(&mut bucket.entries.get_mut(location.entry).value)
+        }
+    }
+
+    /// Inserts `value` for `key`, and returns the value it replaces.
+    ///
+    /// Replacing a value counts `key` as used.
+    /// Inserting a new key into a full cache evicts an entry first,
+    /// see [`pop_lfu`](LfuCache::pop_lfu).
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+
+        if self.capacity == 0 {
+            return None;
+        }
+
+        if self.len() == self.capacity {
+            self.pop_lfu();
+        }
+
+        let bucket = match self.buckets.front_ref() {
+            // The handle was just taken from the list.
+            Some(front) if unsafe { self.buckets.get(front) }.frequency == 1 => front,
+            _ => self.buckets.push_front_ref(Bucket::new(1)),
+        };
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+        };
+
+        // The bucket is linked into the cache.
+        let entry = unsafe { self.buckets.get_mut(bucket) }
+            .entries
+            .push_front_ref(entry);
+
+        self.locations.insert(key, Location { bucket, entry });
+        None
+    }
+
+    /// Removes `key` from the cache, and returns its value.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let location = self.locations.remove(key)?;
+
+        // The map only held entries linked into their bucket.
+        unsafe {
+            let bucket = self.buckets.get_mut(location.bucket);
+            let entry = bucket.entries.remove(location.entry);
+
+            if bucket.entries.is_empty() {
+                self.buckets.remove(location.bucket);
+            }
+
+            Some(entry.value)
+        }
+    }
+
+    /// Removes the least frequently used entry,
+    /// or the one used longest ago among them, and returns it.
+    pub fn pop_lfu(&mut self) -> Option<(K, V)> {
+        let front = self.buckets.front_ref()?;
+
+        // Buckets only exist while they hold entries,
+        // and the entry stays linked until its location is gone.
+        unsafe {
+            let bucket = self.buckets.get_mut(front);
+            let back = bucket.entries.back_ref()?;
+            remove_location(&mut self.locations, &bucket.entries.get(back).key, back);
+
+            let entry = bucket.entries.remove(back);
+
+            if bucket.entries.is_empty() {
+                self.buckets.remove(front);
+            }
+
+            Some((entry.key, entry.value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        hash::Hasher,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// A key equal to every other key, that hashes differently every time.
+    #[derive(Clone, Debug)]
+    struct Fickle;
+
+    impl Hash for Fickle {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            static HASHES: AtomicU64 = AtomicU64::new(0);
+            HASHES.fetch_add(1, Ordering::Relaxed).hash(state);
+        }
+    }
+
+    impl PartialEq for Fickle {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Eq for Fickle {}
+
+    #[test]
+    fn test_fickle_key() {
+        let mut cache = LfuCache::new(4);
+
+        for n in 0..200 {
+            cache.put(Fickle, n);
+
+            for _ in 0..n % 5 {
+                cache.get(&Fickle);
+                cache.peek(&Fickle);
+                cache.frequency(&Fickle);
+            }
+
+            if n % 7 == 0 {
+                cache.remove(&Fickle);
+            }
+            if n % 3 == 0 {
+                cache.pop_lfu();
+            }
+        }
+
+        while cache.pop_lfu().is_some() {}
+        assert!(cache.is_empty());
+        assert!(cache.buckets.is_empty());
+    }
+
+    #[test]
+    fn test_against_model() {
+        // Every entry of the model is (key, value, frequency, last use).
+        let mut model: Vec<(u32, u32, u64, usize)> = Vec::new();
+        let mut cache = LfuCache::new(8);
+
+        for tick in 0..5000 {
+            let key = ((tick * 7919) % 23 % (tick % 17 + 1)) as u32;
+
+            if tick % 3 == 0 {
+                let replaced = model.iter().position(|entry| entry.0 == key);
+                let expected = match replaced {
+                    Some(i) => {
+                        model[i].2 += 1;
+                        model[i].3 = tick;
+                        Some(core::mem::replace(&mut model[i].1, tick as u32))
+                    }
+                    None => {
+                        if model.len() == 8 {
+                            let lfu = (0..model.len())
+                                .min_by_key(|&i| (model[i].2, model[i].3))
+                                .unwrap();
+                            model.remove(lfu);
+                        }
+                        model.push((key, tick as u32, 1, tick));
+                        None
+                    }
+                };
+
+                assert_eq!(cache.put(key, tick as u32), expected);
+            } else if tick % 11 == 0 {
+                let removed = model.iter().position(|entry| entry.0 == key);
+                let expected = removed.map(|i| model.remove(i).1);
+                assert_eq!(cache.remove(&key), expected);
+            } else {
+                let expected = model.iter_mut().find(|entry| entry.0 == key).map(|entry| {
+                    entry.2 += 1;
+                    entry.3 = tick;
+                    entry.1
+                });
+                assert_eq!(cache.get(&key).copied(), expected);
+            }
+
+            assert_eq!(cache.len(), model.len());
+        }
+
+        for (key, value, frequency, _) in &model {
+            assert_eq!(cache.peek(key), Some(value));
+            assert_eq!(cache.frequency(key), Some(*frequency));
+        }
+
+        while let Some((key, _)) = cache.pop_lfu() {
+            let lfu = (0..model.len())
+                .min_by_key(|&i| (model[i].2, model[i].3))
+                .unwrap();
+            assert_eq!(model.remove(lfu).0, key);
+        }
+
+        assert!(model.is_empty());
+        assert!(cache.buckets.is_empty());
+    }
+}
@@ -1,4 +1,96 @@
 //! This library implements a linked list, and a doubly linked list.
+//!
+//! # Features
+//! - `unstable`: enables APIs that need a nightly compiler,
+//!   like pushing items that unsize into the item type of the list,
+//!   or the `thin` module, with a list whose links stay thin for `dyn` items,
+//!   and lets lists be dropped after the data their items borrow, like `Vec`.
+//! - `arbitrary`: implements [`arbitrary::Arbitrary`] for both list types,
+//!   so they can be used as fuzzing inputs.
+//! - `borsh`: implements `BorshSerialize` and `BorshDeserialize` for both list types,
+//!   encoding them like a `Vec`, as a `u32` length followed by the items.
+//! - `quickcheck`: implements [`quickcheck::Arbitrary`] for both list types.
+//! - `rand`: adds `shuffle` and `choose` to both list types.
+//! - `rayon`: adds `par_sort` and `par_sort_by` to [`DoublyList`](doublylist::DoublyList),
+//!   sorting on the rayon thread pool.
+//! - `rkyv`: implements rkyv's `Archive`, `Serialize` and `Deserialize` for both list types,
+//!   archiving them as an `ArchivedVec` of their items.
+//! - `serde`: implements `Serialize` and `Deserialize` for both list types.
+//!   Deserializing in place into a [`DoublyList`](doublylist::DoublyList) reuses its nodes.
+//! - `stats`: keeps [`Stats`](stats::Stats) counters of the nodes linked into and unlinked from
+//!   every list, accessible with `stats()`.
+//! - `futures`: adds [`DoublyList::into_stream`](doublylist::DoublyList::into_stream)
+//!   and [`DoublyList::from_stream`](doublylist::DoublyList::from_stream).
+//! - `proptest`: adds the [`strategy`] module, with strategies generating lists.
+//! - `ffi`: lays out the nodes of a [`DoublyList`](doublylist::DoublyList) as `#[repr(C)]`,
+//!   so lists taken apart with `into_raw_parts` can be handed over FFI.
+//! - `hazard`: adds the [`hazard`] module, with a lock-free stack and queue that free popped nodes
+//!   with hazard pointers instead of epochs, bounding the pauses reclamation causes.
+//!   [`RcuList`](rcu::RcuList) then reclaims its old versions with hazard pointers as well,
+//!   so updating it no longer waits for readers.
+#![cfg_attr(
+    feature = "unstable",
+    feature(
+        unsize,
+        iter_advance_by,
+        trusted_len,
+        dropck_eyepatch,
+        strict_provenance_lints,
+        ptr_metadata,
+        try_trait_v2
+    )
+)]
+// Pointers are never turned into integers and back,
+// which keeps the crate working under strict provenance.
+#![cfg_attr(
+    feature = "unstable",
+    deny(fuzzy_provenance_casts, lossy_provenance_casts)
+)]
 
+pub mod adapters;
+pub mod algo;
+pub mod async_bounded;
+pub mod async_queue;
+pub mod bounded;
+pub mod builder;
+pub mod bytelist;
+pub mod collection;
+pub mod delay_queue;
 pub mod doublylist;
+pub mod expiring;
+pub mod fingertree;
+pub mod fixed;
+pub mod graph;
+#[cfg(feature = "hazard")]
+pub mod hazard;
+pub mod heapsize;
+pub mod hook;
+pub mod indexed;
+pub mod join;
+pub mod journal;
+pub mod keyed;
+pub mod lazy;
+pub mod lfu;
+pub mod linked_hash_map;
+pub mod linked_hash_set;
 pub mod list;
+pub mod piece_table;
+pub mod pool;
+pub mod raw;
+pub mod rcu;
+pub mod rope;
+mod slice_node;
+pub mod spsc;
+pub mod static_list;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+pub mod subscriber;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "unstable")]
+pub mod thin;
+pub mod timer;
+pub mod veclist;
+pub mod zipper;
@@ -0,0 +1,731 @@
+//! A hash map that remembers insertion order, threading its entries through a [`DoublyList`].
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::Hash,
+    iter::{FromIterator, FusedIterator},
+};
+use std::collections::HashMap;
+
+use crate::doublylist::{self, DoublyList, NodeRef};
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+}
+
+/// Removes the handle to `node` from `nodes`, looking it up by `key`.
+///
+/// A key whose `Hash` or `Eq` disagrees with its clone may be stored under another hash,
+/// or match the handle to another node.
+/// Then the handle found is put back, and the handles are searched for `node`,
+/// so no handle outlives the node it refers to.
+fn unmap<K: Hash + Eq, V>(
+    nodes: &mut HashMap<K, NodeRef<Entry<K, V>>>,
+    key: &K,
+    node: NodeRef<Entry<K, V>>,
+) {
+    match nodes.remove_entry(key) {
+        Some((_, found)) if found == node => return,
+        Some((other, found)) => {
+            nodes.insert(other, found);
+        }
+        None => {}
+    }
+
+    nodes.retain(|_, found| *found != node);
+}
+
+/// A hash map iterating over its entries in the order they were inserted.
+///
+/// The entries live in a [`DoublyList`], in order,
+/// and the hash map only keeps where every key is linked in.
+/// Getting, inserting and removing an entry take O(1), as does moving an entry
+/// to either end of the order with [`to_front`](LinkedHashMap::to_front)
+/// and [`to_back`](LinkedHashMap::to_back).
+///
+/// Replacing the value of a key keeps the entry where it is.
+///
+/// A key whose `Hash`, `Eq` or `Clone` implementations disagree with each other
+/// leads to unspecified results, but never to undefined behavior.
+///
+/// # Examples
+/// ```
+/// # use lists::linked_hash_map::LinkedHashMap;
+/// let mut map = LinkedHashMap::new();
+/// map.insert("c", 3);
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+///
+/// assert_eq!(map.insert("a", 10), Some(1));
+/// assert!(map.keys().eq(&["c", "a", "b"]));
+///
+/// map.to_back("c");
+/// assert_eq!(map.pop_front(), Some(("a", 10)));
+/// assert!(map.values().eq(&[2, 3]));
+/// ```
+pub struct LinkedHashMap<K, V> {
+    entries: DoublyList<Entry<K, V>>,
+    nodes: HashMap<K, NodeRef<Entry<K, V>>>,
+}
+
+impl<K, V> Default for LinkedHashMap<K, V> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for LinkedHashMap<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Clone for LinkedHashMap<K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Maps are equal if they hold equal entries, in the same order.
+impl<K: PartialEq, V: PartialEq> PartialEq for LinkedHashMap<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for LinkedHashMap<K, V> {}
+
+impl<K, V> LinkedHashMap<K, V> {
+    /// Returns a new empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: DoublyList::new(),
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Returns a new empty map, with room for `capacity` keys in its hash map.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: DoublyList::new(),
+            nodes: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all entries from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.entries.clear();
+    }
+
+    /// Returns the entry inserted first.
+    #[inline]
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.entries
+            .peek_front()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Returns the entry inserted last.
+    #[inline]
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.entries
+            .peek_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Returns an iterator over the entries, in order.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the entries, in order, with mutable references to the values.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            entries: self.entries.iter_mut(),
+        }
+    }
+
+    /// Returns an iterator over the keys, in order.
+    #[inline(always)]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    /// Returns an iterator over the values, in order.
+    #[inline(always)]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> LinkedHashMap<K, V> {
+    /// Returns `true` if the map holds `key`.
+    #[inline(always)]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.nodes.contains_key(key)
+    }
+
+    /// Returns the value of `key`.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = *self.nodes.get(key)?;
+
+        // The map only holds nodes linked into the entries.
+        Some(unsafe { &self.entries.get(node).value })
+    }
+
+    /// Returns a mutable reference to the value of `key`.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = *self.nodes.get(key)?;
+
+        // The map only holds nodes linked into the entries.
+        Some(unsafe { &mut self.entries.get_mut(node).value })
+    }
+
+    /// Inserts `value` for `key`, and returns the value it replaces.
+    ///
+    /// A new key is inserted at the back, a replaced one keeps its place.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+        };
+
+        let node = self.entries.push_back_ref(entry);
+        self.nodes.insert(key, node);
+        None
+    }
+
+    /// Inserts `value` for `key` at the front, and returns the value it replaces.
+    ///
+    /// A replaced key is moved to the front as well, without allocating a new entry.
+    pub fn insert_front(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&node) = self.nodes.get(&key) {
+            // The map only holds nodes linked into the entries.
+            let entry = unsafe {
+                self.entries.move_to_front(node);
+                self.entries.get_mut(node)
+            };
+            return Some(core::mem::replace(&mut entry.value, value));
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+        };
+
+        let node = self.entries.push_front_ref(entry);
+        self.nodes.insert(key, node);
+        None
+    }
+
+    /// Inserts `value` for `key` at the back, and returns the value it replaces.
+    ///
+    /// A replaced key is moved to the back as well, without allocating a new entry.
+    pub fn insert_back(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&node) = self.nodes.get(&key) {
+            // The map only holds nodes linked into the entries.
+            let entry = unsafe {
+                self.entries.move_to_back(node);
+                self.entries.get_mut(node)
+            };
+            return Some(core::mem::replace(&mut entry.value, value));
+        }
+
+        self.insert(key, value)
+    }
+
+    /// Removes `key` from the map, and returns its value.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes `key` from the map, and returns it with its value.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.nodes.remove(key)?;
+
+        // The map only held nodes linked into the entries.
+        let entry = unsafe { self.entries.remove(node) };
+        Some((entry.key, entry.value))
+    }
+
+    /// Removes the entry inserted first, and returns it.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        let node = self.entries.front_ref()?;
+
+        // The node stays linked until its handle is gone.
+        unsafe {
+            unmap(&mut self.nodes, &self.entries.get(node).key, node);
+            let entry = self.entries.remove(node);
+            Some((entry.key, entry.value))
+        }
+    }
+
+    /// Removes the entry inserted last, and returns it.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        let node = self.entries.back_ref()?;
+
+        // The node stays linked until its handle is gone.
+        unsafe {
+            unmap(&mut self.nodes, &self.entries.get(node).key, node);
+            let entry = self.entries.remove(node);
+            Some((entry.key, entry.value))
+        }
+    }
+
+    /// Moves the entry of `key` to the front, as if it was inserted first.
+    /// Returns `false` if the map does not hold `key`.
+    #[inline]
+    pub fn to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.nodes.get(key) {
+            None => false,
+            Some(&node) => {
+                // The map only holds nodes linked into the entries.
+                unsafe { self.entries.move_to_front(node) };
+                true
+            }
+        }
+    }
+
+    /// Moves the entry of `key` to the back, as if it was inserted last.
+    /// Returns `false` if the map does not hold `key`.
+    #[inline]
+    pub fn to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.nodes.get(key) {
+            None => false,
+            Some(&node) => {
+                // The map only holds nodes linked into the entries.
+                unsafe { self.entries.move_to_back(node) };
+                true
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Extend<(K, V)> for LinkedHashMap<K, V> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> FromIterator<(K, V)> for LinkedHashMap<K, V> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LinkedHashMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut LinkedHashMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> IntoIterator for LinkedHashMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            entries: self.entries.into_iter(),
+        }
+    }
+}
+
+/// An iterator over the entries of a [`LinkedHashMap`], in order.
+pub struct Iter<'a, K, V> {
+    entries: doublylist::Iter<'a, Entry<K, V>>,
+}
+
+impl<K, V> Copy for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the entries of a [`LinkedHashMap`], in order,
+/// with mutable references to the values.
+pub struct IterMut<'a, K, V> {
+    entries: doublylist::IterMut<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next()
+            .map(|entry| (&entry.key, &mut entry.value))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IterMut<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|entry| (&entry.key, &mut entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+/// An iterator moving the entries out of a [`LinkedHashMap`], in order.
+pub struct IntoIter<K, V> {
+    entries: doublylist::IntoIter<Entry<K, V>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| (entry.key, entry.value))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|entry| (entry.key, entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+/// An iterator over the keys of a [`LinkedHashMap`], in order.
+pub struct Keys<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<K, V> Copy for Keys<'_, K, V> {}
+
+impl<K, V> Clone for Keys<'_, K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(key, _)| key)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of a [`LinkedHashMap`], in order.
+pub struct Values<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<K, V> Copy for Values<'_, K, V> {}
+
+impl<K, V> Clone for Values<'_, K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, value)| value)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        hash::Hasher,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// A key equal to every other key, that hashes differently every time.
+    #[derive(Clone, Debug)]
+    struct Fickle;
+
+    impl Hash for Fickle {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            static HASHES: AtomicU64 = AtomicU64::new(0);
+            HASHES.fetch_add(1, Ordering::Relaxed).hash(state);
+        }
+    }
+
+    impl PartialEq for Fickle {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Eq for Fickle {}
+
+    #[test]
+    fn test_fickle_key() {
+        let mut map = LinkedHashMap::new();
+
+        for n in 0..200 {
+            match n % 5 {
+                0 => map.insert_front(Fickle, n),
+                1 => map.insert_back(Fickle, n),
+                _ => map.insert(Fickle, n),
+            };
+
+            for _ in 0..n % 4 {
+                map.get(&Fickle);
+                map.get_mut(&Fickle);
+                map.to_front(&Fickle);
+                map.to_back(&Fickle);
+            }
+
+            if n % 7 == 0 {
+                map.remove(&Fickle);
+            }
+            if n % 3 == 0 {
+                map.pop_front();
+                map.pop_back();
+            }
+        }
+
+        while map.pop_front().is_some() {}
+        assert!(map.is_empty());
+        assert!(map.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_against_model() {
+        let mut map = LinkedHashMap::new();
+        let mut model: Vec<(u32, u32)> = Vec::new();
+
+        for n in 0..3000u32 {
+            let key = n * 7919 % 31;
+
+            match n % 7 {
+                0..=2 => {
+                    let expected = match model.iter_mut().find(|entry| entry.0 == key) {
+                        Some(entry) => Some(core::mem::replace(&mut entry.1, n)),
+                        None => {
+                            model.push((key, n));
+                            None
+                        }
+                    };
+                    assert_eq!(map.insert(key, n), expected);
+                }
+                3 => {
+                    let removed = model.iter().position(|entry| entry.0 == key);
+                    assert_eq!(map.remove(&key), removed.map(|i| model.remove(i).1));
+                }
+                4 => {
+                    let moved = model.iter().position(|entry| entry.0 == key);
+                    assert_eq!(map.to_front(&key), moved.is_some());
+                    if let Some(i) = moved {
+                        let entry = model.remove(i);
+                        model.insert(0, entry);
+                    }
+                }
+                5 => {
+                    let moved = model.iter().position(|entry| entry.0 == key);
+                    assert_eq!(map.to_back(&key), moved.is_some());
+                    if let Some(i) = moved {
+                        let entry = model.remove(i);
+                        model.push(entry);
+                    }
+                }
+                _ if n % 2 == 0 => {
+                    let expected = (!model.is_empty()).then(|| model.remove(0));
+                    assert_eq!(map.pop_front(), expected);
+                }
+                _ => assert_eq!(map.pop_back(), model.pop()),
+            }
+
+            assert_eq!(map.len(), model.len());
+            assert_eq!(
+                map.get(&key),
+                model.iter().find(|e| e.0 == key).map(|e| &e.1)
+            );
+        }
+
+        assert!(map.iter().map(|(&k, &v)| (k, v)).eq(model.iter().copied()));
+        assert!(map
+            .iter()
+            .rev()
+            .map(|(&k, &v)| (k, v))
+            .eq(model.iter().rev().copied()));
+
+        for (_, value) in &mut map {
+            *value += 1;
+        }
+        assert_eq!(map.clone(), map);
+        assert!(map.into_iter().eq(model.iter().map(|&(k, v)| (k, v + 1))));
+    }
+}
@@ -0,0 +1,484 @@
+//! A hash set that remembers insertion order, built on a [`LinkedHashMap`].
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::Hash,
+    iter::{FromIterator, FusedIterator},
+};
+
+use crate::linked_hash_map::{self, Keys, LinkedHashMap};
+
+/// A hash set iterating over its items in the order they were inserted.
+///
+/// Inserting, removing and looking up an item take O(1).
+/// Inserting an item the set already holds keeps it where it is.
+/// The set operations yield their items in order too:
+/// first by the order of the set they come from, and then by the order within it.
+///
+/// An item whose `Hash`, `Eq` or `Clone` implementations disagree with each other
+/// leads to unspecified results, but never to undefined behavior.
+///
+/// # Examples
+/// ```
+/// # use lists::linked_hash_set::LinkedHashSet;
+/// let a = [3, 1, 4, 5].iter().copied().collect::<LinkedHashSet<u32>>();
+/// let b = [5, 9, 2, 1].iter().copied().collect::<LinkedHashSet<u32>>();
+///
+/// assert!(a.union(&b).eq(&[3, 1, 4, 5, 9, 2]));
+/// assert!(a.difference(&b).eq(&[3, 4]));
+/// assert!(b.difference(&a).eq(&[9, 2]));
+/// ```
+pub struct LinkedHashSet<T> {
+    map: LinkedHashMap<T, ()>,
+}
+
+impl<T> Default for LinkedHashSet<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for LinkedHashSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Hash + Eq + Clone> Clone for LinkedHashSet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+/// Sets are equal if they hold equal items, in the same order.
+impl<T: PartialEq> PartialEq for LinkedHashSet<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T: Eq> Eq for LinkedHashSet<T> {}
+
+impl<T> LinkedHashSet<T> {
+    /// Returns a new empty set.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            map: LinkedHashMap::new(),
+        }
+    }
+
+    /// Returns a new empty set, with room for `capacity` items in its hash map.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            map: LinkedHashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of items in the set.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all items from the set.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the front item, which was inserted first unless it was moved.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.map.front().map(|(item, _)| item)
+    }
+
+    /// Returns the back item, which was inserted last unless it was moved.
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.map.back().map(|(item, _)| item)
+    }
+
+    /// Returns an iterator over the items, in order.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            keys: self.map.keys(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> LinkedHashSet<T> {
+    /// Returns `true` if the set holds `item`.
+    #[inline(always)]
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(item)
+    }
+
+    /// Inserts `item` at the back, unless the set already holds it.
+    /// Returns `true` if `item` was inserted.
+    #[inline]
+    pub fn insert(&mut self, item: T) -> bool {
+        if self.contains(&item) {
+            return false;
+        }
+
+        self.map.insert(item, ());
+        true
+    }
+
+    /// Removes `item` from the set.
+    /// Returns `true` if the set held it.
+    #[inline]
+    pub fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(item).is_some()
+    }
+
+    /// Removes the item equal to `item` from the set, and returns it.
+    #[inline]
+    pub fn take<Q>(&mut self, item: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove_entry(item).map(|(item, _)| item)
+    }
+
+    /// Removes the front item, and returns it.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.map.pop_front().map(|(item, _)| item)
+    }
+
+    /// Removes the back item, and returns it.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.map.pop_back().map(|(item, _)| item)
+    }
+
+    /// Moves `item` to the front, as if it was inserted first.
+    /// Returns `false` if the set does not hold `item`.
+    #[inline(always)]
+    pub fn to_front<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.to_front(item)
+    }
+
+    /// Moves `item` to the back, as if it was inserted last.
+    /// Returns `false` if the set does not hold `item`.
+    #[inline(always)]
+    pub fn to_back<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.to_back(item)
+    }
+
+    /// Returns an iterator over the items of `self`,
+    /// followed by the items of `other` that `self` does not hold.
+    #[inline(always)]
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, T> {
+        Union {
+            first: self.iter(),
+            second: other.difference(self),
+        }
+    }
+
+    /// Returns an iterator over the items of `self` that `other` does not hold.
+    #[inline(always)]
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, T> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Extend<T> for LinkedHashSet<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> FromIterator<T> for LinkedHashSet<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedHashSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for LinkedHashSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            entries: self.map.into_iter(),
+        }
+    }
+}
+
+/// An iterator over the items of a [`LinkedHashSet`], in order.
+pub struct Iter<'a, T> {
+    keys: Keys<'a, T, ()>,
+}
+
+impl<T> Copy for Iter<'_, T> {}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys.next()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.keys.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator moving the items out of a [`LinkedHashSet`], in order.
+pub struct IntoIter<T> {
+    entries: linked_hash_map::IntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(item, _)| item)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(|(item, _)| item)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// An iterator over the items of one [`LinkedHashSet`] that another one does not hold,
+/// returned by [`LinkedHashSet::difference`].
+pub struct Difference<'a, T> {
+    iter: Iter<'a, T>,
+    other: &'a LinkedHashSet<T>,
+}
+
+impl<T> Clone for Difference<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter,
+            other: self.other,
+        }
+    }
+}
+
+impl<'a, T: Hash + Eq + Clone> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|item| !other.contains(*item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<T: Hash + Eq + Clone> FusedIterator for Difference<'_, T> {}
+
+/// An iterator over the items of two [`LinkedHashSet`]s,
+/// returned by [`LinkedHashSet::union`].
+pub struct Union<'a, T> {
+    first: Iter<'a, T>,
+    second: Difference<'a, T>,
+}
+
+impl<T> Clone for Union<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first,
+            second: self.second.clone(),
+        }
+    }
+}
+
+impl<'a, T: Hash + Eq + Clone> Iterator for Union<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len();
+        let (_, upper) = self.second.size_hint();
+        (len, upper.and_then(|upper| upper.checked_add(len)))
+    }
+}
+
+impl<T: Hash + Eq + Clone> FusedIterator for Union<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        hash::Hasher,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// An item equal to every other item, that hashes differently every time.
+    #[derive(Clone, Debug)]
+    struct Fickle;
+
+    impl Hash for Fickle {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            static HASHES: AtomicU64 = AtomicU64::new(0);
+            HASHES.fetch_add(1, Ordering::Relaxed).hash(state);
+        }
+    }
+
+    impl PartialEq for Fickle {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Eq for Fickle {}
+
+    #[test]
+    fn test_fickle_item() {
+        let mut set = LinkedHashSet::new();
+        let other = core::iter::repeat_n(Fickle, 8).collect::<LinkedHashSet<_>>();
+
+        for n in 0..200 {
+            set.insert(Fickle);
+
+            for _ in 0..n % 4 {
+                set.contains(&Fickle);
+                set.to_front(&Fickle);
+                set.to_back(&Fickle);
+            }
+
+            if n % 7 == 0 {
+                set.take(&Fickle);
+            }
+            if n % 3 == 0 {
+                set.pop_front();
+                set.pop_back();
+            }
+            if n % 50 == 0 {
+                set.union(&other).count();
+                set.difference(&other).count();
+            }
+        }
+
+        while set.pop_back().is_some() {}
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_order() {
+        let mut set = LinkedHashSet::new();
+
+        for word in ["b", "a", "c", "a", "d"].iter() {
+            set.insert(word.to_string());
+        }
+
+        assert!(set.iter().eq(["b", "a", "c", "d"].iter()));
+        assert!(!set.insert("b".to_string()));
+        assert!(set.to_back("b"));
+        assert!(set.remove("c"));
+        assert!(!set.remove("c"));
+        assert_eq!(set.take("a"), Some("a".to_string()));
+        assert!(set.iter().eq(["d", "b"].iter()));
+
+        let other = ["e", "b", "f"].iter().map(|s| s.to_string()).collect();
+        assert!(set.union(&other).eq(["d", "b", "e", "f"].iter()));
+        assert!(other.union(&set).eq(["e", "b", "f", "d"].iter()));
+        assert!(other.difference(&set).eq(["e", "f"].iter()));
+        assert_eq!(set.union(&other).size_hint(), (2, Some(5)));
+
+        assert_eq!(set.pop_front().as_deref(), Some("d"));
+        assert!(set.into_iter().eq(Some("b".to_string())));
+    }
+}
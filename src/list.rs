@@ -1,4 +1,21 @@
-use core::iter::{FromIterator, FusedIterator};
+use core::{
+    alloc::Layout,
+    borrow::Borrow,
+    fmt::{self, Debug, Display},
+    iter::{FromIterator, FusedIterator},
+    mem,
+    ops::Add,
+    ptr,
+};
+use std::collections::VecDeque;
+
+#[cfg(feature = "stats")]
+use crate::stats::Stats;
+use crate::{
+    doublylist::DoublyList,
+    join::Join,
+    slice_node::{self, SliceNode},
+};
 
 /// The type used to link to another Node.
 ///
@@ -31,10 +48,67 @@ impl<T, U: ?Sized> Node<T, U> {
     }
 }
 
+// Only the links differ from the slice nodes of the `doublylist` module.
+unsafe impl<T> SliceNode<T> for Node<[T]> {
+    const HEADER: Layout = Layout::new::<Node<[T; 0], [T]>>();
+    const ITEMS: usize = mem::offset_of!(Node<[T; 0], [T]>, item);
+
+    #[inline(always)]
+    fn from_raw_parts(ptr: *mut u8, len: usize) -> *mut Self {
+        ptr::slice_from_raw_parts_mut(ptr.cast::<T>(), len) as *mut Self
+    }
+
+    #[inline(always)]
+    unsafe fn init_links(node: *mut Self) {
+        ptr::addr_of_mut!((*node).next).write(None);
+    }
+}
+
+impl<T> Node<[T]> {
+    /// Returns a new boxed node, holding the items of `items`.
+    ///
+    /// The items are moved into the node, without being cloned.
+    #[inline]
+    pub fn from_vec(items: Vec<T>) -> Box<Self> {
+        slice_node::from_vec(items)
+    }
+
+    /// Returns a new boxed node, holding the items of `items`.
+    ///
+    /// The items are moved into the node, without being cloned.
+    #[inline]
+    pub fn from_boxed_slice(items: Box<[T]>) -> Box<Self> {
+        Self::from_vec(items.into_vec())
+    }
+}
+
+impl<T: Clone> Node<[T]> {
+    /// Returns a new boxed node, holding clones of `items`.
+    #[inline]
+    pub fn boxed_slice(items: &[T]) -> Box<Self> {
+        slice_node::boxed_slice(items)
+    }
+}
+
+impl Node<str> {
+    /// Returns a new boxed node, holding a copy of `text`.
+    pub fn boxed_str(text: &str) -> Box<Self> {
+        let node = Node::<[u8]>::boxed_slice(text.as_bytes());
+
+        // A `Node<[u8]>` has the same layout as a `Node<str>`,
+        // and the bytes came from a `str`.
+        unsafe { Box::from_raw(Box::into_raw(node) as *mut Self) }
+    }
+}
+
 /// A list of nodes.
 pub struct List<T: ?Sized> {
     /// Hold just the head of the list
     head: Link<T>,
+
+    /// The allocation counters of the list
+    #[cfg(feature = "stats")]
+    stats: Stats,
 }
 
 impl<T: ?Sized> Default for List<T> {
@@ -44,6 +118,108 @@ impl<T: ?Sized> Default for List<T> {
     }
 }
 
+impl<T: Clone> Clone for List<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::from_iter_ordered(self.iter().cloned())
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for List<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for List<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other)
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for List<T> {}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for List<T> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.iter().eq(other)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<&[U]> for List<T> {
+    #[inline(always)]
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Vec<U>> for List<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for List<T> {
+    #[inline(always)]
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: ?Sized + PartialEq<U>, U: ?Sized> PartialEq<DoublyList<U>> for List<T> {
+    #[inline]
+    fn eq(&self, other: &DoublyList<U>) -> bool {
+        self.iter().eq(other)
+    }
+}
+
+impl<T> From<DoublyList<T>> for List<T> {
+    /// Moves the items of `list` into a new list, in the same order.
+    #[inline]
+    fn from(list: DoublyList<T>) -> Self {
+        Self::from_iter_ordered(list)
+    }
+}
+
+impl<T: ?Sized> Add for List<T> {
+    type Output = Self;
+
+    /// Returns the items of `self` followed by the items of `rhs`,
+    /// linking the head of `rhs` after the last node of `self`.
+    ///
+    /// This walks `self` once. With the `stats` feature,
+    /// `rhs` is walked as well, to count the nodes that move over.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let a = (0..3).rev().collect::<List<u32>>();
+    /// let b = (3..5).rev().collect::<List<u32>>();
+    ///
+    /// assert_eq!(a + b, [0, 1, 2, 3, 4]);
+    /// ```
+    fn add(mut self, mut rhs: Self) -> Self {
+        #[cfg(feature = "stats")]
+        {
+            let count = rhs.iter().count();
+            rhs.record_unlink(count);
+            self.record_link(count);
+        }
+
+        let mut cursor = &mut self.head;
+
+        while let Some(node) = cursor {
+            cursor = &mut node.next;
+        }
+
+        *cursor = rhs.head.take();
+        self
+    }
+}
+
 impl<T: ?Sized> List<T> {
     /// Returns a new empty list.
     /// # Examples
@@ -55,12 +231,49 @@ impl<T: ?Sized> List<T> {
     /// ```
     #[inline(always)]
     pub const fn new() -> Self {
-        Self { head: None }
+        Self {
+            head: None,
+            #[cfg(feature = "stats")]
+            stats: Stats::new(),
+        }
     }
 
-    #[inline(always)]
+    // An iterative clear,
+    // because the default drop behaviour of nodes is recursive!
+    #[inline]
     pub fn clear(&mut self) {
-        *self = Self::new();
+        let mut cursor = self.head.take();
+        let mut count = 0;
+
+        while let Some(mut node) = cursor {
+            cursor = node.next.take();
+            count += 1;
+        }
+
+        self.record_unlink(count);
+    }
+
+    /// Returns the link counters of the list.
+    #[cfg(feature = "stats")]
+    #[inline(always)]
+    pub const fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    #[inline(always)]
+    fn record_link(&mut self, count: usize) {
+        #[cfg(feature = "stats")]
+        self.stats.record_link(count);
+        #[cfg(not(feature = "stats"))]
+        let _ = count;
+    }
+
+    #[inline(always)]
+    fn record_unlink(&mut self, count: usize) {
+        #[cfg(feature = "stats")]
+        self.stats.record_unlink(count);
+        #[cfg(not(feature = "stats"))]
+        let _ = count;
     }
 
     /// Returns `true` if the list is empty, false otherwise.
@@ -69,20 +282,47 @@ impl<T: ?Sized> List<T> {
         self.head.is_none()
     }
 
+    /// Returns the number of bytes allocated for the nodes of the list,
+    /// including the link of every node.
+    ///
+    /// Memory owned by the items themselves is not included,
+    /// see [`HeapSize`](crate::heapsize::HeapSize) for that.
+    pub fn memory_usage(&self) -> usize {
+        self.nodes().map(mem::size_of_val).sum()
+    }
+
+    /// Returns an iterator over the nodes of the list.
+    #[inline(always)]
+    fn nodes(&self) -> impl Iterator<Item = &Node<T>> {
+        core::iter::successors(self.head.as_deref(), |node| node.next.as_deref())
+    }
+
     #[inline(always)]
     pub fn push_node(&mut self, mut node: Box<Node<T>>) {
         node.next = self.head.take();
         self.head = Some(node);
+        self.record_link(1);
     }
 
     #[inline(always)]
     pub fn pop_node(&mut self) -> Option<Box<Node<T>>> {
         self.head.take().map(|mut node| {
             self.head = node.next.take();
+            self.record_unlink(1);
             node
         })
     }
 
+    /// Turns the list into an iterator over its nodes, from the head on.
+    ///
+    /// The nodes can be pushed into another list without reallocating,
+    /// which is what the `Extend` and `FromIterator` implementations
+    /// over `Box<Node<T>>` do.
+    #[inline(always)]
+    pub fn into_nodes(self) -> IntoNodes<T> {
+        IntoNodes { inner: self }
+    }
+
     /// Returns a reference to the head of the list.
     /// # Examples
     /// ```
@@ -94,7 +334,7 @@ impl<T: ?Sized> List<T> {
     /// ```
     #[inline(always)]
     pub fn peek(&self) -> Option<&T> {
-        self.head.as_ref().map(|ref node| &node.item)
+        self.head.as_ref().map(|node| &node.item)
     }
 
     /// Returns a mutable reference to the head of the list.
@@ -118,53 +358,264 @@ impl<T: ?Sized> List<T> {
     /// Returns an iterator over the list,
     /// that yields references to the elements in the list.
     #[inline(always)]
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            inner: self.head.as_ref().map(|node| &**node),
-        }
+    pub const fn iter(&self) -> Iter<'_, T> {
+        let inner = match &self.head {
+            Some(node) => Some(&**node),
+            None => None,
+        };
+
+        Iter { inner }
     }
 
     /// Returns iterator over the list,
     /// that yields mutable references to the elements in the list.
     #[inline(always)]
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
-            inner: self.head.as_mut().map(|node| &mut **node),
+            inner: self.head.as_deref_mut(),
         }
     }
 
-    /// Splits the list at the first element after the closure.
+    /// Returns an adaptor that displays the items in the list,
+    /// separated by `separator`.
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let list = (1..4).collect::<List<_>>();
+    ///
+    /// assert_eq!(list.join(", ").to_string(), "3, 2, 1");
+    /// assert_eq!(format!("[{:02}]", list.join("|")), "[03|02|01]");
+    /// ```
+    #[inline(always)]
+    pub const fn join<'a>(&'a self, separator: &'a str) -> Join<'a, Iter<'a, T>>
+    where
+        T: Display,
+    {
+        Join::new(self.iter(), separator)
+    }
+
+    /// Reverses the order of the nodes in the list, without reallocating them.
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let mut list = (0..3).collect::<List<_>>();
+    /// list.reverse();
+    ///
+    /// assert_eq!(list.pop(), Some(0));
+    /// assert_eq!(list.pop(), Some(1));
+    /// assert_eq!(list.pop(), Some(2));
+    /// ```
+    #[inline]
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut cursor = self.head.take();
+
+        while let Some(mut node) = cursor {
+            cursor = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+
+        self.head = reversed;
+    }
+
+    /// Splits the list right after the first item for which `splitter` returns `true`.
+    ///
+    /// The list keeps the items up to and including that item,
+    /// and the returned list holds the items after it, in the same order.
+    /// Returns `None` if `splitter` returns `false` for every item.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let mut list = (0..5).rev().collect::<List<u32>>();
+    /// let rest = list.split_after(|&n| n == 2);
+    ///
+    /// assert_eq!(list, [0, 1, 2]);
+    /// assert_eq!(rest.unwrap(), [3, 4]);
+    /// ```
     pub fn split_after<F>(&mut self, mut splitter: F) -> Option<List<T>>
     where
         F: FnMut(&T) -> bool,
     {
         let mut iter = self.iter_mut();
 
-        loop {
-            match iter.peek() {
-                Some(item) => {
-                    if splitter(item) {
-                        break;
-                    }
-                }
-                None => break,
+        while let Some(item) = iter.peek() {
+            if splitter(item) {
+                break;
             }
 
-            match iter.next() {
-                Some(_) => continue,
-                None => break,
-            }
+            iter.next();
         }
 
         let IterMut { mut inner } = iter;
 
-        match inner.take() {
-            Some(node) => Some(List {
-                head: node.next.take(),
-            }),
-            None => None,
+        let mut splitted = List::new();
+        splitted.head = inner.take()?.next.take();
+
+        #[cfg(feature = "stats")]
+        {
+            let count = splitted.iter().count();
+            self.record_unlink(count);
+            splitted.record_link(count);
         }
+
+        Some(splitted)
     }
+
+    /// Splits the list right before the first item for which `splitter` returns `true`.
+    ///
+    /// The list keeps the items before that item,
+    /// and the returned list starts with it, followed by the items after it, in the same order.
+    /// Returns `None` if `splitter` returns `false` for every item.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let mut list = (0..5).rev().collect::<List<u32>>();
+    /// let rest = list.split_before(|&n| n == 2);
+    ///
+    /// assert_eq!(list, [0, 1]);
+    /// assert_eq!(rest.unwrap(), [2, 3, 4]);
+    /// ```
+    pub fn split_before<F>(&mut self, mut splitter: F) -> Option<List<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut cursor = &mut self.head;
+
+        while cursor.as_ref().is_some_and(|node| !splitter(&node.item)) {
+            if let Some(node) = cursor {
+                cursor = &mut node.next;
+            }
+        }
+
+        let mut splitted = List::new();
+        splitted.head = Some(cursor.take()?);
+
+        #[cfg(feature = "stats")]
+        {
+            let count = splitted.iter().count();
+            self.record_unlink(count);
+            splitted.record_link(count);
+        }
+
+        Some(splitted)
+    }
+
+    /// Returns `true` if the items are sorted in ascending order, from the head on.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let list = (0..5).rev().collect::<List<u32>>();
+    ///
+    /// assert!(list.is_sorted());
+    /// assert!(!list.is_sorted_by(|a, b| a > b));
+    /// ```
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Returns `true` if `compare` returns `true` for every pair of adjacent items,
+    /// like [`slice::is_sorted_by`].
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        is_sorted_by(self.iter(), |a, b| compare(a, b))
+    }
+
+    /// Returns `true` if the keys `f` returns for the items are sorted in ascending order.
+    #[inline]
+    pub fn is_sorted_by_key<K, F>(&self, f: F) -> bool
+    where
+        K: PartialOrd,
+        F: FnMut(&T) -> K,
+    {
+        is_sorted_by(self.iter().map(f), |a, b| a <= b)
+    }
+
+    /// Returns `true` if the items from the head on are equal to the items of `prefix`,
+    /// which can be another list, a slice, or any iterator of items or references to them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let list = (0..4).rev().collect::<List<u32>>();
+    ///
+    /// assert!(list.starts_with(&[0, 1]));
+    /// assert!(list.ends_with(2..4));
+    /// ```
+    pub fn starts_with<I>(&self, prefix: I) -> bool
+    where
+        T: PartialEq,
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let mut items = self.iter();
+
+        prefix
+            .into_iter()
+            .all(|x| items.next().is_some_and(|item| item == x.borrow()))
+    }
+
+    /// Returns `true` if the last items of the list are equal to the items of `suffix`.
+    ///
+    /// The list can only be walked from its head, so the items of `suffix` are collected first,
+    /// and the list is walked once, keeping its last items in a window as long as `suffix`.
+    pub fn ends_with<I>(&self, suffix: I) -> bool
+    where
+        T: PartialEq,
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let suffix = suffix.into_iter().collect::<Vec<_>>();
+
+        if suffix.is_empty() {
+            return true;
+        }
+
+        let mut window = VecDeque::with_capacity(suffix.len());
+
+        for item in self.iter() {
+            if window.len() == suffix.len() {
+                window.pop_front();
+            }
+
+            window.push_back(item);
+        }
+
+        window.len() == suffix.len()
+            && window
+                .iter()
+                .zip(&suffix)
+                .all(|(&item, x)| item == x.borrow())
+    }
+}
+
+/// Returns `true` if `compare` returns `true` for every pair of adjacent items of `iter`.
+///
+/// Every item is only taken once, and carried forward to be compared with the next.
+fn is_sorted_by<I, F>(mut iter: I, mut compare: F) -> bool
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    let mut previous = match iter.next() {
+        Some(item) => item,
+        None => return true,
+    };
+
+    iter.all(|item| {
+        let sorted = compare(&previous, &item);
+        previous = item;
+        sorted
+    })
 }
 
 impl<T> List<T> {
@@ -202,17 +653,160 @@ impl<T> List<T> {
     pub fn pop(&mut self) -> Option<T> {
         self.pop_node().map(|node| node.item)
     }
+
+    /// Returns a list holding the items of `iter` in the order they are yielded,
+    /// with the first item at the head.
+    ///
+    /// Unlike collecting, which pushes every item to the front,
+    /// this links every node after the previous one through a cursor on the tail.
+    /// # Examples
+    /// ```
+    /// # use lists::list::List;
+    /// let list = List::from_iter_ordered(0..3);
+    ///
+    /// assert_eq!(list, [0, 1, 2]);
+    /// assert_eq!((0..3).collect::<List<_>>(), [2, 1, 0]);
+    /// ```
+    pub fn from_iter_ordered<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let items = iter.into_iter().map(Ok::<T, core::convert::Infallible>);
+
+        match Self::try_from_iter_ordered(items) {
+            Ok(list) => list,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [`List::from_iter_ordered`], but stops at the first error,
+    /// dropping the items linked up so far.
+    fn try_from_iter_ordered<E, I>(iter: I) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<T, E>>,
+    {
+        let mut list = Self::new();
+        let mut tail = &mut list.head;
+        let mut count = 0;
+        let mut result = Ok(());
+
+        for item in iter {
+            match item {
+                Ok(item) => {
+                    tail = &mut tail.insert(Node::boxed(item)).next;
+                    count += 1;
+                }
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+
+        list.record_link(count);
+        result.map(|()| list)
+    }
+}
+
+/// A builder for a list whose nodes each hold a different concrete type,
+/// linked through the common type `U`.
+///
+/// Every node is a `Node<T, U>` for some concrete `T`,
+/// which coerces into a `Node<U>` when `T` unsizes into `U`,
+/// for example when `U` is a trait object that `T` implements.
+/// # Examples
+/// ```
+/// # use lists::list::{Chain, List, Node};
+/// use core::fmt::Display;
+///
+/// let list: List<dyn Display> = Chain::<dyn Display>::new()
+///     .link(Node::boxed(1))
+///     .link(Node::boxed("two"))
+///     .link(Node::boxed(3.5))
+///     .finish();
+///
+/// let strings = list.iter().map(|item| item.to_string()).collect::<Vec<_>>();
+/// assert_eq!(strings, ["1", "two", "3.5"]);
+/// ```
+pub struct Chain<U: ?Sized> {
+    /// The nodes linked so far, last linked node first.
+    reversed: List<U>,
+}
+
+impl<U: ?Sized> Default for Chain<U> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: ?Sized> Chain<U> {
+    /// Returns a new empty chain.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            reversed: List::new(),
+        }
+    }
+
+    /// Links `node` to the end of the chain.
+    #[inline(always)]
+    pub fn link(mut self, node: Box<Node<U>>) -> Self {
+        self.reversed.push_node(node);
+        self
+    }
+
+    /// Returns the list owning the chain,
+    /// with the first linked node at its head.
+    #[inline(always)]
+    pub fn finish(mut self) -> List<U> {
+        self.reversed.reverse();
+        self.reversed
+    }
 }
 
-// An iterative drop,
-// because the default drop behaviour is recursive!
+#[cfg(feature = "rand")]
+impl<T: ?Sized> List<T> {
+    /// Shuffles the list, by relinking its nodes in a uniformly random order.
+    ///
+    /// The items stay where they are in memory.
+    pub fn shuffle<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+        use rand::seq::SliceRandom;
+
+        let mut nodes = Vec::new();
+
+        while let Some(node) = self.pop_node() {
+            nodes.push(node);
+        }
+
+        nodes.shuffle(rng);
+        self.extend(nodes);
+    }
+
+    /// Returns a uniformly random item, or `None` if the list is empty.
+    ///
+    /// Since a list does not know its length, this walks the entire list.
+    #[inline]
+    pub fn choose<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Option<&T> {
+        rand::seq::IteratorRandom::choose(self.iter(), rng)
+    }
+}
+
+#[cfg(not(feature = "unstable"))]
 impl<T: ?Sized> Drop for List<T> {
+    #[inline]
     fn drop(&mut self) {
-        let mut cursor = self.head.take();
+        self.clear();
+    }
+}
 
-        while let Some(mut node) = cursor {
-            cursor = node.next.take();
-        }
+// Dropping the list only drops the boxed nodes it owns,
+// so the items may hold references that already dangle.
+#[cfg(feature = "unstable")]
+unsafe impl<#[may_dangle] T: ?Sized> Drop for List<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
     }
 }
 
@@ -246,6 +840,32 @@ impl<T> IntoIterator for List<T> {
     }
 }
 
+/// Pushes every item to the front of the list,
+/// so the last item ends up at the head.
+impl<T> Extend<T> for List<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push(item)
+        }
+    }
+}
+
+/// Pushes a copy of every item to the front of the list,
+/// so the copy of the last item ends up at the head.
+impl<'a, T: Copy + 'a> Extend<&'a T> for List<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
 impl<T> FromIterator<T> for List<T> {
     #[inline]
     fn from_iter<I>(iter: I) -> Self
@@ -262,6 +882,172 @@ impl<T> FromIterator<T> for List<T> {
     }
 }
 
+/// Pushes every node to the front of the list,
+/// so the last node ends up at the head.
+impl<T: ?Sized> Extend<Box<Node<T>>> for List<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Box<Node<T>>>,
+    {
+        for node in iter {
+            self.push_node(node)
+        }
+    }
+}
+
+impl<T: ?Sized> FromIterator<Box<Node<T>>> for List<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Box<Node<T>>>,
+    {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for List<T> {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter::<T>()?.collect()
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter::<T>()?.collect()
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T: quickcheck::Arbitrary> quickcheck::Arbitrary for List<T> {
+    #[inline]
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Vec::<T>::arbitrary(g).into_iter().rev().collect()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let items = self.iter().cloned().collect::<Vec<_>>();
+        Box::new(
+            items
+                .shrink()
+                .map(|items| items.into_iter().rev().collect()),
+        )
+    }
+}
+
+/// Like a [`DoublyList`], a list is encoded as its length followed by its items.
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshSerialize> borsh::BorshSerialize for List<T> {
+    #[inline]
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        use core::convert::TryFrom;
+
+        // The length goes first, and a list doesn't keep it, so the list is walked twice.
+        let len =
+            u32::try_from(self.iter().count()).map_err(|_| std::io::ErrorKind::InvalidData)?;
+        len.serialize(writer)?;
+
+        for item in self {
+            item.serialize(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshDeserialize> borsh::BorshDeserialize for List<T> {
+    #[inline]
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let len = u32::deserialize_reader(reader)?;
+        List::try_from_iter_ordered((0..len).map(|_| T::deserialize_reader(reader)))
+    }
+}
+
+/// Like a [`DoublyList`], a list is archived as a contiguous sequence of its items.
+#[cfg(feature = "rkyv")]
+impl<T: rkyv::Archive> rkyv::Archive for List<T> {
+    type Archived = rkyv::vec::ArchivedVec<T::Archived>;
+    type Resolver = rkyv::vec::VecResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: rkyv::Place<Self::Archived>) {
+        rkyv::vec::ArchivedVec::resolve_from_len(self.iter().count(), resolver, out);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, S> rkyv::Serialize<S> for List<T>
+where
+    T: rkyv::Serialize<S>,
+    S: rkyv::rancor::Fallible + rkyv::ser::Allocator + rkyv::ser::Writer + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        // The archive needs to know the length up front, which a list doesn't keep.
+        let items = self.iter().collect::<Vec<_>>();
+        rkyv::vec::ArchivedVec::serialize_from_iter::<T, _, _>(items.into_iter(), serializer)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<T, D> rkyv::Deserialize<List<T>, D> for rkyv::vec::ArchivedVec<T::Archived>
+where
+    T: rkyv::Archive,
+    T::Archived: rkyv::Deserialize<T, D>,
+    D: rkyv::rancor::Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<List<T>, D::Error> {
+        List::try_from_iter_ordered(self.iter().map(|item| item.deserialize(deserializer)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: ?Sized + serde::Serialize> serde::Serialize for List<T> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for List<T> {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(SeqVisitor {
+            marker: core::marker::PhantomData,
+        })
+    }
+}
+
+/// Deserializes a sequence into a list, linking up the items in order.
+#[cfg(feature = "serde")]
+struct SeqVisitor<T> {
+    marker: core::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for SeqVisitor<T> {
+    type Value = List<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<List<T>, A::Error> {
+        List::try_from_iter_ordered(core::iter::from_fn(|| seq.next_element().transpose()))
+    }
+}
+
 /// An iterator over a list of nodes.
 pub struct Iter<'a, T: ?Sized> {
     inner: Option<&'a Node<T>>,
@@ -272,7 +1058,7 @@ impl<T: ?Sized> Copy for Iter<'_, T> {}
 impl<T: ?Sized> Clone for Iter<'_, T> {
     #[inline(always)]
     fn clone(&self) -> Self {
-        Iter { ..*self }
+        *self
     }
 }
 
@@ -288,8 +1074,8 @@ impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.take().map(|ref node| {
-            self.inner = node.next.as_ref().map(|node| &**node);
+        self.inner.take().map(|node| {
+            self.inner = node.next.as_deref();
             &node.item
         })
     }
@@ -320,7 +1106,7 @@ impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.take().map(|node| {
-            self.inner = node.next.as_mut().map(|node| &mut **node);
+            self.inner = node.next.as_deref_mut();
             &mut node.item
         })
     }
@@ -344,6 +1130,22 @@ impl<T> Iterator for IntoIter<T> {
 
 impl<T> FusedIterator for IntoIter<T> {}
 
+/// An iterator over the owned nodes of a list, see [`List::into_nodes`].
+pub struct IntoNodes<T: ?Sized> {
+    inner: List<T>,
+}
+
+impl<T: ?Sized> Iterator for IntoNodes<T> {
+    type Item = Box<Node<T>>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.pop_node()
+    }
+}
+
+impl<T: ?Sized> FusedIterator for IntoNodes<T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -358,6 +1160,89 @@ mod tests {
         assert_eq!(splitted.pop(), Some(2));
     }
 
+    #[test]
+    fn test_extend_ref() {
+        let mut list = List::<u32>::new();
+        list.extend(&[1, 2]);
+        list.extend([3].iter());
+
+        assert_eq!(list, [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_is_sorted() {
+        let list = (0..4).rev().collect::<List<u32>>();
+
+        assert!(list.is_sorted());
+        assert!(list.is_sorted_by_key(|n| n / 3));
+        assert!(!list.is_sorted_by_key(|&n| 4 - n));
+        assert!(List::<u32>::new().is_sorted_by(|_, _| false));
+
+        let mut calls = 0;
+        assert!(list.is_sorted_by_key(|&n| {
+            calls += 1;
+            n
+        }));
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn test_starts_ends_with() {
+        let list = (0..4).rev().collect::<List<u32>>();
+
+        assert!(list.starts_with(&list) && list.ends_with(&[0][..0]));
+        assert!(list.starts_with(0..2) && !list.starts_with(0..5));
+        assert!(list.ends_with([3]) && !list.ends_with(1..5));
+
+        let suffix = (2..4).rev().collect::<List<u32>>();
+        assert!(list.ends_with(&list) && list.ends_with(&suffix));
+        assert!(!suffix.ends_with(&list) && !list.ends_with([1, 2]));
+    }
+
+    #[test]
+    fn test_from_iter_ordered() {
+        let list = List::from_iter_ordered(0..4u32);
+        assert_eq!(list, [0, 1, 2, 3]);
+        assert_eq!(list.clone(), list);
+
+        assert!(List::from_iter_ordered(core::iter::empty::<u32>()).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        use serde::de::{
+            value::{Error, SeqDeserializer},
+            Deserialize, IntoDeserializer,
+        };
+
+        let words: SeqDeserializer<_, Error> = vec!["a", "b", "c"].into_deserializer();
+        let list = List::<String>::deserialize(words).unwrap();
+        assert_eq!(list, ["a", "b", "c"]);
+
+        let numbers: SeqDeserializer<_, Error> = vec![1u32, 2].into_deserializer();
+        assert!(List::<String>::deserialize(numbers).is_err());
+    }
+
+    #[test]
+    fn test_add() {
+        let list = (0..3).rev().collect::<List<u32>>();
+        let list = List::new() + list + List::new();
+        let list = list + (3..5).rev().collect();
+
+        assert_eq!(list, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_split_before() {
+        let mut list = (0..5).rev().collect::<List<u32>>();
+
+        assert!(list.split_before(|&n| n > 4).is_none());
+        assert_eq!(list.split_before(|&n| n == 3).unwrap(), [3, 4]);
+        assert_eq!(list.split_before(|&n| n == 0).unwrap(), [0, 1, 2]);
+        assert!(list.is_empty());
+    }
+
     #[test]
     fn test_unsized_elements() {
         let mut list = List::new();
@@ -369,4 +1254,149 @@ mod tests {
 
         assert_eq!(list.peek(), Some(&[40, 80, 120, 200][..]));
     }
+
+    #[test]
+    fn test_unsized_constructors() {
+        let mut strings = List::new();
+        strings.push_node(Node::boxed_str("world"));
+        strings.push_node(Node::boxed_str(""));
+        strings.push_node(Node::boxed_str("hello"));
+        assert_eq!(strings.join(" ").to_string(), "hello  world");
+
+        let words = vec![String::from("a"), String::from("b")];
+        let mut slices = List::new();
+        slices.push_node(Node::boxed_slice(&words));
+        slices.push_node(Node::from_vec(words));
+        slices.push_node(Node::from_boxed_slice(Box::new([]) as Box<[String]>));
+
+        assert!(slices.iter().map(<[_]>::len).eq([0, 2, 2]));
+        assert_eq!(slices.iter().nth(1), slices.iter().nth(2));
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_stats() {
+        let mut list = (0..10).collect::<List<_>>();
+        let splitted = list.split_after(|x| *x == 7).unwrap();
+
+        assert_eq!(list.stats().linked(), 10);
+        assert_eq!(list.stats().live(), 3);
+        assert_eq!(splitted.stats().live(), 7);
+
+        list.pop();
+        list.clear();
+        assert_eq!(list.stats().unlinked(), 10);
+        assert_eq!(list.stats().peak_len(), 10);
+    }
+
+    #[test]
+    fn test_heterogeneous_chain() {
+        use core::any::Any;
+
+        let mut list = Chain::<dyn Any>::new()
+            .link(Node::boxed(1u8))
+            .link(Node::boxed(String::from("two")))
+            .link(Node::boxed([3u16; 3]))
+            .finish();
+
+        list.push_node(Node::boxed(0u64));
+
+        let mut iter = list.iter();
+        assert_eq!(
+            iter.next().and_then(|item| item.downcast_ref()),
+            Some(&0u64)
+        );
+        assert_eq!(iter.next().and_then(|item| item.downcast_ref()), Some(&1u8));
+        assert_eq!(
+            iter.next()
+                .and_then(|item| item.downcast_ref::<String>())
+                .map(|s| &**s),
+            Some("two")
+        );
+        assert_eq!(
+            iter.next().and_then(|item| item.downcast_ref()),
+            Some(&[3u16; 3])
+        );
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_into_nodes() {
+        let list = (0..4).collect::<List<u32>>();
+        let head = list.peek().map(ptr::from_ref);
+
+        // Pushing every node to the front reverses the list.
+        let reversed = list.into_nodes().collect::<List<u32>>();
+        assert!(reversed.iter().eq(&[0, 1, 2, 3]));
+
+        let mut odds = List::<u32>::new();
+        odds.extend(reversed.into_nodes().filter(|node| node.item % 2 == 1));
+        assert!(odds.iter().eq(&[3, 1]));
+        assert_eq!(odds.peek().map(ptr::from_ref), head);
+    }
+
+    #[test]
+    fn test_covariance() {
+        fn list<'a>(list: List<&'static str>) -> List<&'a str> {
+            list
+        }
+
+        fn iter<'i, 'a>(iter: Iter<'i, &'static str>) -> Iter<'i, &'a str> {
+            iter
+        }
+
+        fn into_iter<'a>(iter: IntoIter<&'static str>) -> IntoIter<&'a str> {
+            iter
+        }
+
+        let statics = List::from_iter(["static"]);
+        let local = String::from("local");
+        let local = local.as_str();
+
+        let mut borrowed = list(statics.clone());
+        borrowed.push(local);
+        assert!(borrowed.iter().eq(&["local", "static"]));
+
+        assert!(iter(statics.iter())
+            .chain([&local])
+            .eq(&["static", "local"]));
+        assert!(into_iter(statics.into_iter())
+            .chain([local])
+            .eq(["static", "local"]));
+    }
+
+    #[test]
+    fn test_eq_slices() {
+        // Collecting pushes every item to the front.
+        let list = (1..=3).rev().collect::<List<u32>>();
+
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_eq!(list, &[1, 2, 3][..]);
+        assert_eq!(list, list.clone());
+        assert_ne!(list, [1, 2]);
+        assert_ne!(list, [1, 2, 3, 4]);
+        assert_ne!(list, List::new());
+        assert_eq!(List::<u32>::new(), [0u32; 0]);
+
+        let strings = ["b", "a"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<List<_>>();
+        assert_eq!(strings, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_doubly_list_conversion() {
+        let doubly = (1..=4).collect::<DoublyList<u32>>();
+        let list = List::from(doubly.clone());
+
+        assert_eq!(list, doubly);
+        assert_eq!(doubly, list);
+        assert_eq!(list, [1, 2, 3, 4]);
+        assert_eq!(DoublyList::from(list), doubly);
+
+        assert_ne!(List::from(DoublyList::from_iter([1, 2])), doubly);
+        assert_ne!(doubly, List::<u32>::new());
+    }
 }
@@ -0,0 +1,414 @@
+//! A piece table for edit-heavy documents, built on a [`DoublyList`] of pieces.
+
+use core::{
+    fmt::{self, Debug, Display},
+    ops::{Bound, RangeBounds},
+};
+
+use crate::doublylist::{DoublyList, NodeRef};
+
+/// The buffer a piece points into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    Original,
+    Added,
+}
+
+/// A span of one of the buffers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Piece {
+    source: Source,
+    start: usize,
+    len: usize,
+}
+
+/// A document stored as a list of pieces,
+/// each pointing into either the original text or an append-only buffer of added text.
+///
+/// Edits never copy or move text, they only split and relink pieces.
+/// Since neither buffer is ever overwritten,
+/// a [`Snapshot`] of the pieces is enough to restore the document later,
+/// which makes undo and redo cheap.
+///
+/// Offsets are counted in bytes, and must lie on char boundaries.
+///
+/// # Examples
+/// ```
+/// # use lists::piece_table::PieceTable;
+/// let mut table = PieceTable::new("Hello world");
+/// let before = table.snapshot();
+///
+/// table.insert(5, ",");
+/// table.delete(6..12);
+/// table.insert(6, "there!");
+/// assert_eq!(table.to_string(), "Hello,there!");
+///
+/// table.restore(&before);
+/// assert_eq!(table.to_string(), "Hello world");
+/// ```
+pub struct PieceTable {
+    original: String,
+    added: String,
+    pieces: DoublyList<Piece>,
+
+    /// The length of the document in bytes.
+    len: usize,
+}
+
+/// The pieces of a [`PieceTable`] at some point in time.
+///
+/// A snapshot only describes the table it was taken from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pieces: Vec<Piece>,
+    len: usize,
+}
+
+impl Snapshot {
+    /// Returns the length of the document in bytes, at the time of the snapshot.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the document was empty at the time of the snapshot.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl PieceTable {
+    /// Returns a new table holding `original`.
+    pub fn new<S: Into<String>>(original: S) -> Self {
+        let original = original.into();
+        let len = original.len();
+        let mut pieces = DoublyList::new();
+
+        if len != 0 {
+            pieces.push_back(Piece {
+                source: Source::Original,
+                start: 0,
+                len,
+            });
+        }
+
+        Self {
+            original,
+            added: String::new(),
+            pieces,
+            len,
+        }
+    }
+
+    /// Returns the length of the document in bytes.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the document is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of pieces the document is made of.
+    #[inline(always)]
+    pub const fn piece_count(&self) -> usize {
+        self.pieces.len()
+    }
+
+    #[inline(always)]
+    fn text_of(&self, piece: &Piece) -> &str {
+        let buffer = match piece.source {
+            Source::Original => &self.original,
+            Source::Added => &self.added,
+        };
+
+        &buffer[piece.start..piece.start + piece.len]
+    }
+
+    /// Returns the piece holding the byte at `offset`,
+    /// together with the number of bytes before that piece.
+    ///
+    /// Panics if `offset` does not lie on a char boundary.
+    fn locate(&self, offset: usize) -> Option<(NodeRef<Piece>, usize)> {
+        let mut cursor = self.pieces.front_ref();
+        let mut before = 0;
+
+        // Every handle comes from walking this list.
+        unsafe {
+            while let Some(node) = cursor {
+                let piece = self.pieces.get(node);
+
+                if offset < before + piece.len {
+                    assert!(
+                        self.text_of(piece).is_char_boundary(offset - before),
+                        "offset {} is not a char boundary",
+                        offset
+                    );
+
+                    return Some((node, before));
+                }
+
+                before += piece.len;
+                cursor = self.pieces.next_ref(node);
+            }
+        }
+
+        None
+    }
+
+    /// Inserts `text` at the byte `offset`.
+    ///
+    /// # Panics
+    /// Panics if `offset` is larger than `len()`, or does not lie on a char boundary.
+    pub fn insert(&mut self, offset: usize, text: &str) {
+        assert!(
+            offset <= self.len,
+            "insertion offset (is {}) should be <= len (is {})",
+            offset,
+            self.len
+        );
+
+        if text.is_empty() {
+            return;
+        }
+
+        let piece = Piece {
+            source: Source::Added,
+            start: self.added.len(),
+            len: text.len(),
+        };
+
+        match self.locate(offset) {
+            // Inserting at the end, which extends the last piece
+            // if it ends where the added text starts, like when typing.
+            None => match self.pieces.peek_back_mut() {
+                Some(last)
+                    if last.source == Source::Added && last.start + last.len == piece.start =>
+                {
+                    last.len += piece.len
+                }
+                _ => self.pieces.push_back(piece),
+            },
+            // The handle was just found in this list.
+            Some((node, before)) => unsafe {
+                let split = offset - before;
+
+                if split == 0 {
+                    self.pieces.insert_before(node, piece);
+                } else {
+                    let head = self.pieces.get_mut(node);
+                    let tail = Piece {
+                        source: head.source,
+                        start: head.start + split,
+                        len: head.len - split,
+                    };
+                    head.len = split;
+
+                    let inserted = self.pieces.insert_after(node, piece);
+                    self.pieces.insert_after(inserted, tail);
+                }
+            },
+        }
+
+        self.added.push_str(text);
+        self.len += text.len();
+    }
+
+    /// Deletes the bytes within `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, or does not lie on char boundaries.
+    pub fn delete<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+
+        assert!(
+            start <= end,
+            "range start (is {}) should be <= range end (is {})",
+            start,
+            end
+        );
+        assert!(
+            end <= self.len,
+            "range end (is {}) should be <= len (is {})",
+            end,
+            self.len
+        );
+
+        if start == end {
+            return;
+        }
+
+        // Validates both ends before anything is unlinked.
+        let (mut cursor, mut before) = match self.locate(start) {
+            Some((node, before)) => (Some(node), before),
+            None => return,
+        };
+        self.locate(end);
+
+        // Every handle comes from walking this list.
+        unsafe {
+            while let Some(node) = cursor {
+                if before >= end {
+                    break;
+                }
+
+                cursor = self.pieces.next_ref(node);
+
+                let piece = *self.pieces.get(node);
+                let from = start.saturating_sub(before);
+                let to = (end - before).min(piece.len);
+
+                match (from, to == piece.len) {
+                    (0, true) => {
+                        self.pieces.remove(node);
+                    }
+                    (0, false) => {
+                        let piece = self.pieces.get_mut(node);
+                        piece.start += to;
+                        piece.len -= to;
+                    }
+                    (from, true) => self.pieces.get_mut(node).len = from,
+                    (from, false) => {
+                        self.pieces.get_mut(node).len = from;
+                        self.pieces.insert_after(
+                            node,
+                            Piece {
+                                source: piece.source,
+                                start: piece.start + to,
+                                len: piece.len - to,
+                            },
+                        );
+                    }
+                }
+
+                before += piece.len;
+            }
+        }
+
+        self.len -= end - start;
+    }
+
+    /// Returns a snapshot of the document, to [`restore`](PieceTable::restore) later.
+    ///
+    /// This copies the pieces, but none of the text.
+    #[inline]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            pieces: self.pieces.iter().copied().collect(),
+            len: self.len,
+        }
+    }
+
+    /// Restores the document to the state of `snapshot`.
+    ///
+    /// # Panics
+    /// May panic when `snapshot` was taken from another table.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.pieces = snapshot.pieces.iter().copied().collect();
+        self.len = snapshot.len;
+
+        // Rejects pieces pointing outside of the buffers.
+        for piece in self.pieces.iter() {
+            self.text_of(piece);
+        }
+    }
+
+    /// Returns an iterator over the text of every piece, front to back.
+    #[inline]
+    pub fn pieces(&self) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        self.pieces.iter().map(move |piece| self.text_of(piece))
+    }
+}
+
+impl Default for PieceTable {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+impl Display for PieceTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.pieces().try_for_each(|piece| f.write_str(piece))
+    }
+}
+
+impl Debug for PieceTable {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PieceTable")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits_against_string() {
+        let mut table = PieceTable::new("the quick brown fox");
+        let mut model = String::from("the quick brown fox");
+
+        for step in 0..100 {
+            let at = (step * 7919) % (model.len() + 1);
+            table.insert(at, "ab");
+            model.insert_str(at, "ab");
+
+            let from = (step * 104_729) % model.len();
+            let to = (from + step % 7).min(model.len());
+            table.delete(from..to);
+            model.replace_range(from..to, "");
+
+            assert_eq!(table.len(), model.len());
+        }
+
+        assert_eq!(table.to_string(), model);
+        table.pieces.assert_invariants();
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let mut table = PieceTable::default();
+
+        for word in ["typing ", "is ", "coalesced"] {
+            table.insert(table.len(), word);
+        }
+        assert_eq!(table.piece_count(), 1);
+
+        let typed = table.snapshot();
+        table.delete(..7);
+        table.insert(0, "é");
+        assert_eq!(table.to_string(), "éis coalesced");
+
+        let edited = table.snapshot();
+        table.restore(&typed);
+        assert_eq!(table.to_string(), "typing is coalesced");
+        table.restore(&edited);
+        assert_eq!(table.to_string(), "éis coalesced");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_char_boundary() {
+        PieceTable::new("é").insert(1, "e");
+    }
+}
@@ -0,0 +1,338 @@
+//! A static pool of nodes, for doubly linked lists on targets without an allocator.
+
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Debug},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    doublylist::{Iter, Link},
+    raw::{self, Node},
+};
+
+/// A pool of `N` [`Node`]s, that lists check nodes out of and return them to.
+///
+/// The pool has a const constructor and can be shared between threads,
+/// so it can be placed in a `static`, and never touches the heap.
+/// Checking out a node claims a free slot with a single atomic swap,
+/// so it is lock free, but scans the slots in O(N).
+///
+/// # Examples
+/// ```
+/// # use lists::pool::{PoolList, StaticNodePool};
+/// static POOL: StaticNodePool<u32, 2> = StaticNodePool::new();
+///
+/// let mut list = PoolList::new_in(&POOL);
+/// assert_eq!(list.try_push_back(1), Ok(()));
+/// assert_eq!(list.try_push_back(2), Ok(()));
+/// assert_eq!(list.try_push_back(3), Err(3));
+///
+/// assert_eq!(list.pop_front(), Some(1));
+/// assert_eq!(POOL.available(), 1);
+/// ```
+pub struct StaticNodePool<T, const N: usize> {
+    nodes: [UnsafeCell<MaybeUninit<Node<T>>>; N],
+    taken: [AtomicBool; N],
+}
+
+// A node is only ever accessed by the list that checked it out,
+// so sharing the pool only moves items between threads.
+unsafe impl<T: Send, const N: usize> Send for StaticNodePool<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for StaticNodePool<T, N> {}
+
+impl<T, const N: usize> Default for StaticNodePool<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Debug for StaticNodePool<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StaticNodePool")
+            .field("capacity", &N)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> StaticNodePool<T, N> {
+    /// Returns a new pool, with all of its nodes available.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            nodes: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            taken: [const { AtomicBool::new(false) }; N],
+        }
+    }
+
+    /// Returns the number of nodes in the pool.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of nodes that are not checked out.
+    ///
+    /// Other threads may check out or return nodes at any time,
+    /// so the result is only a snapshot.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.taken
+            .iter()
+            .filter(|taken| !taken.load(Ordering::Relaxed))
+            .count()
+    }
+
+    /// Checks out a node holding `item`, or returns `item` if the pool is exhausted.
+    fn checkout(&self, item: T) -> Result<NonNull<Node<T>>, T> {
+        let slot = self.taken.iter().position(|taken| {
+            taken
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+        });
+
+        match slot {
+            None => Err(item),
+            Some(idx) => unsafe {
+                // The slot was just claimed, so nothing else accesses it.
+                let node = self.nodes[idx].get().cast::<Node<T>>();
+                node.write(Node::new(item));
+                Ok(NonNull::new_unchecked(node))
+            },
+        }
+    }
+
+    /// Returns `node` to the pool, and moves its item out.
+    ///
+    /// # Safety
+    /// `node` must have been checked out of this pool, and be unlinked.
+    unsafe fn release(&self, node: NonNull<Node<T>>) -> T {
+        let item = ptr::read(&(*node.as_ptr()).item);
+        let idx = node
+            .as_ptr()
+            .cast::<UnsafeCell<MaybeUninit<Node<T>>>>()
+            .offset_from_unsigned(self.nodes.as_ptr());
+
+        self.taken[idx].store(false, Ordering::Release);
+        item
+    }
+}
+
+/// A doubly linked list whose nodes are checked out of a [`StaticNodePool`].
+///
+/// Popping an item returns its node to the pool,
+/// and so does dropping the list for all nodes left in it.
+pub struct PoolList<'p, T, const N: usize> {
+    pool: &'p StaticNodePool<T, N>,
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+unsafe impl<T: Send, const N: usize> Send for PoolList<'_, T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for PoolList<'_, T, N> {}
+
+impl<T: Debug, const N: usize> Debug for PoolList<'_, T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'p, T, const N: usize> PoolList<'p, T, N> {
+    /// Returns a new empty list, that checks its nodes out of `pool`.
+    #[inline(always)]
+    pub const fn new_in(pool: &'p StaticNodePool<T, N>) -> Self {
+        Self {
+            pool,
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Pushes `item` to the front of the list,
+    /// or returns it if the pool is exhausted.
+    pub fn try_push_front(&mut self, item: T) -> Result<(), T> {
+        let node = self.pool.checkout(item)?;
+
+        match self.head {
+            None => self.tail = Some(node),
+            // The node is fresh, and the head is ours.
+            Some(head) => unsafe { raw::link_before(head, node) },
+        }
+
+        self.head = Some(node);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Pushes `item` to the back of the list,
+    /// or returns it if the pool is exhausted.
+    pub fn try_push_back(&mut self, item: T) -> Result<(), T> {
+        let node = self.pool.checkout(item)?;
+
+        match self.tail {
+            None => self.head = Some(node),
+            // The node is fresh, and the tail is ours.
+            Some(tail) => unsafe { raw::link_after(tail, node) },
+        }
+
+        self.tail = Some(node);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Unlinks `node` and returns it to the pool.
+    ///
+    /// `node` must be the head or the tail of the list.
+    unsafe fn release(&mut self, node: NonNull<Node<T>>) -> T {
+        let Node { next, prev, .. } = *node.as_ptr();
+
+        if self.head == Some(node) {
+            self.head = next;
+        }
+
+        if self.tail == Some(node) {
+            self.tail = prev;
+        }
+
+        raw::unlink(node);
+        self.len -= 1;
+        self.pool.release(node)
+    }
+
+    /// Pops the front item of the list, returning its node to the pool.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        // The head is linked into this list.
+        self.head.map(|head| unsafe { self.release(head) })
+    }
+
+    /// Pops the back item of the list, returning its node to the pool.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        // The tail is linked into this list.
+        self.tail.map(|tail| unsafe { self.release(tail) })
+    }
+
+    /// Returns a reference to the front item of the list.
+    #[inline]
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head.map(|head| unsafe { &(*head.as_ptr()).item })
+    }
+
+    /// Returns a reference to the back item of the list.
+    #[inline]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.tail.map(|tail| unsafe { &(*tail.as_ptr()).item })
+    }
+
+    /// Removes all items from the list, returning their nodes to the pool.
+    #[inline]
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Returns an iterator over the items of the list.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        // The chain is owned by this list, and borrowed by the iterator.
+        unsafe { Iter::from_raw_parts(self.head, self.tail, self.len) }
+    }
+}
+
+impl<T, const N: usize> Drop for PoolList<'_, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a PoolList<'_, T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_pool() {
+        use std::rc::Rc;
+
+        let pool = StaticNodePool::<Rc<()>, 3>::new();
+        let counter = Rc::new(());
+
+        let mut first = PoolList::new_in(&pool);
+        let mut second = PoolList::new_in(&pool);
+
+        first.try_push_back(counter.clone()).unwrap();
+        second.try_push_front(counter.clone()).unwrap();
+        first.try_push_front(counter.clone()).unwrap();
+        assert!(second.try_push_back(counter.clone()).is_err());
+        assert_eq!(pool.available(), 0);
+
+        drop(first);
+        assert_eq!(pool.available(), 2);
+        assert_eq!(Rc::strong_count(&counter), 2);
+
+        second.try_push_back(counter.clone()).unwrap();
+        assert_eq!(second.len(), 2);
+        assert!(second.pop_back().is_some());
+        assert!(second.pop_front().is_some());
+        assert!(second.is_empty());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_static_pool() {
+        use std::thread;
+
+        static POOL: StaticNodePool<usize, 64> = StaticNodePool::new();
+
+        let threads = (0..4)
+            .map(|n| {
+                thread::spawn(move || {
+                    let mut list = PoolList::new_in(&POOL);
+
+                    for item in 0..16 {
+                        list.try_push_back(n * 16 + item).unwrap();
+                    }
+
+                    list.iter().sum::<usize>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let sum = threads
+            .into_iter()
+            .map(|t| t.join().unwrap())
+            .sum::<usize>();
+        assert_eq!(sum, (0..64).sum());
+        assert_eq!(POOL.available(), 64);
+    }
+}
@@ -0,0 +1,100 @@
+//! Unsafe primitives for linking [`Node`]s by hand.
+//!
+//! A [`DoublyList`](crate::doublylist::DoublyList) is made of boxed nodes,
+//! where each node holds a `next` link, a `prev` link, and an item.
+//! A link is either `None`, marking the end of a chain,
+//! or a pointer to a node that was turned into a raw pointer with [`Node::into_raw`].
+//!
+//! The functions in this module only touch the links of the nodes they are given,
+//! and of their direct neighbours.
+//! They know nothing about the head, tail or length of any list,
+//! so it is up to the caller to keep those up to date.
+//!
+//! # Examples
+//! ```
+//! # use lists::raw::{self, Node};
+//! unsafe {
+//!     let a = Node::into_raw(Node::boxed(1));
+//!     let b = Node::into_raw(Node::boxed(3));
+//!     let c = Node::into_raw(Node::boxed(2));
+//!
+//!     raw::link_after(a, b);
+//!     raw::link_before(b, c);
+//!
+//!     // a <-> c <-> b
+//!     assert_eq!(a.as_ref().next(), Some(c));
+//!     assert_eq!(b.as_ref().prev(), Some(c));
+//!
+//!     raw::unlink(c);
+//!
+//!     // a <-> b
+//!     assert_eq!(a.as_ref().next(), Some(b));
+//!
+//!     drop(Node::from_raw(a));
+//!     drop(Node::from_raw(b));
+//!     drop(Node::from_raw(c));
+//! }
+//! ```
+
+use core::ptr::NonNull;
+
+pub use crate::doublylist::{Link, Node};
+
+/// Links `node` in right after `anchor`.
+///
+/// # Safety
+/// Both pointers must point to live nodes,
+/// and `node` must not be linked to any other node.
+#[inline(always)]
+pub unsafe fn link_after<T: ?Sized>(anchor: NonNull<Node<T>>, node: NonNull<Node<T>>) {
+    let next = (*anchor.as_ptr()).next;
+
+    (*node.as_ptr()).prev = Some(anchor);
+    (*node.as_ptr()).next = next;
+
+    if let Some(next) = next {
+        (*next.as_ptr()).prev = Some(node);
+    }
+
+    (*anchor.as_ptr()).next = Some(node);
+}
+
+/// Links `node` in right before `anchor`.
+///
+/// # Safety
+/// Both pointers must point to live nodes,
+/// and `node` must not be linked to any other node.
+#[inline(always)]
+pub unsafe fn link_before<T: ?Sized>(anchor: NonNull<Node<T>>, node: NonNull<Node<T>>) {
+    let prev = (*anchor.as_ptr()).prev;
+
+    (*node.as_ptr()).next = Some(anchor);
+    (*node.as_ptr()).prev = prev;
+
+    if let Some(prev) = prev {
+        (*prev.as_ptr()).next = Some(node);
+    }
+
+    (*anchor.as_ptr()).prev = Some(node);
+}
+
+/// Unlinks `node` from its neighbours, linking them to each other instead.
+/// Both links of `node` are set to `None` afterwards.
+///
+/// # Safety
+/// `node`, and the nodes it links to, must be live.
+#[inline(always)]
+pub unsafe fn unlink<T: ?Sized>(node: NonNull<Node<T>>) {
+    let Node { prev, next, .. } = *node.as_ptr();
+
+    if let Some(prev) = prev {
+        (*prev.as_ptr()).next = next;
+    }
+
+    if let Some(next) = next {
+        (*next.as_ptr()).prev = prev;
+    }
+
+    (*node.as_ptr()).prev = None;
+    (*node.as_ptr()).next = None;
+}
@@ -0,0 +1,350 @@
+//! A read-copy-update list, for lists that are read far more often than written.
+
+#[cfg(not(feature = "hazard"))]
+use core::sync::atomic::AtomicUsize;
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    ptr::NonNull,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+use std::{
+    sync::{Mutex, PoisonError},
+    thread,
+};
+
+use crate::doublylist::DoublyList;
+#[cfg(feature = "hazard")]
+use crate::hazard::{Domain, Slot};
+
+/*
+ * Readers announce themselves in one of two counters, picked by the parity of `epoch`,
+ * and only then load `current`. A reader that saw a stale epoch while announcing
+ * itself backs out and tries again, so once a writer moved `epoch` on,
+ * no reader joins the counter of the previous epoch anymore.
+ *
+ * A writer publishes a new version by swapping `current`, moves `epoch` on,
+ * and waits for the counter of the previous epoch to drain.
+ * Every reader that could still see the old version is counted there,
+ * so once it reads zero, the old version can be dropped.
+ * Writers are serialized by `writer`, and all operations are `SeqCst`,
+ * so the swap, the epoch change and the counters are seen in one order by all threads.
+ *
+ * With the `hazard` feature, readers instead protect the version they load
+ * with a hazard pointer, and the epoch and counters are left out.
+ * A writer that returns the old version waits until no hazard holds it,
+ * while a writer that drops it retires it, and never waits.
+ */
+
+/// A list that many threads read without locking, while writers publish new versions.
+///
+/// Readers get a snapshot of the current version with [`read`](RcuList::read),
+/// which costs two atomic increments and never waits.
+/// Writers copy the current version, change the copy, and publish it in one atomic step,
+/// after which they wait for the readers still looking at the old version to finish,
+/// and drop it.
+/// This makes writes slow and reads fast, which suits configurations and routing tables.
+///
+/// With the `hazard` feature, old versions are reclaimed with hazard pointers instead.
+/// A read then claims a hazard slot, and [`update`](RcuList::update) no longer waits for readers:
+/// it retires the old version, which is dropped by a later update once no reader holds it.
+///
+/// # Examples
+/// ```
+/// # use lists::rcu::RcuList;
+/// use std::{sync::Arc, thread};
+///
+/// let routes = Arc::new(RcuList::new());
+/// routes.update(|routes| routes.push_back("/home"));
+///
+/// let reader = {
+///     let routes = Arc::clone(&routes);
+///     thread::spawn(move || routes.read().len())
+/// };
+///
+/// routes.update(|routes| routes.push_back("/about"));
+/// assert!(reader.join().unwrap() >= 1);
+/// assert_eq!(*routes.read(), ["/home", "/about"]);
+/// ```
+pub struct RcuList<T> {
+    current: AtomicPtr<DoublyList<T>>,
+    #[cfg(not(feature = "hazard"))]
+    epoch: AtomicUsize,
+    #[cfg(not(feature = "hazard"))]
+    readers: [AtomicUsize; 2],
+    #[cfg(feature = "hazard")]
+    domain: Domain<DoublyList<T>>,
+    writer: Mutex<()>,
+    marker: PhantomData<Box<DoublyList<T>>>,
+}
+
+// Readers share `&T`s between threads, and writers drop versions other threads created.
+unsafe impl<T: Send> Send for RcuList<T> {}
+unsafe impl<T: Send + Sync> Sync for RcuList<T> {}
+
+impl<T> Default for RcuList<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for RcuList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.read().fmt(f)
+    }
+}
+
+impl<T> From<DoublyList<T>> for RcuList<T> {
+    /// Returns a list whose first version is `list`.
+    #[inline]
+    fn from(list: DoublyList<T>) -> Self {
+        Self {
+            current: AtomicPtr::new(Box::into_raw(Box::new(list))),
+            #[cfg(not(feature = "hazard"))]
+            epoch: AtomicUsize::new(0),
+            #[cfg(not(feature = "hazard"))]
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            #[cfg(feature = "hazard")]
+            domain: Domain::new(),
+            writer: Mutex::new(()),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for RcuList<T> {
+    fn drop(&mut self) {
+        // Readers borrow the list, so none are left.
+        unsafe { drop(Box::from_raw(*self.current.get_mut())) };
+    }
+}
+
+impl<T> RcuList<T> {
+    /// Returns a new list, whose first version is empty.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from(DoublyList::new())
+    }
+
+    /// Returns a snapshot of the current version of the list.
+    ///
+    /// The snapshot stays the same while writers publish new versions,
+    /// and keeps the writers from dropping it until the guard is dropped.
+    #[cfg(not(feature = "hazard"))]
+    #[inline]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        let readers = loop {
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            let readers = &self.readers[epoch % 2];
+            readers.fetch_add(1, Ordering::SeqCst);
+
+            if self.epoch.load(Ordering::SeqCst) == epoch {
+                break readers;
+            }
+
+            readers.fetch_sub(1, Ordering::SeqCst);
+        };
+
+        let list = self.current.load(Ordering::SeqCst);
+
+        ReadGuard {
+            // `current` is never null, and the version stays alive while the reader is counted.
+            list: unsafe { NonNull::new_unchecked(list) },
+            readers,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a snapshot of the current version of the list.
+    ///
+    /// The snapshot stays the same while writers publish new versions,
+    /// and keeps the writers from dropping it until the guard is dropped.
+    #[cfg(feature = "hazard")]
+    #[inline]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        let slot = self.domain.acquire();
+        let list = slot.protect(0, &self.current);
+
+        ReadGuard {
+            // `current` is never null, and the version stays alive while the hazard holds it.
+            list: unsafe { NonNull::new_unchecked(list) },
+            slot,
+            marker: PhantomData,
+        }
+    }
+
+    /// Publishes `list` as the new version, and returns the previous version,
+    /// once no reader looks at it anymore.
+    ///
+    /// A thread must not write while it holds a [`ReadGuard`] of the same list,
+    /// as it would wait for itself forever.
+    pub fn replace(&self, list: DoublyList<T>) -> DoublyList<T> {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        let old = self.swap_locked(list);
+        self.synchronize(old);
+
+        // `old` was swapped out, and every reader that could see it is done.
+        *unsafe { Box::from_raw(old) }
+    }
+
+    /// Publishes a copy of the current version, changed by `f`, and returns what `f` returned.
+    ///
+    /// The previous version is dropped once no reader looks at it anymore,
+    /// so like [`replace`](Self::replace), this must not be called while holding a [`ReadGuard`].
+    /// If `f` panics, nothing is published.
+    pub fn update<F, R>(&self, f: F) -> R
+    where
+        T: Clone,
+        F: FnOnce(&mut DoublyList<T>) -> R,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Only writers swap `current`, and they hold the lock.
+        let mut copy = unsafe { (*self.current.load(Ordering::SeqCst)).clone() };
+        let result = f(&mut copy);
+
+        let old = self.swap_locked(copy);
+        self.reclaim(old);
+        result
+    }
+
+    /// Returns the current version, which no reader can be looking at.
+    #[inline]
+    pub fn into_inner(mut self) -> DoublyList<T> {
+        // `current` is never null, and owning the list rules out readers.
+        mem::take(unsafe { &mut **self.current.get_mut() })
+    }
+
+    /// Swaps in `list`, and returns the previous version.
+    /// The caller must hold `writer`.
+    #[inline]
+    fn swap_locked(&self, list: DoublyList<T>) -> *mut DoublyList<T> {
+        self.current
+            .swap(Box::into_raw(Box::new(list)), Ordering::SeqCst)
+    }
+
+    /// Waits for the readers of `old`, which was swapped out.
+    #[cfg(not(feature = "hazard"))]
+    fn synchronize(&self, _old: *mut DoublyList<T>) {
+        let epoch = self.epoch.fetch_add(1, Ordering::SeqCst);
+        let readers = &self.readers[epoch % 2];
+
+        while readers.load(Ordering::SeqCst) != 0 {
+            thread::yield_now();
+        }
+    }
+
+    /// Waits for the readers of `old`, which was swapped out.
+    #[cfg(feature = "hazard")]
+    fn synchronize(&self, old: *mut DoublyList<T>) {
+        // Readers that publish `old` from now on see it was swapped out, and load again.
+        while self.domain.is_protected(old) {
+            thread::yield_now();
+        }
+    }
+
+    /// Drops `old`, which was swapped out, once no reader looks at it anymore.
+    #[cfg(not(feature = "hazard"))]
+    fn reclaim(&self, old: *mut DoublyList<T>) {
+        self.synchronize(old);
+
+        // Every reader that could see `old` is done.
+        drop(unsafe { Box::from_raw(old) });
+    }
+
+    /// Retires `old`, which was swapped out, to be dropped once no hazard holds it.
+    #[cfg(feature = "hazard")]
+    fn reclaim(&self, old: *mut DoublyList<T>) {
+        let slot = self.domain.acquire();
+
+        // The slot was just claimed, and only the writer that swapped out `old` retires it.
+        unsafe { self.domain.retire(slot, old) };
+        slot.release();
+    }
+}
+
+/// A snapshot of an [`RcuList`], returned by [`RcuList::read`].
+pub struct ReadGuard<'a, T> {
+    list: NonNull<DoublyList<T>>,
+    #[cfg(not(feature = "hazard"))]
+    readers: &'a AtomicUsize,
+    #[cfg(feature = "hazard")]
+    slot: &'a Slot<DoublyList<T>>,
+    marker: PhantomData<&'a DoublyList<T>>,
+}
+
+// The guard hands out a `&DoublyList<T>`, and only touches atomics when dropped.
+unsafe impl<T: Sync> Send for ReadGuard<'_, T> {}
+unsafe impl<T: Sync> Sync for ReadGuard<'_, T> {}
+
+impl<T> Deref for ReadGuard<'_, T> {
+    type Target = DoublyList<T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        // The version stays alive while this reader is counted, or its hazard holds it.
+        unsafe { self.list.as_ref() }
+    }
+}
+
+impl<T: Debug> Debug for ReadGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T> Drop for ReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(not(feature = "hazard"))]
+        self.readers.fetch_sub(1, Ordering::SeqCst);
+        #[cfg(feature = "hazard")]
+        self.slot.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_snapshots() {
+        let list = Arc::new(RcuList::from((0..10).collect::<DoublyList<u64>>()));
+
+        let readers = (0..4)
+            .map(|_| {
+                let list = Arc::clone(&list);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        let snapshot = list.read();
+                        let len = snapshot.len() as u64;
+                        // Every version holds the numbers from 0 up to its length.
+                        assert_eq!(snapshot.iter().sum::<u64>(), len * (len - 1) / 2);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for n in 10..200 {
+            list.update(|list| list.push_back(n));
+        }
+
+        readers
+            .into_iter()
+            .for_each(|reader| reader.join().unwrap());
+
+        let snapshot = list.read();
+        assert_eq!(snapshot.len(), 200);
+        drop(snapshot);
+
+        let old = list.replace(DoublyList::new());
+        assert_eq!(old.len(), 200);
+        assert!(Arc::try_unwrap(list).unwrap().into_inner().is_empty());
+    }
+}
@@ -0,0 +1,407 @@
+//! A rope for large texts, built on a [`DoublyList`] of `str` nodes.
+
+use core::{
+    fmt::{self, Debug, Display},
+    iter::FusedIterator,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::doublylist::{self, DoublyList, Node, NodeRef};
+
+/// The largest chunk, in bytes, an edit will produce.
+const MAX_CHUNK: usize = 1024;
+
+/// A text stored as a list of chunks.
+///
+/// Every chunk is an unsized `Node<str>`,
+/// so a chunk is a single allocation holding both its links and its text.
+/// Edits only rebuild the chunks they touch,
+/// and appending one rope to another splices their chunks together in O(1).
+///
+/// Positions are counted in `char`s.
+/// Finding the chunk holding a position walks the chunks,
+/// which is O(n) in the length of the text, but never copies the text.
+///
+/// # Examples
+/// ```
+/// # use lists::rope::Rope;
+/// let mut rope = Rope::from("Hello world");
+/// rope.insert(5, ",");
+/// rope.append(&mut Rope::from("!\nBye"));
+///
+/// assert_eq!(rope.to_string(), "Hello, world!\nBye");
+///
+/// rope.remove(5..12);
+/// assert_eq!(rope.lines().collect::<Vec<_>>(), ["Hello!", "Bye"]);
+/// ```
+#[derive(Default)]
+pub struct Rope {
+    chunks: DoublyList<str>,
+
+    /// The length of the text in bytes.
+    len_bytes: usize,
+
+    /// The length of the text in chars.
+    len_chars: usize,
+}
+
+/// Returns the byte index of the char at `char_idx` in `text`,
+/// or the length of `text` if `char_idx` is the number of chars in it.
+#[inline]
+fn byte_index(text: &str, char_idx: usize) -> usize {
+    text.char_indices()
+        .nth(char_idx)
+        .map_or(text.len(), |(idx, _)| idx)
+}
+
+/// Splits `text` into chunks of at most `MAX_CHUNK` bytes, on char boundaries.
+fn split_chunks(mut text: &str) -> impl Iterator<Item = &str> {
+    core::iter::from_fn(move || {
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut end = text.len().min(MAX_CHUNK);
+
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let (chunk, rest) = text.split_at(end);
+        text = rest;
+        Some(chunk)
+    })
+}
+
+impl Rope {
+    /// Returns a new empty rope.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            chunks: DoublyList::new(),
+            len_bytes: 0,
+            len_chars: 0,
+        }
+    }
+
+    /// Returns the length of the text in bytes.
+    #[inline(always)]
+    pub const fn len_bytes(&self) -> usize {
+        self.len_bytes
+    }
+
+    /// Returns the length of the text in chars.
+    #[inline(always)]
+    pub const fn len_chars(&self) -> usize {
+        self.len_chars
+    }
+
+    /// Returns `true` if the rope holds no text.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len_bytes == 0
+    }
+
+    /// Appends `text` to the end of the rope.
+    pub fn push_str(&mut self, text: &str) {
+        for chunk in split_chunks(text) {
+            self.chunks.push_back_node(Node::boxed_str(chunk));
+        }
+
+        self.len_bytes += text.len();
+        self.len_chars += text.chars().count();
+    }
+
+    /// Moves all text of `other` to the end of this rope, in O(1).
+    /// `other` is left empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut Rope) {
+        self.chunks.append(&mut other.chunks);
+        self.len_bytes += core::mem::take(&mut other.len_bytes);
+        self.len_chars += core::mem::take(&mut other.len_chars);
+    }
+
+    /// Returns the chunk holding the char at `char_idx`,
+    /// together with the number of chars before that chunk.
+    ///
+    /// A position at the boundary of two chunks belongs to the first one.
+    fn locate(&self, char_idx: usize) -> Option<(NodeRef<str>, usize)> {
+        let mut cursor = self.chunks.front_ref();
+        let mut before = 0;
+
+        // Every handle comes from walking this list.
+        unsafe {
+            while let Some(chunk) = cursor {
+                let chars = self.chunks.get(chunk).chars().count();
+
+                if char_idx <= before + chars {
+                    return Some((chunk, before));
+                }
+
+                before += chars;
+                cursor = self.chunks.next_ref(chunk);
+            }
+        }
+
+        None
+    }
+
+    /// Replaces the chunk `at` with chunks holding `text`.
+    ///
+    /// `at` must be linked into `self.chunks`.
+    unsafe fn replace_chunk(&mut self, at: NodeRef<str>, text: &str) {
+        let mut anchor = at;
+
+        for chunk in split_chunks(text) {
+            anchor = self
+                .chunks
+                .insert_node_after(anchor, Node::boxed_str(chunk));
+        }
+
+        self.chunks.remove_node(at);
+    }
+
+    /// Inserts `text` at the char position `char_idx`.
+    ///
+    /// # Panics
+    /// Panics if `char_idx` is larger than `len_chars()`.
+    pub fn insert(&mut self, char_idx: usize, text: &str) {
+        assert!(
+            char_idx <= self.len_chars,
+            "insertion index (is {}) should be <= len (is {})",
+            char_idx,
+            self.len_chars
+        );
+
+        if text.is_empty() {
+            return;
+        }
+
+        let (chunk, before) = match self.locate(char_idx) {
+            Some(found) => found,
+            None => return self.push_str(text),
+        };
+
+        // The handle was just found in this list.
+        unsafe {
+            let old = self.chunks.get(chunk);
+            let (head, tail) = old.split_at(byte_index(old, char_idx - before));
+
+            let mut joined = String::with_capacity(old.len() + text.len());
+            joined.push_str(head);
+            joined.push_str(text);
+            joined.push_str(tail);
+
+            self.replace_chunk(chunk, &joined);
+        }
+
+        self.len_bytes += text.len();
+        self.len_chars += text.chars().count();
+    }
+
+    /// Removes the chars within `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    pub fn remove<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len_chars,
+        };
+
+        assert!(
+            start <= end,
+            "range start (is {}) should be <= range end (is {})",
+            start,
+            end
+        );
+        assert!(
+            end <= self.len_chars,
+            "range end (is {}) should be <= len (is {})",
+            end,
+            self.len_chars
+        );
+
+        let mut cursor = self.chunks.front_ref();
+        let mut before = 0;
+
+        // Every handle comes from walking this list.
+        unsafe {
+            while let Some(chunk) = cursor {
+                if before >= end {
+                    break;
+                }
+
+                cursor = self.chunks.next_ref(chunk);
+
+                let text = self.chunks.get(chunk);
+                let chars = text.chars().count();
+
+                if before + chars > start {
+                    let from = byte_index(text, start.saturating_sub(before));
+                    let to = byte_index(text, (end - before).min(chars));
+
+                    let mut kept = String::with_capacity(text.len() - (to - from));
+                    kept.push_str(&text[..from]);
+                    kept.push_str(&text[to..]);
+
+                    self.len_bytes -= to - from;
+                    self.replace_chunk(chunk, &kept);
+                }
+
+                before += chars;
+            }
+        }
+
+        self.len_chars -= end - start;
+    }
+
+    /// Returns an iterator over the chunks of the rope.
+    #[inline(always)]
+    pub fn chunks(&self) -> doublylist::Iter<'_, str> {
+        self.chunks.iter()
+    }
+
+    /// Returns an iterator over the chars of the rope.
+    #[inline]
+    pub fn chars(&self) -> impl DoubleEndedIterator<Item = char> + '_ {
+        self.chunks.iter().flat_map(str::chars)
+    }
+
+    /// Returns an iterator over the lines of the rope,
+    /// split like [`str::lines`] does.
+    #[inline(always)]
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            chunks: self.chunks.iter(),
+            current: "",
+        }
+    }
+}
+
+impl From<&str> for Rope {
+    #[inline]
+    fn from(text: &str) -> Self {
+        let mut rope = Rope::new();
+        rope.push_str(text);
+        rope
+    }
+}
+
+impl Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.chunks.iter().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl Debug for Rope {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Rope").field(&self.to_string()).finish()
+    }
+}
+
+/// An iterator over the lines of a [`Rope`].
+#[derive(Clone)]
+pub struct Lines<'a> {
+    chunks: doublylist::Iter<'a, str>,
+
+    /// The part of the current chunk that is not yielded yet.
+    current: &'a str,
+}
+
+impl Iterator for Lines<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+
+        loop {
+            if let Some(end) = self.current.find('\n') {
+                line.push_str(&self.current[..end]);
+                self.current = &self.current[end + 1..];
+
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+
+                return Some(line);
+            }
+
+            line.push_str(self.current);
+            self.current = "";
+
+            match self.chunks.next() {
+                Some(chunk) => self.current = chunk,
+                None if line.is_empty() => return None,
+                None => return Some(line),
+            }
+        }
+    }
+}
+
+impl FusedIterator for Lines<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits_against_string() {
+        let mut rope = Rope::new();
+        let mut model = String::new();
+
+        let text = "héllo wörld, ".repeat(200);
+        rope.push_str(&text);
+        model.push_str(&text);
+
+        for step in 0..50 {
+            let len = model.chars().count();
+            let at = (step * 7919) % (len + 1);
+
+            rope.insert(at, "ïnsert");
+            let byte = byte_index(&model, at);
+            model.insert_str(byte, "ïnsert");
+
+            let len = model.chars().count();
+            let from = (step * 104_729) % len;
+            let to = (from + step * 13).min(len);
+
+            rope.remove(from..to);
+            let (from, to) = (byte_index(&model, from), byte_index(&model, to));
+            model.replace_range(from..to, "");
+
+            assert_eq!(rope.len_chars(), model.chars().count());
+            assert_eq!(rope.len_bytes(), model.len());
+        }
+
+        assert_eq!(rope.to_string(), model);
+        assert!(rope.chars().eq(model.chars()));
+        assert!(rope.chunks().all(|chunk| chunk.len() <= MAX_CHUNK));
+        rope.chunks.assert_invariants();
+    }
+
+    #[test]
+    fn test_lines_across_chunks() {
+        let mut rope = Rope::from("first\r");
+        rope.append(&mut Rope::from("\nsec"));
+        rope.append(&mut Rope::from("ond\n\nlast\n"));
+
+        let text = rope.to_string();
+        assert!(rope.lines().eq(text.lines()));
+
+        rope.remove(..);
+        assert!(rope.is_empty());
+        assert_eq!(rope.lines().next(), None);
+    }
+}
@@ -0,0 +1,139 @@
+//! Allocating nodes that hold a slice of items, shared by the node types of both lists.
+//!
+//! The items of a `Node<[T]>` live inline, behind its links,
+//! so such a node is allocated by hand, with a layout computed for its length.
+
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem,
+    ptr::{self, NonNull},
+};
+use std::alloc;
+
+/*
+ * A `Node<[T; N], [T]>` unsizes into a `Node<[T]>`,
+ * so both share the layout of their links,
+ * and the items start at the same offset.
+ * This lets us compute the layout of a `Node<[T]>` of any length
+ * from the layout of a `Node<[T; 0], [T]>`.
+ */
+
+/// A node type holding a slice of `T`s.
+///
+/// # Safety
+/// `HEADER` must be the layout of the node of no items,
+/// `ITEMS` must be the offset of the items within any node of the type,
+/// and `from_raw_parts` must only attach the length of the items to the pointer.
+pub(crate) unsafe trait SliceNode<T> {
+    /// The layout of the node of no items.
+    const HEADER: Layout;
+
+    /// The offset of the first item within the node.
+    const ITEMS: usize;
+
+    /// Returns a pointer to the node at `ptr`, holding `len` items.
+    fn from_raw_parts(ptr: *mut u8, len: usize) -> *mut Self;
+
+    /// Sets the links of the freshly allocated `node` to `None`.
+    ///
+    /// # Safety
+    /// `node` must point to an allocation of the layout of a node of its length.
+    unsafe fn init_links(node: *mut Self);
+}
+
+/// Returns the layout of a node of type `N`, holding `len` items.
+///
+/// # Panics
+/// Panics if the size of the node overflows.
+pub(crate) fn layout<T, N: SliceNode<T> + ?Sized>(len: usize) -> Layout {
+    Layout::array::<T>(len)
+        .and_then(|items| Layout::from_size_align(N::ITEMS + items.size(), N::HEADER.align()))
+        .map(|layout| layout.pad_to_align())
+        .expect("capacity overflow")
+}
+
+/// Allocates a node for `len` items, with its links set to `None`.
+/// The items are left uninitialized.
+fn alloc<T, N: SliceNode<T> + ?Sized>(len: usize) -> NonNull<N> {
+    let layout = layout::<T, N>(len);
+
+    unsafe {
+        // The links make sure the layout is never zero sized.
+        let ptr = alloc::alloc(layout);
+
+        if ptr.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+
+        let node = N::from_raw_parts(ptr, len);
+        N::init_links(node);
+        NonNull::new_unchecked(node)
+    }
+}
+
+/// Returns a pointer to the first item of `node`, which must have been allocated.
+#[inline(always)]
+unsafe fn items<T, N: SliceNode<T> + ?Sized>(node: NonNull<N>) -> *mut T {
+    node.as_ptr().cast::<u8>().add(N::ITEMS).cast()
+}
+
+/// Returns a new boxed node, holding the items of `items`.
+///
+/// The items are moved into the node, without being cloned.
+/// Since the links live in front of the items,
+/// the buffer of the vector itself cannot be reused.
+pub(crate) fn from_vec<T, N: SliceNode<T> + ?Sized>(mut items: Vec<T>) -> Box<N> {
+    let len = items.len();
+    let node = alloc::<T, N>(len);
+
+    unsafe {
+        ptr::copy_nonoverlapping(items.as_ptr(), self::items(node), len);
+
+        // The items are owned by the node now.
+        items.set_len(0);
+        Box::from_raw(node.as_ptr())
+    }
+}
+
+/// Frees a partially initialized node, if cloning an item panics.
+struct Guard<T, N: SliceNode<T> + ?Sized> {
+    node: NonNull<N>,
+    len: usize,
+    initialized: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T, N: SliceNode<T> + ?Sized> Drop for Guard<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            let items = items(self.node);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(items, self.initialized));
+
+            alloc::dealloc(self.node.as_ptr().cast(), layout::<T, N>(self.len));
+        }
+    }
+}
+
+/// Returns a new boxed node, holding clones of `items`.
+pub(crate) fn boxed_slice<T: Clone, N: SliceNode<T> + ?Sized>(items: &[T]) -> Box<N> {
+    let mut guard = Guard {
+        node: alloc::<T, N>(items.len()),
+        len: items.len(),
+        initialized: 0,
+        marker: PhantomData,
+    };
+
+    unsafe {
+        let dst = self::items(guard.node);
+
+        for item in items {
+            dst.add(guard.initialized).write(item.clone());
+            guard.initialized += 1;
+        }
+
+        let node = guard.node;
+        mem::forget(guard);
+        Box::from_raw(node.as_ptr())
+    }
+}
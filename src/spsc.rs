@@ -0,0 +1,303 @@
+//! A bounded, wait-free single-producer single-consumer queue.
+
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::sync::Arc;
+
+/*
+ * The slots form a ring, addressed by two counters:
+ * `head` counts the items popped, and is only written by the consumer,
+ * `tail` counts the items pushed, and is only written by the producer.
+ * The slots from `head` up to `tail` hold items, all others are vacant.
+ *
+ * Both counters run modulo twice the capacity, rather than wrapping at `usize::MAX`,
+ * so every counter maps to the same slot no matter how many items went through,
+ * and a full ring, where `tail` is a capacity ahead of `head`, differs from an empty one.
+ *
+ * The producer publishes an item by storing `tail` with `Release`,
+ * after the item was written, and the consumer hands a vacant slot back
+ * by storing `head` with `Release`, after the item was read.
+ */
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for Ring<T> {}
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    #[inline(always)]
+    fn slot(&self, index: usize) -> *mut MaybeUninit<T> {
+        // Counters past the capacity map to the slots once more.
+        let index = index.checked_sub(self.slots.len()).unwrap_or(index);
+        self.slots[index].get()
+    }
+
+    /// Returns the counter after `index`.
+    #[inline(always)]
+    fn next(&self, index: usize) -> usize {
+        match index + 1 == 2 * self.slots.len() {
+            true => 0,
+            false => index + 1,
+        }
+    }
+
+    /// Returns the number of items from `head` up to `tail`.
+    #[inline(always)]
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        match tail.checked_sub(head) {
+            Some(len) => len,
+            None => tail + 2 * self.slots.len() - head,
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        self.distance(head, tail)
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        let mut head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+
+        while head != tail {
+            // The slots from `head` up to `tail` hold items.
+            unsafe { (*self.slot(head)).assume_init_drop() };
+            head = self.next(head);
+        }
+    }
+}
+
+/// Returns the two ends of a new queue, that holds at most `capacity` items.
+///
+/// All slots are allocated up front, so pushing and popping never allocate,
+/// and both complete in a bounded number of steps,
+/// which makes the queue fit for audio and IO threads.
+///
+/// # Panics
+/// Panics if `capacity` is 0, or more than `usize::MAX / 2`.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+///
+/// let (mut producer, mut consumer) = lists::spsc::channel(16);
+///
+/// let sender = thread::spawn(move || {
+///     for sample in 0..1000 {
+///         while producer.try_push(sample).is_err() {}
+///     }
+/// });
+///
+/// let mut received = 0;
+/// while received < 1000 {
+///     if let Some(sample) = consumer.try_pop() {
+///         assert_eq!(sample, received);
+///         received += 1;
+///     }
+/// }
+///
+/// sender.join().unwrap();
+/// ```
+pub fn channel<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    assert!(capacity != 0, "capacity must be at least 1");
+    assert!(capacity <= usize::MAX / 2, "capacity overflow");
+
+    let ring = Arc::new(Ring {
+        slots: (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+
+    (
+        Producer { ring: ring.clone() },
+        Consumer {
+            ring,
+            marker: PhantomData,
+        },
+    )
+}
+
+/// The pushing end of a queue created by [`channel`].
+pub struct Producer<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T> Debug for Producer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> Producer<T> {
+    /// Pushes `item` to the back of the queue, or returns it if the queue is full.
+    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+        let head = self.ring.head.load(Ordering::Acquire);
+
+        if self.ring.distance(head, tail) == self.capacity() {
+            return Err(item);
+        }
+
+        // The slot is vacant, and only the producer writes to vacant slots.
+        unsafe { (*self.ring.slot(tail)).write(item) };
+        self.ring
+            .tail
+            .store(self.ring.next(tail), Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns the number of items the queue can hold.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.ring.slots.len()
+    }
+}
+
+/// The popping end of a queue created by [`channel`].
+pub struct Consumer<T> {
+    ring: Arc<Ring<T>>,
+
+    /// [`peek`](Consumer::peek) hands out shared references to the items,
+    /// so sharing the consumer between threads requires `T: Sync`.
+    marker: PhantomData<T>,
+}
+
+impl<T> Debug for Consumer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Pops the front item of the queue, if there is one.
+    pub fn try_pop(&mut self) -> Option<T> {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // The slot holds an item, and only the consumer reads from filled slots.
+        let item = unsafe { (*self.ring.slot(head)).assume_init_read() };
+        self.ring
+            .head
+            .store(self.ring.next(head), Ordering::Release);
+        Some(item)
+    }
+
+    /// Returns a reference to the front item of the queue, if there is one.
+    pub fn peek(&self) -> Option<&T> {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // The item stays in place until the consumer pops it,
+        // which needs a mutable borrow.
+        Some(unsafe { (*self.ring.slot(head)).assume_init_ref() })
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of items the queue can hold.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.ring.slots.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wraparound() {
+        let (mut producer, mut consumer) = channel(3);
+
+        for round in 0..10 {
+            assert_eq!(producer.try_push(round), Ok(()));
+            assert_eq!(producer.try_push(round + 1), Ok(()));
+            assert_eq!(consumer.peek(), Some(&round));
+            assert_eq!(consumer.try_pop(), Some(round));
+            assert_eq!(consumer.try_pop(), Some(round + 1));
+        }
+
+        assert_eq!(producer.try_push(0), Ok(()));
+        assert_eq!(producer.try_push(1), Ok(()));
+        assert_eq!(producer.try_push(2), Ok(()));
+        assert_eq!(producer.try_push(3), Err(3));
+        assert!(producer.is_full());
+        assert_eq!(consumer.len(), 3);
+
+        // The counters run modulo twice the capacity.
+        assert!(producer.ring.tail.load(Ordering::Relaxed) < 6);
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        let counter = Arc::new(());
+        let (mut producer, consumer) = channel(4);
+
+        for _ in 0..3 {
+            producer.try_push(counter.clone()).unwrap();
+        }
+
+        drop(consumer);
+        assert_eq!(Arc::strong_count(&counter), 4);
+        drop(producer);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+}
@@ -0,0 +1,174 @@
+//! Read-only doubly linked lists, built at compile time.
+
+use core::{
+    fmt::{self, Debug},
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+};
+
+use crate::doublylist::{Iter, Node};
+
+/// A chain of `N` [`Node`]s, linked at compile time,
+/// and usually built by the [`static_list!`](crate::static_list) macro.
+///
+/// The nodes are stored inline, and link to each other by address,
+/// so the list is meant to live in a `static`.
+///
+/// # Examples
+/// ```
+/// use lists::static_list;
+///
+/// let primes = static_list!(u8; 2, 3, 5, 7);
+///
+/// assert!(primes.iter().eq(&[2, 3, 5, 7]));
+/// assert_eq!(primes.iter().rev().next(), Some(&7));
+/// ```
+pub struct StaticList<T: 'static, const N: usize> {
+    nodes: [Node<T>; N],
+}
+
+// The list is never mutated, so sharing it only shares its items.
+unsafe impl<T: Sync, const N: usize> Sync for StaticList<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Send for StaticList<T, N> {}
+
+impl<T: Debug, const N: usize> Debug for StaticList<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> StaticList<T, N> {
+    /// Returns a list holding `items`, whose nodes are linked as if they lived at `this`.
+    ///
+    /// `this` should be the `static` the list is stored in,
+    /// which may be referenced from its own initializer:
+    /// ```
+    /// # use lists::static_list::StaticList;
+    /// static LIST: StaticList<&str, 2> = StaticList::new(["a", "b"], &LIST);
+    ///
+    /// assert!(LIST.iter().eq(&["a", "b"]));
+    /// ```
+    /// A list stored anywhere else still links to the nodes of `this`.
+    pub const fn new(items: [T; N], this: &'static Self) -> Self {
+        let base = ptr::addr_of!(this.nodes).cast::<Node<T>>();
+        let src = ptr::addr_of!(items).cast::<T>();
+
+        let mut nodes = [const { MaybeUninit::<Node<T>>::uninit() }; N];
+        let mut idx = 0;
+
+        // Only addresses within `this` are computed, nothing is read from it.
+        unsafe {
+            while idx < N {
+                nodes[idx] = MaybeUninit::new(Node {
+                    item: src.add(idx).read(),
+                    prev: match idx {
+                        0 => None,
+                        _ => Some(NonNull::new_unchecked(base.add(idx - 1).cast_mut())),
+                    },
+                    next: match idx + 1 == N {
+                        true => None,
+                        false => Some(NonNull::new_unchecked(base.add(idx + 1).cast_mut())),
+                    },
+                });
+
+                idx += 1;
+            }
+
+            // Every item was moved into a node.
+            mem::forget(items);
+
+            Self {
+                nodes: ptr::addr_of!(nodes).cast::<[Node<T>; N]>().read(),
+            }
+        }
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns a reference to the first node of the list.
+    #[inline(always)]
+    pub const fn front_node(&self) -> Option<&Node<T>> {
+        self.nodes.first()
+    }
+
+    /// Returns a reference to the last node of the list.
+    #[inline(always)]
+    pub const fn back_node(&self) -> Option<&Node<T>> {
+        self.nodes.last()
+    }
+
+    /// Returns an iterator over the items of the list.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let head = self.nodes.first().map(NonNull::from);
+        let tail = self.nodes.last().map(NonNull::from);
+
+        // The nodes are linked up front to back, and never change.
+        unsafe { Iter::from_raw_parts(head, tail, N) }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a StaticList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Builds a [`StaticList`] at compile time, and returns a `&'static` reference to it.
+///
+/// The type of the items comes first, followed by the items,
+/// which have to be const expressions.
+///
+/// # Examples
+/// ```
+/// use lists::static_list;
+///
+/// let commands = static_list!(&str; "start", "stop");
+/// assert!(commands.iter().eq(&["start", "stop"]));
+///
+/// let empty = static_list!(u32;);
+/// assert!(empty.is_empty());
+/// ```
+#[macro_export]
+macro_rules! static_list {
+    ($ty:ty; $($item:expr),* $(,)?) => {{
+        static LIST: $crate::static_list::StaticList<
+            $ty,
+            { <[&str]>::len(&[$(stringify!($item)),*]) },
+        > = $crate::static_list::StaticList::new([$($item),*], &LIST);
+
+        &LIST
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_static_list() {
+        let names = static_list!(String; String::new(), String::new(),);
+        assert_eq!(names.len(), 2);
+
+        let table = static_list!((u8, &str); (1, "one"), (2, "two"), (3, "three"));
+        let back = table.back_node().unwrap();
+        let middle = unsafe { back.prev().unwrap().as_ref() };
+        assert_eq!(middle.item(), &(2, "two"));
+
+        assert!(table.iter().rev().map(|(n, _)| n).eq(&[3, 2, 1]));
+        assert_eq!(table.iter().len(), 3);
+    }
+}
@@ -0,0 +1,65 @@
+//! Link counters, kept by every list when the `stats` feature is enabled.
+
+/// Counters of the nodes that went through a list.
+///
+/// A node counts as linked whenever it is linked into the list,
+/// and as unlinked whenever it is unlinked from it, or dropped with it.
+/// This counts link events rather than allocations: nodes pushed and popped
+/// with the node level APIs, or moved between lists, are counted as well,
+/// and so is every node that an operation like sorting unlinks and links again.
+/// Either way, `live()` is always the number of nodes the list currently owns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    linked: usize,
+    unlinked: usize,
+    peak_len: usize,
+}
+
+impl Stats {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Self {
+            linked: 0,
+            unlinked: 0,
+            peak_len: 0,
+        }
+    }
+
+    /// Returns the number of times a node was linked into the list.
+    #[inline(always)]
+    pub const fn linked(&self) -> usize {
+        self.linked
+    }
+
+    /// Returns the number of times a node was unlinked from the list.
+    #[inline(always)]
+    pub const fn unlinked(&self) -> usize {
+        self.unlinked
+    }
+
+    /// Returns the number of nodes the list currently owns.
+    #[inline(always)]
+    pub const fn live(&self) -> usize {
+        self.linked.saturating_sub(self.unlinked)
+    }
+
+    /// Returns the highest number of nodes the list ever owned at once.
+    #[inline(always)]
+    pub const fn peak_len(&self) -> usize {
+        self.peak_len
+    }
+
+    #[inline(always)]
+    pub(crate) const fn record_link(&mut self, count: usize) {
+        self.linked += count;
+
+        if self.live() > self.peak_len {
+            self.peak_len = self.live();
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record_unlink(&mut self, count: usize) {
+        self.unlinked += count;
+    }
+}
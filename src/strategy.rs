@@ -0,0 +1,48 @@
+//! [`proptest`] strategies generating lists.
+//!
+//! # Examples
+//! ```
+//! use lists::strategy;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn test_len(list in strategy::doubly_list(any::<u8>(), 0..100)) {
+//!         prop_assert!(list.len() < 100);
+//!     }
+//! }
+//! # test_len();
+//! ```
+
+use proptest::{
+    collection::{self, SizeRange},
+    strategy::Strategy,
+};
+
+use crate::{doublylist::DoublyList, list::List};
+
+/// Returns a strategy generating a `List`,
+/// with its items generated by `element` and its length within `size`.
+///
+/// Lists shrink like vectors do, towards fewer and simpler items.
+#[inline]
+pub fn list<S>(element: S, size: impl Into<SizeRange>) -> impl Strategy<Value = List<S::Value>>
+where
+    S: Strategy,
+{
+    collection::vec(element, size).prop_map(|items| items.into_iter().rev().collect())
+}
+
+/// Returns a strategy generating a `DoublyList`,
+/// with its items generated by `element` and its length within `size`.
+///
+/// Lists shrink like vectors do, towards fewer and simpler items.
+#[inline]
+pub fn doubly_list<S>(
+    element: S,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = DoublyList<S::Value>>
+where
+    S: Strategy,
+{
+    collection::vec(element, size).prop_map(|items| items.into_iter().collect())
+}
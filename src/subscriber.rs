@@ -0,0 +1,278 @@
+//! A list of event subscribers, that unsubscribe by dropping their [`Subscription`].
+
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug},
+    mem,
+};
+use std::rc::{Rc, Weak};
+
+use crate::veclist::{Index, VecList};
+
+type Callback<'a, E> = Rc<dyn Fn(&E) + 'a>;
+
+/*
+ * `emit` never holds a borrow of `inner` while it calls a callback,
+ * so callbacks are free to subscribe, unsubscribe and emit themselves.
+ *
+ * While an emit is running, an unsubscribed callback is only taken out of its slot,
+ * and its index is kept in `pending`: the slot stays linked,
+ * so the cursor of every running emit stays valid, and it skips the empty slot.
+ * The outermost emit unlinks the pending slots once it is done.
+ * Callbacks are dropped outside of any borrow as well,
+ * as dropping one may drop the subscriptions it captured.
+ */
+struct Inner<'a, E> {
+    subscribers: VecList<Option<Callback<'a, E>>>,
+    pending: Vec<Index>,
+    emitting: usize,
+}
+
+/// A list of callbacks, that are called with every event emitted to the list.
+///
+/// Subscribing returns a [`Subscription`], that unsubscribes its callback in O(1) when dropped.
+/// Callbacks may subscribe, unsubscribe, and emit events while an event is being emitted,
+/// including unsubscribing themselves.
+///
+/// # Examples
+/// ```
+/// # use lists::subscriber::SubscriberList;
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let events = SubscriberList::new();
+///
+/// let first = Rc::clone(&log);
+/// let subscription = events.subscribe(move |n: &u32| first.borrow_mut().push(*n));
+/// let second = Rc::clone(&log);
+/// let _kept = events.subscribe(move |n: &u32| second.borrow_mut().push(n * 10));
+///
+/// events.emit(&1);
+/// drop(subscription);
+/// events.emit(&2);
+///
+/// assert_eq!(*log.borrow(), [1, 10, 20]);
+/// ```
+pub struct SubscriberList<'a, E> {
+    inner: Rc<RefCell<Inner<'a, E>>>,
+}
+
+impl<E> Default for SubscriberList<'_, E> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Debug for SubscriberList<'_, E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SubscriberList")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<'a, E> SubscriberList<'a, E> {
+    /// Returns a new list without subscribers.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                subscribers: VecList::new(),
+                pending: Vec::new(),
+                emitting: 0,
+            })),
+        }
+    }
+
+    /// Returns the number of subscribed callbacks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        let inner = self.inner.borrow();
+        inner.subscribers.len() - inner.pending.len()
+    }
+
+    /// Returns `true` if no callbacks are subscribed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Subscribes `callback` after all other callbacks,
+    /// and returns the subscription that keeps it subscribed.
+    ///
+    /// A callback subscribed while an event is being emitted is not called with that event.
+    #[inline]
+    pub fn subscribe<F>(&self, callback: F) -> Subscription<'a, E>
+    where
+        F: Fn(&E) + 'a,
+    {
+        let index = self
+            .inner
+            .borrow_mut()
+            .subscribers
+            .push_back(Some(Rc::new(callback)));
+
+        Subscription {
+            inner: Rc::downgrade(&self.inner),
+            index,
+        }
+    }
+
+    /// Calls every subscribed callback with `event`, in the order they subscribed.
+    ///
+    /// A callback that is unsubscribed before its turn is not called.
+    pub fn emit(&self, event: &E) {
+        let (mut cursor, last) = {
+            let mut inner = self.inner.borrow_mut();
+            inner.emitting += 1;
+            (
+                inner.subscribers.front_index(),
+                inner.subscribers.back_index(),
+            )
+        };
+
+        // Unlinks the pending slots even if a callback panics.
+        let _dispatch = Dispatch(&self.inner);
+
+        while let Some(index) = cursor {
+            let callback = {
+                let inner = self.inner.borrow();
+
+                cursor = if Some(index) == last {
+                    None
+                } else {
+                    inner.subscribers.next_index(index)
+                };
+
+                inner.subscribers.get(index).cloned().flatten()
+            };
+
+            if let Some(callback) = callback {
+                callback(event);
+            }
+        }
+    }
+}
+
+/// Ends an emit, and unlinks the pending slots once no emit is running anymore.
+struct Dispatch<'r, 'a, E>(&'r RefCell<Inner<'a, E>>);
+
+impl<E> Drop for Dispatch<'_, '_, E> {
+    fn drop(&mut self) {
+        let mut inner = self.0.borrow_mut();
+        inner.emitting -= 1;
+
+        if inner.emitting == 0 {
+            // The slots are empty, so unlinking them drops no callbacks.
+            for index in mem::take(&mut inner.pending) {
+                inner.subscribers.remove(index);
+            }
+        }
+    }
+}
+
+/// A subscribed callback of a [`SubscriberList`], that is unsubscribed when this is dropped.
+#[must_use = "dropping a subscription unsubscribes its callback"]
+pub struct Subscription<'a, E> {
+    inner: Weak<RefCell<Inner<'a, E>>>,
+    index: Index,
+}
+
+impl<E> Debug for Subscription<'_, E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("subscribed", &self.is_subscribed())
+            .finish()
+    }
+}
+
+impl<E> Subscription<'_, E> {
+    /// Returns `true` if the callback is still subscribed,
+    /// which is only `false` once the list was dropped.
+    #[inline]
+    pub fn is_subscribed(&self) -> bool {
+        self.inner.upgrade().is_some()
+    }
+
+    /// Unsubscribes the callback, like dropping the subscription does.
+    #[inline(always)]
+    pub fn unsubscribe(self) {}
+}
+
+impl<E> Drop for Subscription<'_, E> {
+    fn drop(&mut self) {
+        let inner = match self.inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+
+        let callback = {
+            let mut inner = inner.borrow_mut();
+
+            if inner.emitting == 0 {
+                inner.subscribers.remove(self.index).flatten()
+            } else {
+                inner.pending.push(self.index);
+                inner.subscribers.get_mut(self.index).and_then(Option::take)
+            }
+        };
+
+        drop(callback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsubscribe_during_emit() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let events = Rc::new(SubscriberList::new());
+
+        // Unsubscribes itself on its first event.
+        let once = Rc::new(RefCell::new(None));
+        let subscription = {
+            let once = Rc::clone(&once);
+            let log = Rc::clone(&log);
+            events.subscribe(move |n: &u32| {
+                log.borrow_mut().push(("once", *n));
+                drop(once.borrow_mut().take());
+            })
+        };
+        *once.borrow_mut() = Some(subscription);
+
+        // Unsubscribes the callback after it, and emits again.
+        let next = Rc::new(RefCell::new(None));
+        let _first = {
+            let next = Rc::clone(&next);
+            let log = Rc::clone(&log);
+            let weak = Rc::downgrade(&events);
+            events.subscribe(move |n: &u32| {
+                log.borrow_mut().push(("first", *n));
+                if *n == 1 {
+                    drop(next.borrow_mut().take());
+                    weak.upgrade().unwrap().emit(&2);
+                }
+            })
+        };
+
+        let second = {
+            let log = Rc::clone(&log);
+            events.subscribe(move |n: &u32| log.borrow_mut().push(("second", *n)))
+        };
+        *next.borrow_mut() = Some(second);
+
+        events.emit(&1);
+        assert_eq!(*log.borrow(), [("once", 1), ("first", 1), ("first", 2)]);
+        assert_eq!(events.len(), 1);
+        assert!(once.borrow().is_none());
+
+        events.emit(&3);
+        assert_eq!(log.borrow().last(), Some(&("first", 3)));
+        assert_eq!(events.inner.borrow().subscribers.len(), 1);
+    }
+}
@@ -0,0 +1,335 @@
+//! A hierarchical timer wheel, built on buckets of [`DoublyList`]s.
+
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+};
+use std::collections::HashMap;
+
+use crate::doublylist::{self, DoublyList, NodeRef};
+
+/// The number of bits of a deadline, that select a bucket within a level.
+const BITS: u32 = 6;
+
+/// The number of buckets per level.
+const SLOTS: usize = 1 << BITS;
+
+/// Enough levels to cover every `u64` deadline.
+const LEVELS: usize = (u64::BITS as usize).div_ceil(BITS as usize);
+
+/// Identifies a timer in a [`TimerWheel`], to cancel it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimerId(u64);
+
+struct Entry<T> {
+    id: TimerId,
+    deadline: u64,
+    item: T,
+}
+
+/// Where an entry is linked into the wheel.
+struct Location<T> {
+    bucket: usize,
+    node: NodeRef<Entry<T>>,
+}
+
+/*
+ * Level `l` holds the timers whose deadline first differs from `now`
+ * in the `l`-th group of `BITS` bits, counting from the lowest group,
+ * in the bucket selected by that group of the deadline.
+ * Since deadlines lie ahead, every occupied bucket of a level
+ * is at or after the bucket `now` falls in.
+ *
+ * Advancing jumps `now` straight to the start of the earliest occupied bucket,
+ * and places its timers again, relative to the new `now`.
+ * That moves them down at least one level, until they fire from level 0,
+ * where every bucket covers a single tick.
+ */
+/// A hierarchical timer wheel, holding items that fire at a deadline.
+///
+/// Time is measured in ticks, of whatever length the user chooses.
+/// Inserting and cancelling a timer take O(1),
+/// and every timer moves down the levels of the wheel at most once per level
+/// before it fires, however far ahead it was scheduled.
+///
+/// # Examples
+/// ```
+/// # use lists::timer::TimerWheel;
+/// let mut wheel = TimerWheel::new(0);
+///
+/// let retry = wheel.insert(30, "retry");
+/// wheel.insert(10, "ping");
+/// wheel.insert(5_000, "expire");
+///
+/// assert_eq!(wheel.advance(20).map(|(_, item)| item).collect::<Vec<_>>(), ["ping"]);
+/// assert_eq!(wheel.cancel(retry), Some("retry"));
+/// assert_eq!(wheel.advance(10_000).map(|(_, item)| item).collect::<Vec<_>>(), ["expire"]);
+/// ```
+pub struct TimerWheel<T> {
+    buckets: Box<[DoublyList<Entry<T>>]>,
+
+    /// One bit per bucket of every level, set when the bucket is not empty.
+    occupied: [u64; LEVELS],
+
+    timers: HashMap<TimerId, Location<T>>,
+    now: u64,
+    next_id: u64,
+}
+
+impl<T> Debug for TimerWheel<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimerWheel")
+            .field("now", &self.now)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T> TimerWheel<T> {
+    /// Returns a new empty wheel, starting at the tick `now`.
+    pub fn new(now: u64) -> Self {
+        Self {
+            buckets: (0..LEVELS * SLOTS).map(|_| DoublyList::new()).collect(),
+            occupied: [0; LEVELS],
+            timers: HashMap::new(),
+            now,
+            next_id: 0,
+        }
+    }
+
+    /// Returns the current tick of the wheel.
+    #[inline(always)]
+    pub const fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Returns the number of pending timers.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Returns `true` if no timers are pending.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Returns the level and slot of the bucket for `deadline`.
+    #[inline(always)]
+    fn bucket_for(&self, deadline: u64) -> (usize, usize) {
+        // Timers that are already due fire from the current bucket of level 0.
+        let deadline = deadline.max(self.now);
+        let level = match deadline ^ self.now {
+            0 => 0,
+            diff => ((u64::BITS - 1 - diff.leading_zeros()) / BITS) as usize,
+        };
+
+        let slot = (deadline >> (level as u32 * BITS)) as usize & (SLOTS - 1);
+        (level, slot)
+    }
+
+    /// Links the node of `entry` into its bucket, and records where.
+    fn place(&mut self, entry: Box<doublylist::Node<Entry<T>>>) {
+        let (level, slot) = self.bucket_for(entry.item().deadline);
+        let id = entry.item().id;
+        let bucket = level * SLOTS + slot;
+
+        let node = self.buckets[bucket].push_back_node_ref(entry);
+        self.occupied[level] |= 1 << slot;
+        self.timers.insert(id, Location { bucket, node });
+    }
+
+    /// Schedules `item` to fire at the tick `deadline`, and returns its id.
+    ///
+    /// A deadline that has already passed fires on the next [`advance`](TimerWheel::advance).
+    pub fn insert(&mut self, deadline: u64, item: T) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+
+        self.place(doublylist::Node::boxed(Entry { id, deadline, item }));
+        id
+    }
+
+    /// Returns the deadline of the pending timer `id`.
+    #[inline]
+    pub fn deadline(&self, id: TimerId) -> Option<u64> {
+        let location = self.timers.get(&id)?;
+
+        // The map only holds nodes linked into their bucket.
+        Some(unsafe { self.buckets[location.bucket].get(location.node) }.deadline)
+    }
+
+    /// Cancels the pending timer `id`, and returns its item.
+    pub fn cancel(&mut self, id: TimerId) -> Option<T> {
+        let Location { bucket, node } = self.timers.remove(&id)?;
+
+        // The map only holds nodes linked into their bucket.
+        let entry = unsafe { self.buckets[bucket].remove(node) };
+
+        if self.buckets[bucket].is_empty() {
+            self.occupied[bucket / SLOTS] &= !(1 << (bucket % SLOTS));
+        }
+
+        Some(entry.item)
+    }
+
+    /// Returns the level, slot and start tick of the earliest occupied bucket.
+    fn earliest_bucket(&self) -> Option<(usize, usize, u64)> {
+        (0..LEVELS)
+            .filter_map(|level| {
+                let shift = level as u32 * BITS;
+                let current = (self.now >> shift) as usize & (SLOTS - 1);
+                let ahead = self.occupied[level] & (!0 << current);
+
+                if ahead == 0 {
+                    return None;
+                }
+
+                let slot = ahead.trailing_zeros() as usize;
+                let above = self.now.checked_shr(shift + BITS).unwrap_or(0);
+                let start = above.checked_shl(shift + BITS).unwrap_or(0) | ((slot as u64) << shift);
+                Some((level, slot, start.max(self.now)))
+            })
+            .min_by_key(|&(_, _, start)| start)
+    }
+
+    /// Advances the wheel to the tick `now`, and returns the timers that fired on the way,
+    /// in the order of their deadlines.
+    ///
+    /// Moving backwards in time does nothing.
+    pub fn advance(&mut self, now: u64) -> Fired<T> {
+        let mut fired = DoublyList::new();
+
+        while let Some((level, slot, start)) = self.earliest_bucket() {
+            if start > now {
+                break;
+            }
+
+            self.now = start;
+            self.occupied[level] &= !(1 << slot);
+
+            let mut bucket = core::mem::take(&mut self.buckets[level * SLOTS + slot]);
+
+            while let Some(entry) = bucket.pop_front_node() {
+                if level == 0 {
+                    self.timers.remove(&entry.item().id);
+                    fired.push_back_node(entry);
+                } else {
+                    self.place(entry);
+                }
+            }
+        }
+
+        self.now = self.now.max(now);
+        Fired {
+            entries: fired.into_iter(),
+        }
+    }
+}
+
+/// An iterator over the timers fired by [`TimerWheel::advance`].
+pub struct Fired<T> {
+    entries: doublylist::IntoIter<Entry<T>>,
+}
+
+impl<T> Debug for Fired<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Fired").field(&self.entries.len()).finish()
+    }
+}
+
+impl<T> Iterator for Fired<T> {
+    type Item = (TimerId, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| (entry.id, entry.item))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Fired<T> {}
+
+impl<T> FusedIterator for Fired<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fires_in_order() {
+        let start = 1_000_000;
+        let mut wheel = TimerWheel::new(start);
+        let mut expected = Vec::new();
+
+        for n in 0..2000u64 {
+            let deadline = start + (n * 7919) % 300_000;
+            let id = wheel.insert(deadline, n);
+
+            if n % 5 == 0 {
+                assert_eq!(wheel.cancel(id), Some(n));
+                assert_eq!(wheel.cancel(id), None);
+            } else {
+                expected.push((deadline, n));
+            }
+        }
+
+        let overdue = wheel.insert(0, u64::MAX);
+        assert_eq!(wheel.deadline(overdue), Some(0));
+        expected.insert(0, (start, u64::MAX));
+        expected.sort();
+
+        let mut fired = Vec::new();
+        let mut now = start;
+
+        while !wheel.is_empty() {
+            for (_, item) in wheel.advance(now) {
+                fired.push(item);
+            }
+
+            assert!(fired.len() == expected.partition_point(|&(deadline, _)| deadline <= now));
+            now += 997;
+        }
+
+        let deadline = |item: u64| match item {
+            u64::MAX => start,
+            n => start + (n * 7919) % 300_000,
+        };
+        assert!(fired.windows(2).all(|w| deadline(w[0]) <= deadline(w[1])));
+
+        fired.sort();
+        let mut items = expected.iter().map(|&(_, item)| item).collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(fired, items);
+    }
+
+    #[test]
+    fn test_far_deadlines() {
+        let mut wheel = TimerWheel::new(u64::MAX - 10);
+        wheel.insert(u64::MAX, "last");
+        wheel.insert(u64::MAX - 5, "soon");
+
+        assert!(wheel.advance(u64::MAX - 6).next().is_none());
+        assert_eq!(
+            wheel.advance(u64::MAX - 5).next().map(|(_, item)| item),
+            Some("soon")
+        );
+        assert_eq!(
+            wheel.advance(u64::MAX).next().map(|(_, item)| item),
+            Some("last")
+        );
+        assert!(wheel.is_empty());
+
+        let mut wheel = TimerWheel::new(0);
+        wheel.insert(u64::MAX, ());
+        assert_eq!(wheel.advance(u64::MAX - 1).len(), 0);
+        assert_eq!(wheel.advance(u64::MAX).len(), 1);
+    }
+}
@@ -0,0 +1,842 @@
+//! A doubly linked list, whose nodes are stored in a `Vec`.
+
+use core::{
+    convert::TryFrom,
+    fmt::{self, Debug},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+    mem,
+};
+
+/// The index used as a link to no slot.
+const NIL: u32 = u32::MAX;
+
+/// A handle to an item of a [`VecList`].
+///
+/// A handle stays valid until its item is removed,
+/// however the list is changed around it.
+/// Afterwards it refers to no item at all,
+/// even when the slot of the item is reused for another one:
+/// every slot counts how often it was vacated,
+/// and a handle only matches the count it was created with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Index {
+    slot: u32,
+    generation: u32,
+}
+
+#[derive(Clone)]
+enum Slot<T> {
+    Occupied {
+        item: T,
+        next: u32,
+        prev: u32,
+        generation: u32,
+    },
+    Vacant {
+        next_free: u32,
+        generation: u32,
+    },
+}
+
+impl<T> Slot<T> {
+    #[inline(always)]
+    fn generation(&self) -> u32 {
+        match self {
+            Slot::Occupied { generation, .. } | Slot::Vacant { generation, .. } => *generation,
+        }
+    }
+
+    /// Returns the item of the slot, if it is occupied by the item of `index`.
+    #[inline(always)]
+    fn item_of(&self, index: Index) -> Option<&T> {
+        match self {
+            Slot::Occupied {
+                item, generation, ..
+            } if *generation == index.generation => Some(item),
+            _ => None,
+        }
+    }
+
+    /// Returns the item of the slot, if it is occupied by the item of `index`.
+    #[inline(always)]
+    fn item_of_mut(&mut self, index: Index) -> Option<&mut T> {
+        match self {
+            Slot::Occupied {
+                item, generation, ..
+            } if *generation == index.generation => Some(item),
+            _ => None,
+        }
+    }
+}
+
+/// A doubly linked list, whose nodes are stored in a `Vec` and link to each other by `u32` index.
+///
+/// Removed nodes leave a hole in the `Vec`, that the next insertion fills,
+/// so indices of other nodes never change.
+/// Compared to a [`DoublyList`](crate::doublylist::DoublyList),
+/// the nodes are close together in memory, which makes walking the list much faster,
+/// and pushing mostly reuses memory instead of allocating.
+/// Every item is addressed by an [`Index`] handle, which is safe to use,
+/// even after its item was removed.
+///
+/// # Examples
+/// ```
+/// # use lists::veclist::VecList;
+/// let mut list = VecList::new();
+///
+/// let b = list.push_back('b');
+/// list.push_back('d');
+/// list.insert_before(b, 'a').unwrap();
+/// let c = list.insert_after(b, 'c').unwrap();
+///
+/// assert_eq!(list.remove(c), Some('c'));
+/// assert!(list.iter().eq(&['a', 'b', 'd']));
+///
+/// // The slot of `c` is reused, but `c` does not refer to the new item.
+/// list.push_back('e');
+/// assert_eq!(list.get(c), None);
+/// ```
+#[derive(Clone)]
+pub struct VecList<T> {
+    slots: Vec<Slot<T>>,
+    head: u32,
+    tail: u32,
+
+    /// The first vacant slot, which links to the next one.
+    free: u32,
+    len: usize,
+}
+
+impl<T> Default for VecList<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for VecList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> VecList<T> {
+    /// Returns a new empty list.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            free: NIL,
+            len: 0,
+        }
+    }
+
+    /// Returns a new empty list, with room for `capacity` items.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Returns the number of items in the list.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of items the list can hold without reallocating.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Removes all items from the list.
+    ///
+    /// The slots are kept, so that handles to the removed items stay stale.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Returns the links of the item of `index`.
+    #[inline(always)]
+    fn links(&self, index: Index) -> Option<(u32, u32)> {
+        match self.slots.get(index.slot as usize)? {
+            Slot::Occupied {
+                next,
+                prev,
+                generation,
+                ..
+            } if *generation == index.generation => Some((*next, *prev)),
+            _ => None,
+        }
+    }
+
+    /// Returns the handle of the item in the occupied slot `slot`, or `None` for [`NIL`].
+    #[inline(always)]
+    fn index(&self, slot: u32) -> Option<Index> {
+        match slot {
+            NIL => None,
+            slot => Some(Index {
+                slot,
+                generation: self.slots[slot as usize].generation(),
+            }),
+        }
+    }
+
+    #[inline(always)]
+    fn set_next(&mut self, slot: u32, to: u32) {
+        match slot {
+            NIL => self.head = to,
+            slot => match &mut self.slots[slot as usize] {
+                Slot::Occupied { next, .. } => *next = to,
+                Slot::Vacant { .. } => unreachable!(),
+            },
+        }
+    }
+
+    #[inline(always)]
+    fn set_prev(&mut self, slot: u32, to: u32) {
+        match slot {
+            NIL => self.tail = to,
+            slot => match &mut self.slots[slot as usize] {
+                Slot::Occupied { prev, .. } => *prev = to,
+                Slot::Vacant { .. } => unreachable!(),
+            },
+        }
+    }
+
+    /// Moves `item` into a vacant slot linked in between `prev` and `next`.
+    fn link(&mut self, item: T, prev: u32, next: u32) -> Index {
+        let index = match self.free {
+            NIL => {
+                let slot = u32::try_from(self.slots.len())
+                    .ok()
+                    .filter(|&slot| slot != NIL)
+                    .expect("capacity overflow");
+
+                self.slots.push(Slot::Vacant {
+                    next_free: NIL,
+                    generation: 0,
+                });
+                Index {
+                    slot,
+                    generation: 0,
+                }
+            }
+            slot => match self.slots[slot as usize] {
+                Slot::Vacant {
+                    next_free,
+                    generation,
+                } => {
+                    self.free = next_free;
+                    Index { slot, generation }
+                }
+                Slot::Occupied { .. } => unreachable!(),
+            },
+        };
+
+        self.slots[index.slot as usize] = Slot::Occupied {
+            item,
+            next,
+            prev,
+            generation: index.generation,
+        };
+        self.set_next(prev, index.slot);
+        self.set_prev(next, index.slot);
+        self.len += 1;
+        index
+    }
+
+    /// Unlinks the occupied slot `slot`, and returns its item.
+    fn unlink(&mut self, slot: u32) -> T {
+        let vacant = Slot::Vacant {
+            next_free: self.free,
+            // Wrapping around only revives handles
+            // that outlived 2^32 items in the same slot.
+            generation: self.slots[slot as usize].generation().wrapping_add(1),
+        };
+
+        match mem::replace(&mut self.slots[slot as usize], vacant) {
+            Slot::Occupied {
+                item, next, prev, ..
+            } => {
+                self.set_next(prev, next);
+                self.set_prev(next, prev);
+                self.free = slot;
+                self.len -= 1;
+                item
+            }
+            Slot::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    /// Pushes `item` to the front of the list, and returns its handle.
+    #[inline]
+    pub fn push_front(&mut self, item: T) -> Index {
+        self.link(item, NIL, self.head)
+    }
+
+    /// Pushes `item` to the back of the list, and returns its handle.
+    #[inline]
+    pub fn push_back(&mut self, item: T) -> Index {
+        self.link(item, self.tail, NIL)
+    }
+
+    /// Inserts `item` right after the item of `at`, and returns its handle.
+    ///
+    /// Returns `item` back if `at` does not refer to an item of the list.
+    #[inline]
+    pub fn insert_after(&mut self, at: Index, item: T) -> Result<Index, T> {
+        match self.links(at) {
+            Some((next, _)) => Ok(self.link(item, at.slot, next)),
+            None => Err(item),
+        }
+    }
+
+    /// Inserts `item` right before the item of `at`, and returns its handle.
+    ///
+    /// Returns `item` back if `at` does not refer to an item of the list.
+    #[inline]
+    pub fn insert_before(&mut self, at: Index, item: T) -> Result<Index, T> {
+        match self.links(at) {
+            Some((_, prev)) => Ok(self.link(item, prev, at.slot)),
+            None => Err(item),
+        }
+    }
+
+    /// Removes the item of `index` from the list, and returns it.
+    #[inline]
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        self.links(index)?;
+        Some(self.unlink(index.slot))
+    }
+
+    /// Pops the front item of the list.
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        match self.head {
+            NIL => None,
+            head => Some(self.unlink(head)),
+        }
+    }
+
+    /// Pops the back item of the list.
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        match self.tail {
+            NIL => None,
+            tail => Some(self.unlink(tail)),
+        }
+    }
+
+    /// Relinks the item of `index` in between `prev` and `next`, which must not be `index`.
+    fn relink(&mut self, index: Index, prev: u32, next: u32) -> bool {
+        let (old_next, old_prev) = match self.links(index) {
+            Some(links) => links,
+            None => return false,
+        };
+
+        self.set_next(old_prev, old_next);
+        self.set_prev(old_next, old_prev);
+
+        match &mut self.slots[index.slot as usize] {
+            Slot::Occupied {
+                next: n, prev: p, ..
+            } => {
+                *n = next;
+                *p = prev;
+            }
+            Slot::Vacant { .. } => unreachable!(),
+        }
+
+        self.set_next(prev, index.slot);
+        self.set_prev(next, index.slot);
+        true
+    }
+
+    /// Moves the item of `index` to the front of the list.
+    ///
+    /// Returns `false` if `index` does not refer to an item of the list.
+    pub fn move_to_front(&mut self, index: Index) -> bool {
+        if self.head == index.slot {
+            return self.links(index).is_some();
+        }
+
+        self.relink(index, NIL, self.head)
+    }
+
+    /// Moves the item of `index` to the back of the list.
+    ///
+    /// Returns `false` if `index` does not refer to an item of the list.
+    pub fn move_to_back(&mut self, index: Index) -> bool {
+        if self.tail == index.slot {
+            return self.links(index).is_some();
+        }
+
+        self.relink(index, self.tail, NIL)
+    }
+
+    /// Returns a reference to the item of `index`.
+    #[inline]
+    pub fn get(&self, index: Index) -> Option<&T> {
+        self.slots.get(index.slot as usize)?.item_of(index)
+    }
+
+    /// Returns a mutable reference to the item of `index`.
+    #[inline]
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        self.slots.get_mut(index.slot as usize)?.item_of_mut(index)
+    }
+
+    /// Returns `true` if `index` refers to an item of the list.
+    #[inline]
+    pub fn contains(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Returns the handle of the front item.
+    #[inline]
+    pub fn front_index(&self) -> Option<Index> {
+        self.index(self.head)
+    }
+
+    /// Returns the handle of the back item.
+    #[inline]
+    pub fn back_index(&self) -> Option<Index> {
+        self.index(self.tail)
+    }
+
+    /// Returns the handle of the item after the item of `index`.
+    #[inline]
+    pub fn next_index(&self, index: Index) -> Option<Index> {
+        self.index(self.links(index)?.0)
+    }
+
+    /// Returns the handle of the item before the item of `index`.
+    #[inline]
+    pub fn prev_index(&self, index: Index) -> Option<Index> {
+        self.index(self.links(index)?.1)
+    }
+
+    /// Returns a reference to the front item.
+    #[inline]
+    pub fn peek_front(&self) -> Option<&T> {
+        self.get(self.front_index()?)
+    }
+
+    /// Returns a mutable reference to the front item.
+    #[inline]
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.front_index()?)
+    }
+
+    /// Returns a reference to the back item.
+    #[inline]
+    pub fn peek_back(&self) -> Option<&T> {
+        self.get(self.back_index()?)
+    }
+
+    /// Returns a mutable reference to the back item.
+    #[inline]
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.back_index()?)
+    }
+
+    /// Returns an iterator over the items of the list.
+    #[inline(always)]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: &self.slots,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    /// Returns an iterator over the handles and items of the list.
+    #[inline(always)]
+    pub fn indexed(&self) -> Indexed<'_, T> {
+        Indexed { iter: self.iter() }
+    }
+
+    /// Returns an iterator over mutable references to the items of the list.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.as_mut_ptr(),
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Extend<T> for VecList<T> {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for VecList<T> {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<'a, T> IntoIterator for &'a VecList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut VecList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for VecList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+/// An iterator over the items of a [`VecList`].
+pub struct Iter<'a, T> {
+    slots: &'a [Slot<T>],
+    front: u32,
+    back: u32,
+    len: usize,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    #[inline(always)]
+    fn occupied(&self, slot: u32) -> (Index, &'a T, u32, u32) {
+        match &self.slots[slot as usize] {
+            Slot::Occupied {
+                item,
+                next,
+                prev,
+                generation,
+            } => {
+                let index = Index {
+                    slot,
+                    generation: *generation,
+                };
+                (index, item, *next, *prev)
+            }
+            Slot::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn next_indexed(&mut self) -> Option<(Index, &'a T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (index, item, next, _) = self.occupied(self.front);
+        self.front = next;
+        self.len -= 1;
+        Some((index, item))
+    }
+
+    #[inline]
+    fn next_back_indexed(&mut self) -> Option<(Index, &'a T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (index, item, _, prev) = self.occupied(self.back);
+        self.back = prev;
+        self.len -= 1;
+        Some((index, item))
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_indexed().map(|(_, item)| item)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_indexed().map(|(_, item)| item)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the handles and items of a [`VecList`].
+#[derive(Clone)]
+pub struct Indexed<'a, T> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Indexed<'a, T> {
+    type Item = (Index, &'a T);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_indexed()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Indexed<'_, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back_indexed()
+    }
+}
+
+impl<T> ExactSizeIterator for Indexed<'_, T> {}
+
+impl<T> FusedIterator for Indexed<'_, T> {}
+
+/// An iterator over mutable references to the items of a [`VecList`].
+pub struct IterMut<'a, T> {
+    slots: *mut Slot<T>,
+    front: u32,
+    back: u32,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> IterMut<'a, T> {
+    /// Returns the item and links of `slot`.
+    ///
+    /// # Safety
+    /// `slot` must be occupied, and not be returned before.
+    #[inline(always)]
+    unsafe fn occupied(&mut self, slot: u32) -> (&'a mut T, u32, u32) {
+        match &mut *self.slots.add(slot as usize) {
+            Slot::Occupied {
+                item, next, prev, ..
+            } => (item, *next, *prev),
+            Slot::Vacant { .. } => unreachable!(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // The `len` items from `front` were not returned yet.
+        let (item, next, _) = unsafe { self.occupied(self.front) };
+        self.front = next;
+        self.len -= 1;
+        Some(item)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // The `len` items up to `back` were not returned yet.
+        let (item, _, prev) = unsafe { self.occupied(self.back) };
+        self.back = prev;
+        self.len -= 1;
+        Some(item)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the items of a [`VecList`].
+pub struct IntoIter<T> {
+    list: VecList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against_vec() {
+        let mut list = VecList::new();
+        let mut model = Vec::new();
+
+        for step in 0..500usize {
+            let at = (step * 7919) % (model.len() + 1);
+
+            match model.get(at) {
+                Some(&(index, _)) => {
+                    let new = list.insert_before(index, step).unwrap();
+                    model.insert(at, (new, step));
+                }
+                None => model.push((list.push_back(step), step)),
+            }
+
+            if step % 3 == 0 {
+                let (index, item) = model.remove((step * 104_729) % model.len());
+                assert_eq!(list.remove(index), Some(item));
+                assert_eq!(list.remove(index), None);
+            }
+        }
+
+        assert!(list.iter().eq(model.iter().map(|(_, item)| item)));
+        assert!(list
+            .iter()
+            .rev()
+            .eq(model.iter().rev().map(|(_, item)| item)));
+        assert!(list
+            .indexed()
+            .map(|(index, _)| index)
+            .eq(model.iter().map(|&(index, _)| index)));
+        assert!(list.slots.len() <= 500 - 500 / 3 + 1);
+
+        for item in &mut list {
+            *item *= 2;
+        }
+
+        assert!(list
+            .into_iter()
+            .eq(model.into_iter().map(|(_, item)| item * 2)));
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut list = (0..4).collect::<VecList<_>>();
+        let front = list.front_index().unwrap();
+        let second = list.next_index(front).unwrap();
+
+        assert!(list.move_to_back(front));
+        assert!(list.iter().eq(&[1, 2, 3, 0]));
+        assert_eq!(list.front_index(), Some(second));
+        assert_eq!(list.prev_index(second), None);
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.get(second), None);
+    }
+
+    #[test]
+    fn test_stale_handles() {
+        let mut list = VecList::new();
+        let first = list.push_back(1);
+        let second = list.push_back(2);
+
+        assert_eq!(list.remove(first), Some(1));
+        let reused = list.push_front(3);
+        assert_eq!(reused.slot, first.slot);
+
+        assert!(!list.contains(first));
+        assert_eq!(list.get_mut(first), None);
+        assert_eq!(list.insert_after(first, 4), Err(4));
+        assert!(!list.move_to_back(first));
+        assert_eq!(list.next_index(first), None);
+        assert_eq!(list.remove(first), None);
+
+        assert!(list.move_to_back(reused));
+        assert!(list.move_to_back(reused));
+        assert_eq!(list.get(reused), Some(&3));
+        assert_eq!(list.front_index(), Some(second));
+        assert!(list.indexed().eq(vec![(second, &2), (reused, &3)]));
+    }
+}
@@ -0,0 +1,256 @@
+//! A zipper over a [`List`], for safe navigation in both directions.
+
+use core::fmt::{self, Debug};
+
+use crate::list::{List, Node};
+
+/// A [`List`] with a focus, that moves left and right in O(1).
+///
+/// The zipper keeps the items left of the focus in a list of its own,
+/// nearest item first, and the focus with the items right of it in another.
+/// Moving the focus relinks a single node from one list to the other,
+/// so a zipper navigates like a cursor over a doubly linked list,
+/// without any unsafe code or handles.
+///
+/// The focus can also be just past the last item, where there is no item to focus on,
+/// and inserting appends to the list.
+///
+/// # Examples
+/// ```
+/// # use lists::{list::List, zipper::Zipper};
+/// let mut list = List::new();
+/// list.push(3);
+/// list.push(1);
+///
+/// let mut zipper = Zipper::new(list);
+/// assert_eq!(zipper.focus(), Some(&1));
+///
+/// assert!(zipper.move_right());
+/// zipper.insert(2);
+/// assert_eq!(zipper.replace(20), Some(2));
+///
+/// assert!(zipper.move_left());
+/// assert_eq!(zipper.into_list(), [1, 20, 3]);
+/// ```
+pub struct Zipper<T: ?Sized> {
+    /// The items left of the focus, nearest first.
+    left: List<T>,
+
+    /// The focus, followed by the items right of it.
+    right: List<T>,
+
+    position: usize,
+}
+
+impl<T: ?Sized> Default for Zipper<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(List::new())
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Zipper<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Zipper")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+impl<T: ?Sized> From<List<T>> for Zipper<T> {
+    #[inline(always)]
+    fn from(list: List<T>) -> Self {
+        Self::new(list)
+    }
+}
+
+impl<T: ?Sized> Zipper<T> {
+    /// Returns a zipper over `list`, focused on its head.
+    #[inline(always)]
+    pub const fn new(list: List<T>) -> Self {
+        Self {
+            left: List::new(),
+            right: list,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of items left of the focus.
+    #[inline(always)]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns `true` if there are no items left of the focus.
+    #[inline(always)]
+    pub fn is_at_start(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /// Returns `true` if the focus is past the last item.
+    #[inline(always)]
+    pub fn is_at_end(&self) -> bool {
+        self.right.is_empty()
+    }
+
+    /// Returns a reference to the item in focus.
+    #[inline(always)]
+    pub fn focus(&self) -> Option<&T> {
+        self.right.peek()
+    }
+
+    /// Returns a mutable reference to the item in focus.
+    #[inline(always)]
+    pub fn focus_mut(&mut self) -> Option<&mut T> {
+        self.right.peek_mut()
+    }
+
+    /// Returns a reference to the item right before the focus.
+    #[inline(always)]
+    pub fn peek_left(&self) -> Option<&T> {
+        self.left.peek()
+    }
+
+    /// Moves the focus one item to the left.
+    /// Returns `false` if the focus already was on the first item.
+    #[inline]
+    pub fn move_left(&mut self) -> bool {
+        match self.left.pop_node() {
+            None => false,
+            Some(node) => {
+                self.right.push_node(node);
+                self.position -= 1;
+                true
+            }
+        }
+    }
+
+    /// Moves the focus one item to the right.
+    /// Returns `false` if the focus already was past the last item.
+    #[inline]
+    pub fn move_right(&mut self) -> bool {
+        match self.right.pop_node() {
+            None => false,
+            Some(node) => {
+                self.left.push_node(node);
+                self.position += 1;
+                true
+            }
+        }
+    }
+
+    /// Moves the focus to the first item.
+    #[inline]
+    pub fn move_to_start(&mut self) {
+        while self.move_left() {}
+    }
+
+    /// Moves the focus past the last item.
+    #[inline]
+    pub fn move_to_end(&mut self) {
+        while self.move_right() {}
+    }
+
+    /// Inserts `node` at the focus, moving the item in focus one to the right.
+    /// The item of `node` is in focus afterwards.
+    #[inline(always)]
+    pub fn insert_node(&mut self, node: Box<Node<T>>) {
+        self.right.push_node(node);
+    }
+
+    /// Removes the node in focus, and returns it.
+    /// The item right of it is in focus afterwards.
+    #[inline(always)]
+    pub fn remove_node(&mut self) -> Option<Box<Node<T>>> {
+        self.right.pop_node()
+    }
+
+    /// Returns the whole list, in order.
+    #[inline]
+    pub fn into_list(mut self) -> List<T> {
+        self.move_to_start();
+        self.right
+    }
+}
+
+impl<T> Zipper<T> {
+    /// Inserts `item` at the focus, moving the item in focus one to the right.
+    /// `item` is in focus afterwards.
+    #[inline(always)]
+    pub fn insert(&mut self, item: T) {
+        self.right.push(item);
+    }
+
+    /// Removes the item in focus, and returns it.
+    /// The item right of it is in focus afterwards.
+    #[inline(always)]
+    pub fn remove(&mut self) -> Option<T> {
+        self.right.pop()
+    }
+
+    /// Replaces the item in focus with `item`, and returns the old item.
+    /// Past the last item, `item` is appended instead.
+    #[inline]
+    pub fn replace(&mut self, item: T) -> Option<T> {
+        match self.right.peek_mut() {
+            Some(focus) => Some(core::mem::replace(focus, item)),
+            None => {
+                self.right.push(item);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_against_vec() {
+        let mut zipper = Zipper::new(List::new());
+        let mut model = Vec::new();
+        let mut cursor = 0;
+
+        for n in 0..2000u32 {
+            match n % 6 {
+                0 | 1 => {
+                    zipper.insert(n);
+                    model.insert(cursor, n);
+                }
+                2 => {
+                    assert_eq!(zipper.move_left(), cursor > 0);
+                    cursor = cursor.saturating_sub(1);
+                }
+                3 => {
+                    assert_eq!(zipper.move_right(), cursor < model.len());
+                    cursor = (cursor + 1).min(model.len());
+                }
+                4 => {
+                    let expected = match model.get_mut(cursor) {
+                        Some(item) => Some(core::mem::replace(item, n)),
+                        None => {
+                            model.push(n);
+                            None
+                        }
+                    };
+                    assert_eq!(zipper.replace(n), expected);
+                }
+                _ => {
+                    let expected = (cursor < model.len()).then(|| model.remove(cursor));
+                    assert_eq!(zipper.remove(), expected);
+                }
+            }
+
+            assert_eq!(zipper.position(), cursor);
+            assert_eq!(zipper.focus(), model.get(cursor));
+        }
+
+        zipper.move_to_end();
+        assert!(zipper.is_at_end());
+        assert_eq!(zipper.peek_left(), model.last());
+        assert_eq!(zipper.into_list(), model);
+    }
+}